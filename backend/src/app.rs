@@ -12,6 +12,18 @@ pub fn create_app(app_state: AppState) -> Router {
     let api_routes = Router::new()
         .merge(routes::health::routes())
         .merge(routes::node::routes())
+        .merge(routes::network::routes())
+        .merge(routes::chain::routes())
+        .merge(routes::headers::routes())
+        .merge(routes::address::routes())
+        .merge(routes::utxo::routes())
+        .merge(routes::regtest::routes())
+        .merge(routes::scenario::routes())
+        .merge(routes::managed_node::routes())
+        .merge(routes::metrics::routes())
+        .merge(routes::search::routes())
+        .merge(routes::wallet::routes())
+        .merge(routes::send::routes())
         .merge(routes::config::routes());
 
     Router::new()
@@ -0,0 +1,54 @@
+use axum::{
+    extract::FromRequestParts,
+    http::{header, request::Parts},
+};
+use bitcoin::hashes::{Hash, sha256};
+use std::sync::Arc;
+use subtle::ConstantTimeEq;
+
+use crate::config::constants::env_keys;
+use crate::errors::AppError;
+use crate::state::app_state::AppState;
+
+/// Extractor that guards endpoints which change node state.
+///
+/// Requests must carry `Authorization: Bearer <ADMIN_API_TOKEN>`.
+/// When no token is configured, write actions are refused entirely.
+pub struct WriteAccess;
+
+impl FromRequestParts<Arc<AppState>> for WriteAccess {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        let expected = state.admin_token.as_deref().ok_or_else(|| {
+            AppError::Forbidden(format!(
+                "Write actions are disabled; set {} to enable them",
+                env_keys::ADMIN_API_TOKEN
+            ))
+        })?;
+
+        let provided = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+
+        match provided {
+            Some(token) if tokens_match(token, expected) => Ok(WriteAccess),
+            _ => Err(AppError::Forbidden(
+                "A valid admin token is required for this action".to_string(),
+            )),
+        }
+    }
+}
+
+/// Compare tokens in constant time. Hashing first gives both sides the same
+/// length, so the comparison doesn't reveal the expected token's length either.
+fn tokens_match(provided: &str, expected: &str) -> bool {
+    let provided = sha256::Hash::hash(provided.as_bytes());
+    let expected = sha256::Hash::hash(expected.as_bytes());
+    provided.as_byte_array().ct_eq(expected.as_byte_array()).into()
+}
@@ -5,4 +5,34 @@ pub mod env_keys {
     pub const BTC_RPC_USER: &str = "BTC_RPC_USER";
     pub const BTC_RPC_PASS: &str = "BTC_RPC_PASS";
     pub const DATABASE_URL: &str = "DATABASE_URL";
+    /// Optional bearer token required by endpoints that change node state
+    pub const ADMIN_API_TOKEN: &str = "ADMIN_API_TOKEN";
+    /// Seconds between chain tip polls of each configured node
+    pub const CHAIN_MONITOR_INTERVAL_SECS: &str = "CHAIN_MONITOR_INTERVAL_SECS";
+    /// Seconds between header index polls of the active node
+    pub const HEADER_INDEX_POLL_SECS: &str = "HEADER_INDEX_POLL_SECS";
+    /// Enables the optional scriptPubKey address index ("true" / "false")
+    pub const ADDRESS_INDEX_ENABLED: &str = "ADDRESS_INDEX_ENABLED";
+    /// Seconds between address index polls of the active node
+    pub const ADDRESS_INDEX_POLL_SECS: &str = "ADDRESS_INDEX_POLL_SECS";
+    /// Path to the bitcoind binary used for the managed local node
+    pub const BITCOIND_PATH: &str = "BITCOIND_PATH";
+    /// Directory holding the managed node's per-network datadirs
+    pub const MANAGED_NODE_DATA_DIR: &str = "MANAGED_NODE_DATA_DIR";
+    /// Seconds to wait for the managed node to finish warmup
+    pub const MANAGED_NODE_STARTUP_TIMEOUT_SECS: &str = "MANAGED_NODE_STARTUP_TIMEOUT_SECS";
+    /// Seconds between metrics samples of each configured node
+    pub const METRICS_SAMPLE_INTERVAL_SECS: &str = "METRICS_SAMPLE_INTERVAL_SECS";
+    /// Days of metrics samples kept before pruning
+    pub const METRICS_RETENTION_DAYS: &str = "METRICS_RETENTION_DAYS";
+    /// Default and maximum seconds a wallet stays unlocked
+    pub const WALLET_UNLOCK_TIMEOUT_SECS: &str = "WALLET_UNLOCK_TIMEOUT_SECS";
+    /// Seconds between syncs of wallets into node wallets on the active node (0 disables)
+    pub const NODE_WALLET_SYNC_SECS: &str = "NODE_WALLET_SYNC_SECS";
+    /// Where wallet balances and UTXOs come from ("node_wallet" / "address_index")
+    pub const WALLET_DATA_SOURCE: &str = "WALLET_DATA_SOURCE";
+    /// Default feerate (sat/vB) coin selection expects future spends to pay
+    pub const LONG_TERM_FEERATE: &str = "LONG_TERM_FEERATE";
+    /// Node wallet that regtest mining and the faucet pay from and to
+    pub const REGTEST_MINER_WALLET: &str = "REGTEST_MINER_WALLET";
 }
@@ -20,3 +20,11 @@ fn validate_required(key: &str) {
         panic!("Missing required environment variable: {}", key);
     }
 }
+
+/// Read an optional variable, falling back to `default` when unset or unparsable
+pub fn var_or<T: std::str::FromStr>(key: &str, default: T) -> T {
+    env::var(key)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
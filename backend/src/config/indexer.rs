@@ -0,0 +1,16 @@
+use super::constants::env_keys;
+use super::env::var_or;
+
+pub struct AddressIndexConfig {
+    pub enabled: bool,
+    pub poll_secs: u64,
+}
+
+impl AddressIndexConfig {
+    pub fn from_env() -> Self {
+        Self {
+            enabled: var_or(env_keys::ADDRESS_INDEX_ENABLED, false),
+            poll_secs: var_or(env_keys::ADDRESS_INDEX_POLL_SECS, 10),
+        }
+    }
+}
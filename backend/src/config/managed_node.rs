@@ -0,0 +1,24 @@
+use std::path::PathBuf;
+
+use super::constants::env_keys;
+use super::env::var_or;
+
+pub struct ManagedNodeConfig {
+    /// bitcoind binary; the managed node is unavailable when unset
+    pub bitcoind_path: Option<PathBuf>,
+    pub data_dir: PathBuf,
+    pub startup_timeout_secs: u64,
+}
+
+impl ManagedNodeConfig {
+    pub fn from_env() -> Self {
+        Self {
+            bitcoind_path: std::env::var(env_keys::BITCOIND_PATH)
+                .ok()
+                .filter(|path| !path.is_empty())
+                .map(PathBuf::from),
+            data_dir: PathBuf::from(var_or(env_keys::MANAGED_NODE_DATA_DIR, "managed-node".to_string())),
+            startup_timeout_secs: var_or(env_keys::MANAGED_NODE_STARTUP_TIMEOUT_SECS, 60),
+        }
+    }
+}
@@ -1,3 +1,5 @@
 pub mod bitcoin;
 pub mod constants;
 pub mod env;
+pub mod indexer;
+pub mod managed_node;
@@ -0,0 +1,88 @@
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use argon2::{Algorithm, Argon2, Params, Version};
+use zeroize::Zeroizing;
+
+use crate::errors::AppError;
+
+const SALT_LENGTH: usize = 16;
+const KEY_LENGTH: usize = 32;
+
+/// Argon2id cost parameters, stored with every secret so they can be raised later
+#[derive(Debug, Clone, Copy)]
+pub struct KdfParams {
+    /// Memory cost in KiB
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self {
+            m_cost: Params::DEFAULT_M_COST,
+            t_cost: Params::DEFAULT_T_COST,
+            p_cost: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+/// A secret encrypted with AES-256-GCM under an Argon2id password-derived key
+#[derive(Debug, Clone)]
+pub struct EncryptedSecret {
+    pub ciphertext: Vec<u8>,
+    pub nonce: Vec<u8>,
+    pub salt: Vec<u8>,
+    pub kdf: KdfParams,
+}
+
+fn derive_key(password: &str, salt: &[u8], kdf: KdfParams) -> Result<Zeroizing<[u8; KEY_LENGTH]>, AppError> {
+    let params = Params::new(kdf.m_cost, kdf.t_cost, kdf.p_cost, Some(KEY_LENGTH))
+        .map_err(|e| AppError::Internal(format!("Invalid key derivation parameters: {}", e)))?;
+
+    let mut key = Zeroizing::new([0u8; KEY_LENGTH]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(password.as_bytes(), salt, key.as_mut())
+        .map_err(|e| AppError::Internal(format!("Key derivation failed: {}", e)))?;
+    Ok(key)
+}
+
+/// Encrypt `secret` under `password`. `aad` binds the ciphertext to its owner
+/// (e.g. a wallet ID) so it cannot be swapped between records.
+pub fn encrypt(secret: &[u8], password: &str, aad: &[u8]) -> Result<EncryptedSecret, AppError> {
+    let kdf = KdfParams::default();
+    let mut salt = vec![0u8; SALT_LENGTH];
+    OsRng.fill_bytes(&mut salt);
+
+    let key = derive_key(password, &salt, kdf)?;
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key.as_ref()));
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, Payload { msg: secret, aad })
+        .map_err(|_| AppError::Internal("Encryption failed".to_string()))?;
+
+    Ok(EncryptedSecret {
+        ciphertext,
+        nonce: nonce.to_vec(),
+        salt,
+        kdf,
+    })
+}
+
+/// Decrypt a secret; a wrong password or tampered record is reported as Forbidden
+pub fn decrypt(secret: &EncryptedSecret, password: &str, aad: &[u8]) -> Result<Zeroizing<Vec<u8>>, AppError> {
+    if secret.nonce.len() != 12 {
+        return Err(AppError::Internal("Stored secret has an invalid nonce".to_string()));
+    }
+
+    let key = derive_key(password, &secret.salt, secret.kdf)?;
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key.as_ref()));
+    cipher
+        .decrypt(
+            Nonce::from_slice(&secret.nonce),
+            Payload { msg: &secret.ciphertext, aad },
+        )
+        .map(Zeroizing::new)
+        .map_err(|_| AppError::Forbidden("Wrong wallet password".to_string()))
+}
@@ -4,8 +4,34 @@ pub mod sqlite;
 // Re-export commonly used types
 pub use traits::{
     NodeConfigRepository,
+    ChainTipRepository,
+    HeaderRepository,
+    AddressIndexRepository,
+    MetricsRepository,
+    BlockStatsRepository,
+    UtxoSetRepository,
+    WalletRepository,
+    WalletAddressRepository,
+    NodeWalletRepository,
+    WalletUtxoRepository,
+    WalletTransactionRepository,
+    PendingSendRepository,
     NodeConfig,
     NewNodeConfig,
     DbError
 };
-pub use sqlite::SqliteNodeConfigRepository;
+pub use sqlite::{
+    SqliteNodeConfigRepository,
+    SqliteChainTipRepository,
+    SqliteHeaderRepository,
+    SqliteAddressIndexRepository,
+    SqliteMetricsRepository,
+    SqliteBlockStatsRepository,
+    SqliteUtxoSetRepository,
+    SqliteWalletRepository,
+    SqliteWalletAddressRepository,
+    SqliteNodeWalletRepository,
+    SqliteWalletUtxoRepository,
+    SqliteWalletTransactionRepository,
+    SqlitePendingSendRepository,
+};
@@ -0,0 +1,230 @@
+use async_trait::async_trait;
+use sqlx::{Row, SqlitePool};
+
+use crate::db::traits::{AddressIndexRepository, DbError};
+use crate::domain::address::{AddressHistoryEntry, AddressTotals, AddressUtxo, IndexedBlock};
+
+/// SQLite implementation of AddressIndexRepository
+pub struct SqliteAddressIndexRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteAddressIndexRepository {
+    /// Create a new SQLite repository from an existing pool
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AddressIndexRepository for SqliteAddressIndexRepository {
+    async fn tip(&self) -> Result<Option<(u64, String)>, DbError> {
+        let row = sqlx::query(
+            "SELECT height, hash FROM address_index_blocks ORDER BY height DESC LIMIT 1",
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|r| (r.get::<i64, _>("height") as u64, r.get("hash"))))
+    }
+
+    async fn block_hash_at(&self, height: u64) -> Result<Option<String>, DbError> {
+        let hash = sqlx::query_scalar("SELECT hash FROM address_index_blocks WHERE height = ?")
+            .bind(height as i64)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(hash)
+    }
+
+    async fn apply_block(&self, block: &IndexedBlock) -> Result<(), DbError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("INSERT INTO address_index_blocks (height, hash) VALUES (?, ?)")
+            .bind(block.height as i64)
+            .bind(&block.hash)
+            .execute(&mut *tx)
+            .await?;
+
+        for output in &block.outputs {
+            // OR IGNORE: the two historic duplicate coinbase txids (BIP30)
+            sqlx::query(
+                r#"
+                INSERT OR IGNORE INTO address_outputs (txid, vout, height, script_pub_key, address, value_sat)
+                VALUES (?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&output.txid)
+            .bind(output.vout as i64)
+            .bind(block.height as i64)
+            .bind(&output.script_pub_key)
+            .bind(&output.address)
+            .bind(output.value_sat as i64)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for spend in &block.spends {
+            sqlx::query(
+                r#"
+                UPDATE address_outputs
+                SET spent_txid = ?, spent_vin = ?, spent_height = ?
+                WHERE txid = ? AND vout = ?
+                "#,
+            )
+            .bind(&spend.txid)
+            .bind(spend.vin as i64)
+            .bind(block.height as i64)
+            .bind(&spend.prev_txid)
+            .bind(spend.prev_vout as i64)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn rollback_from(&self, height: u64) -> Result<(), DbError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM address_outputs WHERE height >= ?")
+            .bind(height as i64)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE address_outputs
+            SET spent_txid = NULL, spent_vin = NULL, spent_height = NULL
+            WHERE spent_height >= ?
+            "#,
+        )
+        .bind(height as i64)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM address_index_blocks WHERE height >= ?")
+            .bind(height as i64)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn totals(&self, script_pub_key: &str) -> Result<AddressTotals, DbError> {
+        let row = sqlx::query(
+            r#"
+            SELECT
+                COALESCE(SUM(value_sat), 0) AS received,
+                COALESCE(SUM(CASE WHEN spent_txid IS NOT NULL THEN value_sat ELSE 0 END), 0) AS sent
+            FROM address_outputs
+            WHERE script_pub_key = ?
+            "#,
+        )
+        .bind(script_pub_key)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let tx_count: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM (
+                SELECT txid FROM address_outputs WHERE script_pub_key = ?1
+                UNION
+                SELECT spent_txid FROM address_outputs WHERE script_pub_key = ?1 AND spent_txid IS NOT NULL
+            )
+            "#,
+        )
+        .bind(script_pub_key)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(AddressTotals {
+            total_received_sat: row.get::<i64, _>("received") as u64,
+            total_sent_sat: row.get::<i64, _>("sent") as u64,
+            tx_count: tx_count as u64,
+        })
+    }
+
+    async fn list_utxos(&self, script_pub_key: &str) -> Result<Vec<AddressUtxo>, DbError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT txid, vout, value_sat, height
+            FROM address_outputs
+            WHERE script_pub_key = ? AND spent_txid IS NULL
+            ORDER BY height DESC, txid, vout
+            "#,
+        )
+        .bind(script_pub_key)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| AddressUtxo {
+                txid: r.get("txid"),
+                vout: r.get::<i64, _>("vout") as u32,
+                value_sat: r.get::<i64, _>("value_sat") as u64,
+                height: r.get::<i64, _>("height") as u64,
+            })
+            .collect())
+    }
+
+    async fn list_history(&self, script_pub_key: &str, offset: i64, limit: i64) -> Result<Vec<AddressHistoryEntry>, DbError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT txid, MAX(height) AS height, SUM(received) AS received, SUM(sent) AS sent
+            FROM (
+                SELECT txid, height, value_sat AS received, 0 AS sent
+                FROM address_outputs WHERE script_pub_key = ?1
+                UNION ALL
+                SELECT spent_txid AS txid, spent_height AS height, 0 AS received, value_sat AS sent
+                FROM address_outputs WHERE script_pub_key = ?1 AND spent_txid IS NOT NULL
+            )
+            GROUP BY txid
+            ORDER BY height DESC, txid
+            LIMIT ?2 OFFSET ?3
+            "#,
+        )
+        .bind(script_pub_key)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| {
+                let received = r.get::<i64, _>("received");
+                let sent = r.get::<i64, _>("sent");
+                AddressHistoryEntry {
+                    txid: r.get("txid"),
+                    height: r.get::<i64, _>("height") as u64,
+                    received_sat: received as u64,
+                    sent_sat: sent as u64,
+                    net_sat: received - sent,
+                }
+            })
+            .collect())
+    }
+
+    async fn filter_seen(&self, script_pub_keys: &[String]) -> Result<Vec<String>, DbError> {
+        let script_pub_keys = serde_json::to_string(script_pub_keys)
+            .map_err(|e| DbError::InvalidInput(e.to_string()))?;
+
+        let rows = sqlx::query_scalar(
+            r#"
+            SELECT DISTINCT script_pub_key FROM address_outputs
+            WHERE script_pub_key IN (SELECT value FROM json_each(?))
+            "#,
+        )
+        .bind(script_pub_keys)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+}
@@ -0,0 +1,100 @@
+use async_trait::async_trait;
+use sqlx::{Row, SqlitePool, sqlite::SqliteRow};
+
+use crate::db::traits::{BlockStatsRepository, DbError};
+use crate::domain::block_stats::{BlockStats, MAX_BLOCK_WEIGHT};
+
+/// SQLite implementation of BlockStatsRepository
+pub struct SqliteBlockStatsRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteBlockStatsRepository {
+    /// Create a new SQLite repository from an existing pool
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+fn stats_from_row(r: &SqliteRow) -> BlockStats {
+    let total_weight = r.get::<i64, _>("total_weight") as u64;
+
+    BlockStats {
+        height: r.get::<i64, _>("height") as u64,
+        hash: r.get("hash"),
+        time: r.get("time"),
+        tx_count: r.get::<i64, _>("tx_count") as u64,
+        total_fee_sat: r.get::<i64, _>("total_fee_sat") as u64,
+        subsidy_sat: r.get::<i64, _>("subsidy_sat") as u64,
+        median_feerate: r.get("median_feerate"),
+        avg_feerate: r.get("avg_feerate"),
+        total_size: r.get::<i64, _>("total_size") as u64,
+        total_weight,
+        fullness: total_weight as f64 / MAX_BLOCK_WEIGHT as f64,
+        segwit_tx_count: r.get::<i64, _>("segwit_tx_count") as u64,
+        inputs: r.get::<i64, _>("inputs") as u64,
+        segwit_inputs: r.get::<i64, _>("segwit_inputs") as u64,
+        taproot_inputs: r.get::<Option<i64>, _>("taproot_inputs").map(|v| v as u64),
+    }
+}
+
+#[async_trait]
+impl BlockStatsRepository for SqliteBlockStatsRepository {
+    async fn get_many(&self, hashes: &[String]) -> Result<Vec<BlockStats>, DbError> {
+        let hashes = serde_json::to_string(hashes)
+            .map_err(|e| DbError::InvalidInput(e.to_string()))?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM block_stats
+            WHERE hash IN (SELECT value FROM json_each(?))
+            "#,
+        )
+        .bind(hashes)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(stats_from_row).collect())
+    }
+
+    async fn store(&self, stats: &BlockStats) -> Result<(), DbError> {
+        let mut tx = self.pool.begin().await?;
+
+        // Stats of a block replaced by a reorg will never be asked for again
+        sqlx::query("DELETE FROM block_stats WHERE height = ? AND hash != ?")
+            .bind(stats.height as i64)
+            .bind(&stats.hash)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO block_stats (
+                hash, height, time, tx_count, total_fee_sat, subsidy_sat, median_feerate,
+                avg_feerate, total_size, total_weight, segwit_tx_count, inputs, segwit_inputs,
+                taproot_inputs
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&stats.hash)
+        .bind(stats.height as i64)
+        .bind(stats.time)
+        .bind(stats.tx_count as i64)
+        .bind(stats.total_fee_sat as i64)
+        .bind(stats.subsidy_sat as i64)
+        .bind(stats.median_feerate)
+        .bind(stats.avg_feerate)
+        .bind(stats.total_size as i64)
+        .bind(stats.total_weight as i64)
+        .bind(stats.segwit_tx_count as i64)
+        .bind(stats.inputs as i64)
+        .bind(stats.segwit_inputs as i64)
+        .bind(stats.taproot_inputs.map(|v| v as i64))
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,226 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{Row, SqlitePool, sqlite::SqliteRow};
+
+use crate::db::traits::{ChainTipRepository, DbError};
+use crate::domain::chain::{ChainTip, ReorgEvent, TipObservation};
+
+/// SQLite implementation of ChainTipRepository
+pub struct SqliteChainTipRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteChainTipRepository {
+    /// Create a new SQLite repository from an existing pool
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+fn tip_from_row(r: &SqliteRow) -> TipObservation {
+    TipObservation {
+        node_id: r.get("node_id"),
+        height: r.get::<i64, _>("height") as u64,
+        hash: r.get("hash"),
+        observed_at: r.get("observed_at"),
+    }
+}
+
+fn reorg_from_row(r: &SqliteRow) -> Result<ReorgEvent, DbError> {
+    let orphaned: String = r.get("orphaned_hashes");
+    let orphaned_hashes = serde_json::from_str(&orphaned)
+        .map_err(|e| DbError::DatabaseError(format!("Corrupt orphaned_hashes: {}", e)))?;
+
+    Ok(ReorgEvent {
+        id: r.get("id"),
+        node_id: r.get("node_id"),
+        fork_height: r.get::<i64, _>("fork_height") as u64,
+        fork_hash: r.get("fork_hash"),
+        depth: r.get::<i64, _>("depth") as u64,
+        old_tip_height: r.get::<i64, _>("old_tip_height") as u64,
+        old_tip_hash: r.get("old_tip_hash"),
+        new_tip_height: r.get::<i64, _>("new_tip_height") as u64,
+        new_tip_hash: r.get("new_tip_hash"),
+        orphaned_hashes,
+        detected_at: r.get("detected_at"),
+    })
+}
+
+#[async_trait]
+impl ChainTipRepository for SqliteChainTipRepository {
+    async fn record_best_tip(&self, tip: &TipObservation) -> Result<(), DbError> {
+        sqlx::query(
+            r#"
+            INSERT INTO best_tip_history (node_id, height, hash, observed_at)
+            VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(&tip.node_id)
+        .bind(tip.height as i64)
+        .bind(&tip.hash)
+        .bind(tip.observed_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn latest_best_tip(&self, node_id: &str) -> Result<Option<TipObservation>, DbError> {
+        let row = sqlx::query(
+            r#"
+            SELECT node_id, height, hash, observed_at
+            FROM best_tip_history
+            WHERE node_id = ?
+            ORDER BY id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(node_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.as_ref().map(tip_from_row))
+    }
+
+    async fn list_tip_history(&self, node_id: &str, limit: i64) -> Result<Vec<TipObservation>, DbError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT node_id, height, hash, observed_at
+            FROM best_tip_history
+            WHERE node_id = ?
+            ORDER BY id DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(node_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(tip_from_row).collect())
+    }
+
+    async fn prune_tip_history(&self, node_id: &str, keep: i64) -> Result<(), DbError> {
+        sqlx::query(
+            r#"
+            DELETE FROM best_tip_history
+            WHERE node_id = ? AND id NOT IN (
+                SELECT id FROM best_tip_history WHERE node_id = ? ORDER BY id DESC LIMIT ?
+            )
+            "#,
+        )
+        .bind(node_id)
+        .bind(node_id)
+        .bind(keep)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn replace_chain_tips(&self, node_id: &str, tips: &[ChainTip]) -> Result<(), DbError> {
+        let now = Utc::now().timestamp();
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM chain_tips WHERE node_id = ?")
+            .bind(node_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for tip in tips {
+            sqlx::query(
+                r#"
+                INSERT INTO chain_tips (node_id, hash, height, branch_len, status, observed_at)
+                VALUES (?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(node_id)
+            .bind(&tip.hash)
+            .bind(tip.height as i64)
+            .bind(tip.branch_len as i64)
+            .bind(&tip.status)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn list_chain_tips(&self, node_id: &str) -> Result<Vec<ChainTip>, DbError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT hash, height, branch_len, status
+            FROM chain_tips
+            WHERE node_id = ?
+            ORDER BY height DESC
+            "#,
+        )
+        .bind(node_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| ChainTip {
+                height: r.get::<i64, _>("height") as u64,
+                hash: r.get("hash"),
+                branch_len: r.get::<i64, _>("branch_len") as u64,
+                status: r.get("status"),
+            })
+            .collect())
+    }
+
+    async fn record_reorg(&self, event: &ReorgEvent) -> Result<(), DbError> {
+        let orphaned = serde_json::to_string(&event.orphaned_hashes)
+            .map_err(|e| DbError::InvalidInput(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO reorg_events (
+                id, node_id, fork_height, fork_hash, depth,
+                old_tip_height, old_tip_hash, new_tip_height, new_tip_hash,
+                orphaned_hashes, detected_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&event.id)
+        .bind(&event.node_id)
+        .bind(event.fork_height as i64)
+        .bind(&event.fork_hash)
+        .bind(event.depth as i64)
+        .bind(event.old_tip_height as i64)
+        .bind(&event.old_tip_hash)
+        .bind(event.new_tip_height as i64)
+        .bind(&event.new_tip_hash)
+        .bind(orphaned)
+        .bind(event.detected_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn list_reorgs(&self, node_id: Option<&str>, limit: i64) -> Result<Vec<ReorgEvent>, DbError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, node_id, fork_height, fork_hash, depth,
+                   old_tip_height, old_tip_hash, new_tip_height, new_tip_hash,
+                   orphaned_hashes, detected_at
+            FROM reorg_events
+            WHERE (?1 IS NULL OR node_id = ?1)
+            ORDER BY detected_at DESC
+            LIMIT ?2
+            "#,
+        )
+        .bind(node_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(reorg_from_row).collect()
+    }
+}
@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use sqlx::{Row, SqlitePool, sqlite::SqliteRow};
+
+use crate::db::traits::{DbError, HeaderRepository};
+use crate::domain::header::BlockHeader;
+
+/// SQLite implementation of HeaderRepository
+pub struct SqliteHeaderRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteHeaderRepository {
+    /// Create a new SQLite repository from an existing pool
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+const HEADER_COLUMNS: &str = "height, hash, previous_hash, version, merkle_root, time, \
+     median_time, nonce, bits, difficulty, chainwork, tx_count";
+
+fn header_from_row(r: &SqliteRow) -> BlockHeader {
+    BlockHeader {
+        height: r.get::<i64, _>("height") as u64,
+        hash: r.get("hash"),
+        previous_hash: r.get("previous_hash"),
+        version: r.get("version"),
+        merkle_root: r.get("merkle_root"),
+        time: r.get("time"),
+        median_time: r.get("median_time"),
+        nonce: r.get::<i64, _>("nonce") as u64,
+        bits: r.get("bits"),
+        difficulty: r.get("difficulty"),
+        chainwork: r.get("chainwork"),
+        tx_count: r.get::<i64, _>("tx_count") as u64,
+    }
+}
+
+#[async_trait]
+impl HeaderRepository for SqliteHeaderRepository {
+    async fn tip(&self) -> Result<Option<BlockHeader>, DbError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM block_headers ORDER BY height DESC LIMIT 1",
+            HEADER_COLUMNS
+        ))
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.as_ref().map(header_from_row))
+    }
+
+    async fn get_by_height(&self, height: u64) -> Result<Option<BlockHeader>, DbError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM block_headers WHERE height = ?",
+            HEADER_COLUMNS
+        ))
+        .bind(height as i64)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.as_ref().map(header_from_row))
+    }
+
+    async fn get_by_hash(&self, hash: &str) -> Result<Option<BlockHeader>, DbError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM block_headers WHERE hash = ?",
+            HEADER_COLUMNS
+        ))
+        .bind(hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.as_ref().map(header_from_row))
+    }
+
+    async fn get_at_time(&self, timestamp: i64) -> Result<Option<BlockHeader>, DbError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM block_headers WHERE time <= ? ORDER BY height DESC LIMIT 1",
+            HEADER_COLUMNS
+        ))
+        .bind(timestamp)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.as_ref().map(header_from_row))
+    }
+
+    async fn list_range(&self, from: u64, to: u64) -> Result<Vec<BlockHeader>, DbError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM block_headers WHERE height BETWEEN ? AND ? ORDER BY height ASC",
+            HEADER_COLUMNS
+        ))
+        .bind(from as i64)
+        .bind(to as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(header_from_row).collect())
+    }
+
+    async fn insert_batch(&self, headers: &[BlockHeader]) -> Result<(), DbError> {
+        let mut tx = self.pool.begin().await?;
+
+        for header in headers {
+            sqlx::query(&format!(
+                "INSERT INTO block_headers ({}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                HEADER_COLUMNS
+            ))
+            .bind(header.height as i64)
+            .bind(&header.hash)
+            .bind(&header.previous_hash)
+            .bind(header.version)
+            .bind(&header.merkle_root)
+            .bind(header.time)
+            .bind(header.median_time)
+            .bind(header.nonce as i64)
+            .bind(&header.bits)
+            .bind(header.difficulty)
+            .bind(&header.chainwork)
+            .bind(header.tx_count as i64)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn delete_from_height(&self, height: u64) -> Result<u64, DbError> {
+        let result = sqlx::query("DELETE FROM block_headers WHERE height >= ?")
+            .bind(height as i64)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
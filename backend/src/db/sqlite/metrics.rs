@@ -0,0 +1,99 @@
+use async_trait::async_trait;
+use sqlx::{Row, SqlitePool};
+
+use crate::db::traits::{DbError, MetricsRepository};
+use crate::domain::metrics::{Metric, MetricPoint, MetricSample};
+
+/// SQLite implementation of MetricsRepository
+pub struct SqliteMetricsRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteMetricsRepository {
+    /// Create a new SQLite repository from an existing pool
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl MetricsRepository for SqliteMetricsRepository {
+    async fn record_sample(&self, sample: &MetricSample) -> Result<(), DbError> {
+        sqlx::query(
+            r#"
+            INSERT INTO node_metrics (
+                node_id, sampled_at, block_height, headers, mempool_size, mempool_bytes,
+                peer_count, bytes_recv, bytes_sent, verification_progress
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&sample.node_id)
+        .bind(sample.sampled_at)
+        .bind(sample.block_height as i64)
+        .bind(sample.headers as i64)
+        .bind(sample.mempool_size as i64)
+        .bind(sample.mempool_bytes as i64)
+        .bind(sample.peer_count as i64)
+        .bind(sample.bytes_recv as i64)
+        .bind(sample.bytes_sent as i64)
+        .bind(sample.verification_progress)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn series(
+        &self,
+        node_id: &str,
+        metric: Metric,
+        from: i64,
+        to: i64,
+        bucket_secs: i64,
+    ) -> Result<Vec<MetricPoint>, DbError> {
+        // The column name comes from a fixed enum, never from user input
+        let column = metric.column();
+        let query = format!(
+            r#"
+            SELECT (sampled_at / ?1) * ?1 AS bucket,
+                   CAST(AVG({column}) AS REAL) AS avg_value,
+                   CAST(MIN({column}) AS REAL) AS min_value,
+                   CAST(MAX({column}) AS REAL) AS max_value,
+                   COUNT(*) AS samples
+            FROM node_metrics
+            WHERE node_id = ?2 AND sampled_at BETWEEN ?3 AND ?4
+            GROUP BY bucket
+            ORDER BY bucket ASC
+            "#
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(bucket_secs)
+            .bind(node_id)
+            .bind(from)
+            .bind(to)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows
+            .iter()
+            .map(|r| MetricPoint {
+                timestamp: r.get("bucket"),
+                avg: r.get("avg_value"),
+                min: r.get::<f64, _>("min_value"),
+                max: r.get::<f64, _>("max_value"),
+                samples: r.get("samples"),
+            })
+            .collect())
+    }
+
+    async fn prune_before(&self, cutoff: i64) -> Result<(), DbError> {
+        sqlx::query("DELETE FROM node_metrics WHERE sampled_at < ?")
+            .bind(cutoff)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
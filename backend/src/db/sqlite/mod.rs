@@ -1,3 +1,27 @@
+pub mod address_index;
+pub mod block_stats;
+pub mod chain_tips;
+pub mod headers;
+pub mod metrics;
 pub mod node_config;
+pub mod node_wallet;
+pub mod pending_send;
+pub mod utxoset;
+pub mod wallet;
+pub mod wallet_address;
+pub mod wallet_transaction;
+pub mod wallet_utxo;
 
+pub use address_index::SqliteAddressIndexRepository;
+pub use block_stats::SqliteBlockStatsRepository;
+pub use chain_tips::SqliteChainTipRepository;
+pub use headers::SqliteHeaderRepository;
+pub use metrics::SqliteMetricsRepository;
 pub use node_config::SqliteNodeConfigRepository;
+pub use node_wallet::SqliteNodeWalletRepository;
+pub use pending_send::SqlitePendingSendRepository;
+pub use utxoset::SqliteUtxoSetRepository;
+pub use wallet::SqliteWalletRepository;
+pub use wallet_address::SqliteWalletAddressRepository;
+pub use wallet_transaction::SqliteWalletTransactionRepository;
+pub use wallet_utxo::SqliteWalletUtxoRepository;
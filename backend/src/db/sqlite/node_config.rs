@@ -181,11 +181,7 @@ impl NodeConfigRepository for SqliteNodeConfigRepository {
 
     async fn test_connection(&self, config: &NodeConfig) -> Result<bool, DbError> {
         // Create a temporary RPC client with the provided config
-        let client = BitcoinRpc::new(
-            config.rpc_url.clone(),
-            config.rpc_user.clone(),
-            config.rpc_password.clone(),
-        );
+        let client = BitcoinRpc::from_node_config(config);
 
         // Try to get blockchain info to test the connection
         match client.get_blockchain_info().await {
@@ -0,0 +1,93 @@
+use async_trait::async_trait;
+use sqlx::{Row, SqlitePool};
+
+use crate::db::traits::{DbError, NodeWalletRepository};
+use crate::domain::wallet::NodeWalletSync;
+
+/// SQLite implementation of NodeWalletRepository
+pub struct SqliteNodeWalletRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteNodeWalletRepository {
+    /// Create a new SQLite repository from an existing pool
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl NodeWalletRepository for SqliteNodeWalletRepository {
+    async fn get(&self, wallet_id: &str) -> Result<Option<NodeWalletSync>, DbError> {
+        let row = sqlx::query(
+            r#"
+            SELECT wallet_id, node_wallet, node_id, rescan_from, synced_at, last_error
+            FROM wallet_node_sync WHERE wallet_id = ?
+            "#,
+        )
+        .bind(wallet_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|r| NodeWalletSync {
+            wallet_id: r.get("wallet_id"),
+            node_wallet: r.get("node_wallet"),
+            node_id: r.get("node_id"),
+            rescan_from: r.get("rescan_from"),
+            synced_at: r.get("synced_at"),
+            last_error: r.get("last_error"),
+        }))
+    }
+
+    async fn enrol(&self, wallet_id: &str, node_wallet: &str, rescan_from: i64) -> Result<(), DbError> {
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO wallet_node_sync (wallet_id, node_wallet, rescan_from)
+            VALUES (?, ?, ?)
+            "#,
+        )
+        .bind(wallet_id)
+        .bind(node_wallet)
+        .bind(rescan_from)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn set_rescan_from(&self, wallet_id: &str, rescan_from: i64) -> Result<(), DbError> {
+        let result = sqlx::query("UPDATE wallet_node_sync SET rescan_from = ? WHERE wallet_id = ?")
+            .bind(rescan_from)
+            .bind(wallet_id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        Ok(())
+    }
+
+    async fn record_import(
+        &self,
+        wallet_id: &str,
+        node_id: Option<&str>,
+        synced_at: i64,
+        error: Option<&str>,
+    ) -> Result<(), DbError> {
+        sqlx::query(
+            r#"
+            UPDATE wallet_node_sync SET node_id = ?, synced_at = ?, last_error = ?
+            WHERE wallet_id = ?
+            "#,
+        )
+        .bind(node_id)
+        .bind(synced_at)
+        .bind(error)
+        .bind(wallet_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
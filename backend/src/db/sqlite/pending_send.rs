@@ -0,0 +1,180 @@
+use async_trait::async_trait;
+use sqlx::{Row, SqlitePool, sqlite::SqliteRow};
+
+use crate::db::traits::{DbError, PendingSendRepository};
+use crate::domain::coin_selection::SelectionAlgorithm;
+use crate::domain::send::{PendingSend, SendStage};
+
+/// SQLite implementation of PendingSendRepository
+pub struct SqlitePendingSendRepository {
+    pool: SqlitePool,
+}
+
+impl SqlitePendingSendRepository {
+    /// Create a new SQLite repository from an existing pool
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+fn send_from_row(r: &SqliteRow) -> Result<PendingSend, DbError> {
+    let stage: String = r.get("stage");
+    let inputs: String = r.get("inputs");
+    let recipients: String = r.get("recipients");
+
+    Ok(PendingSend {
+        id: r.get("id"),
+        wallet_id: r.get("wallet_id"),
+        stage: SendStage::parse(&stage)
+            .ok_or_else(|| DbError::InvalidInput(format!("Unknown send stage '{}'", stage)))?,
+        unsigned_txid: r.get("unsigned_txid"),
+        psbt: r.get("psbt"),
+        tx_hex: r.get("tx_hex"),
+        txid: r.get("txid"),
+        inputs: serde_json::from_str(&inputs).map_err(|e| DbError::InvalidInput(e.to_string()))?,
+        recipients: serde_json::from_str(&recipients).map_err(|e| DbError::InvalidInput(e.to_string()))?,
+        change_address: r.get("change_address"),
+        change_sat: r.get::<Option<i64>, _>("change_sat").map(|c| c as u64),
+        fee_sat: r.get::<i64, _>("fee_sat") as u64,
+        feerate: r.get("feerate"),
+        vsize: r.get::<i64, _>("vsize") as u64,
+        algorithm: r
+            .get::<Option<String>, _>("algorithm")
+            .and_then(|a| SelectionAlgorithm::parse(&a)),
+        replaces: r.get("replaces"),
+        last_error: r.get("last_error"),
+        created_at: r.get("created_at"),
+        updated_at: r.get("updated_at"),
+    })
+}
+
+#[async_trait]
+impl PendingSendRepository for SqlitePendingSendRepository {
+    async fn create(&self, send: &PendingSend) -> Result<(), DbError> {
+        let inputs = serde_json::to_string(&send.inputs).map_err(|e| DbError::InvalidInput(e.to_string()))?;
+        let recipients =
+            serde_json::to_string(&send.recipients).map_err(|e| DbError::InvalidInput(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO pending_sends (
+                id, wallet_id, stage, unsigned_txid, psbt, tx_hex, txid, inputs, recipients,
+                change_address, change_sat, fee_sat, feerate, vsize, algorithm, replaces, last_error,
+                created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&send.id)
+        .bind(&send.wallet_id)
+        .bind(send.stage.as_str())
+        .bind(&send.unsigned_txid)
+        .bind(&send.psbt)
+        .bind(&send.tx_hex)
+        .bind(&send.txid)
+        .bind(inputs)
+        .bind(recipients)
+        .bind(&send.change_address)
+        .bind(send.change_sat.map(|c| c as i64))
+        .bind(send.fee_sat as i64)
+        .bind(send.feerate)
+        .bind(send.vsize as i64)
+        .bind(send.algorithm.map(|a| a.as_str()))
+        .bind(&send.replaces)
+        .bind(&send.last_error)
+        .bind(send.created_at)
+        .bind(send.updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<PendingSend>, DbError> {
+        let row = sqlx::query("SELECT * FROM pending_sends WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.as_ref().map(send_from_row).transpose()
+    }
+
+    async fn find_by_unsigned_txid(&self, unsigned_txid: &str) -> Result<Option<PendingSend>, DbError> {
+        // An abandoned send may have been rebuilt with the same transaction
+        let row = sqlx::query(
+            r#"
+            SELECT * FROM pending_sends
+            WHERE unsigned_txid = ? AND stage != 'abandoned'
+            ORDER BY created_at DESC, id
+            LIMIT 1
+            "#,
+        )
+        .bind(unsigned_txid)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.as_ref().map(send_from_row).transpose()
+    }
+
+    async fn list(&self, wallet_id: &str) -> Result<Vec<PendingSend>, DbError> {
+        let rows = sqlx::query("SELECT * FROM pending_sends WHERE wallet_id = ? ORDER BY created_at DESC, id")
+            .bind(wallet_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(send_from_row).collect()
+    }
+
+    async fn update(&self, send: &PendingSend) -> Result<(), DbError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE pending_sends
+            SET stage = ?, psbt = ?, tx_hex = ?, txid = ?, last_error = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(send.stage.as_str())
+        .bind(&send.psbt)
+        .bind(&send.tx_hex)
+        .bind(&send.txid)
+        .bind(&send.last_error)
+        .bind(send.updated_at)
+        .bind(&send.id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        Ok(())
+    }
+
+    async fn reserved_outpoints(&self, wallet_id: &str) -> Result<Vec<String>, DbError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT j.value AS outpoint
+            FROM pending_sends s, json_each(s.inputs) j
+            WHERE s.wallet_id = ? AND s.stage IN ('created', 'signed', 'finalized')
+            "#,
+        )
+        .bind(wallet_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(|r| r.get("outpoint")).collect())
+    }
+
+    async fn change_addresses(&self, wallet_id: &str) -> Result<Vec<String>, DbError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT DISTINCT change_address FROM pending_sends
+            WHERE wallet_id = ? AND stage != 'abandoned' AND change_address IS NOT NULL
+            "#,
+        )
+        .bind(wallet_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(|r| r.get("change_address")).collect())
+    }
+}
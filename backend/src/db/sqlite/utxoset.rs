@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+use sqlx::{Row, SqlitePool, sqlite::SqliteRow};
+
+use crate::db::traits::{DbError, UtxoSetRepository};
+use crate::domain::utxoset::{UtxoSetHashType, UtxoSetInfo};
+
+/// SQLite implementation of UtxoSetRepository
+pub struct SqliteUtxoSetRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteUtxoSetRepository {
+    /// Create a new SQLite repository from an existing pool
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+fn info_from_row(r: &SqliteRow, hash_type: UtxoSetHashType) -> UtxoSetInfo {
+    UtxoSetInfo {
+        height: r.get::<i64, _>("height") as u64,
+        best_block: r.get("best_block"),
+        hash_type,
+        hash: r.get("hash"),
+        txouts: r.get::<i64, _>("txouts") as u64,
+        bogosize: r.get::<i64, _>("bogosize") as u64,
+        total_amount_sat: r.get::<i64, _>("total_amount_sat") as u64,
+        transactions: r.get::<Option<i64>, _>("transactions").map(|v| v as u64),
+        disk_size: r.get::<Option<i64>, _>("disk_size").map(|v| v as u64),
+        computed_at: r.get("computed_at"),
+    }
+}
+
+#[async_trait]
+impl UtxoSetRepository for SqliteUtxoSetRepository {
+    async fn store(&self, info: &UtxoSetInfo) -> Result<(), DbError> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO utxoset_snapshots (
+                best_block, hash_type, height, hash, txouts, bogosize, total_amount_sat,
+                transactions, disk_size, computed_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&info.best_block)
+        .bind(info.hash_type.as_str())
+        .bind(info.height as i64)
+        .bind(&info.hash)
+        .bind(info.txouts as i64)
+        .bind(info.bogosize as i64)
+        .bind(info.total_amount_sat as i64)
+        .bind(info.transactions.map(|v| v as i64))
+        .bind(info.disk_size.map(|v| v as i64))
+        .bind(info.computed_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get(&self, best_block: &str, hash_type: UtxoSetHashType) -> Result<Option<UtxoSetInfo>, DbError> {
+        let row = sqlx::query("SELECT * FROM utxoset_snapshots WHERE best_block = ? AND hash_type = ?")
+            .bind(best_block)
+            .bind(hash_type.as_str())
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.map(|r| info_from_row(&r, hash_type)))
+    }
+
+    async fn list_recent(&self, hash_type: UtxoSetHashType, limit: i64) -> Result<Vec<UtxoSetInfo>, DbError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM utxoset_snapshots
+            WHERE hash_type = ?
+            ORDER BY computed_at DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(hash_type.as_str())
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(|r| info_from_row(r, hash_type)).collect())
+    }
+}
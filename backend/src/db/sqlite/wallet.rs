@@ -0,0 +1,195 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{Row, SqlitePool, sqlite::SqliteRow};
+use uuid::Uuid;
+
+use crate::crypto::{EncryptedSecret, KdfParams};
+use crate::db::traits::{DbError, WalletRepository};
+use crate::domain::wallet::{NewWallet, ScriptType, Wallet, WalletAccount, WalletKind};
+
+const SELECT_WALLETS: &str = r#"
+    SELECT w.*, EXISTS (SELECT 1 FROM wallet_secrets s WHERE s.wallet_id = w.id) AS has_seed
+    FROM wallets w
+"#;
+
+/// SQLite implementation of WalletRepository
+pub struct SqliteWalletRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteWalletRepository {
+    /// Create a new SQLite repository from an existing pool
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    async fn accounts(&self, wallet_id: &str) -> Result<Vec<WalletAccount>, DbError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT script_type, derivation_path, xpub
+            FROM wallet_accounts
+            WHERE wallet_id = ?
+            ORDER BY purpose
+            "#,
+        )
+        .bind(wallet_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|r| {
+                let script_type: String = r.get("script_type");
+                Ok(WalletAccount {
+                    script_type: ScriptType::parse(&script_type).ok_or_else(|| {
+                        DbError::InvalidInput(format!("Unknown script type '{}'", script_type))
+                    })?,
+                    derivation_path: r.get("derivation_path"),
+                    xpub: r.get("xpub"),
+                })
+            })
+            .collect()
+    }
+
+    async fn wallet_from_row(&self, r: &SqliteRow) -> Result<Wallet, DbError> {
+        let id: String = r.get("id");
+        let kind: String = r.get("kind");
+
+        Ok(Wallet {
+            accounts: self.accounts(&id).await?,
+            id,
+            name: r.get("name"),
+            network: r.get("network"),
+            kind: WalletKind::parse(&kind)
+                .ok_or_else(|| DbError::InvalidInput(format!("Unknown wallet kind '{}'", kind)))?,
+            master_fingerprint: r.get("master_fingerprint"),
+            has_seed: r.get("has_seed"),
+            created_at: r.get("created_at"),
+        })
+    }
+}
+
+#[async_trait]
+impl WalletRepository for SqliteWalletRepository {
+    async fn create(&self, wallet: NewWallet) -> Result<Wallet, DbError> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().timestamp();
+
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO wallets (id, name, network, kind, master_fingerprint, created_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&id)
+        .bind(&wallet.name)
+        .bind(&wallet.network)
+        .bind(wallet.kind.as_str())
+        .bind(&wallet.master_fingerprint)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+
+        for account in &wallet.accounts {
+            sqlx::query(
+                r#"
+                INSERT INTO wallet_accounts (wallet_id, script_type, purpose, derivation_path, xpub)
+                VALUES (?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&id)
+            .bind(account.script_type.as_str())
+            .bind(account.script_type.purpose())
+            .bind(&account.derivation_path)
+            .bind(&account.xpub)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        if let Some(secret) = &wallet.secret {
+            sqlx::query(
+                r#"
+                INSERT INTO wallet_secrets (wallet_id, ciphertext, nonce, salt, kdf_m_cost, kdf_t_cost, kdf_p_cost)
+                VALUES (?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&id)
+            .bind(&secret.ciphertext)
+            .bind(&secret.nonce)
+            .bind(&secret.salt)
+            .bind(secret.kdf.m_cost)
+            .bind(secret.kdf.t_cost)
+            .bind(secret.kdf.p_cost)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(Wallet {
+            id,
+            name: wallet.name,
+            network: wallet.network,
+            kind: wallet.kind,
+            master_fingerprint: wallet.master_fingerprint,
+            accounts: wallet.accounts,
+            has_seed: wallet.secret.is_some(),
+            created_at: now,
+        })
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<Wallet>, DbError> {
+        let row = sqlx::query(&format!("{} WHERE id = ?", SELECT_WALLETS))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        match row {
+            Some(r) => Ok(Some(self.wallet_from_row(&r).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_by_name(&self, name: &str) -> Result<Option<Wallet>, DbError> {
+        let row = sqlx::query(&format!("{} WHERE name = ? COLLATE NOCASE", SELECT_WALLETS))
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        match row {
+            Some(r) => Ok(Some(self.wallet_from_row(&r).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn list(&self) -> Result<Vec<Wallet>, DbError> {
+        let rows = sqlx::query(&format!("{} ORDER BY created_at DESC", SELECT_WALLETS))
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut wallets = Vec::with_capacity(rows.len());
+        for r in &rows {
+            wallets.push(self.wallet_from_row(r).await?);
+        }
+        Ok(wallets)
+    }
+
+    async fn get_secret(&self, wallet_id: &str) -> Result<Option<EncryptedSecret>, DbError> {
+        let row = sqlx::query("SELECT * FROM wallet_secrets WHERE wallet_id = ?")
+            .bind(wallet_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.map(|r| EncryptedSecret {
+            ciphertext: r.get("ciphertext"),
+            nonce: r.get("nonce"),
+            salt: r.get("salt"),
+            kdf: KdfParams {
+                m_cost: r.get("kdf_m_cost"),
+                t_cost: r.get("kdf_t_cost"),
+                p_cost: r.get("kdf_p_cost"),
+            },
+        }))
+    }
+}
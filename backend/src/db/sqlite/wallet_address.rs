@@ -0,0 +1,184 @@
+use async_trait::async_trait;
+use sqlx::{Row, SqlitePool, sqlite::SqliteRow};
+
+use crate::db::traits::{DbError, WalletAddressRepository};
+use crate::domain::wallet::{AddressChain, AddressChainState, AddressFilter, ScriptType, WalletAddress};
+
+/// SQLite implementation of WalletAddressRepository
+pub struct SqliteWalletAddressRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteWalletAddressRepository {
+    /// Create a new SQLite repository from an existing pool
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+fn address_from_row(r: &SqliteRow) -> Result<WalletAddress, DbError> {
+    let script_type: String = r.get("script_type");
+    let chain = r.get::<i64, _>("chain") as u32;
+
+    Ok(WalletAddress {
+        address: r.get("address"),
+        script_type: ScriptType::parse(&script_type)
+            .ok_or_else(|| DbError::InvalidInput(format!("Unknown script type '{}'", script_type)))?,
+        chain: AddressChain::from_index(chain)
+            .ok_or_else(|| DbError::InvalidInput(format!("Unknown address chain {}", chain)))?,
+        index: r.get::<i64, _>("idx") as u32,
+        derivation_path: r.get("derivation_path"),
+        script_pub_key: r.get("script_pub_key"),
+        used: r.get::<i64, _>("used") == 1,
+    })
+}
+
+#[async_trait]
+impl WalletAddressRepository for SqliteWalletAddressRepository {
+    async fn chain_state(
+        &self,
+        wallet_id: &str,
+        script_type: ScriptType,
+        chain: AddressChain,
+    ) -> Result<Option<AddressChainState>, DbError> {
+        let row = sqlx::query(
+            r#"
+            SELECT gap_limit, issued FROM wallet_address_chains
+            WHERE wallet_id = ? AND script_type = ? AND chain = ?
+            "#,
+        )
+        .bind(wallet_id)
+        .bind(script_type.as_str())
+        .bind(chain.index())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|r| AddressChainState {
+            gap_limit: r.get::<i64, _>("gap_limit") as u32,
+            issued: r.get::<i64, _>("issued") as u32,
+        }))
+    }
+
+    async fn create_chain_state(
+        &self,
+        wallet_id: &str,
+        script_type: ScriptType,
+        chain: AddressChain,
+        state: AddressChainState,
+    ) -> Result<(), DbError> {
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO wallet_address_chains (wallet_id, script_type, chain, gap_limit, issued)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(wallet_id)
+        .bind(script_type.as_str())
+        .bind(chain.index())
+        .bind(state.gap_limit)
+        .bind(state.issued)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn store_addresses(&self, wallet_id: &str, addresses: &[WalletAddress]) -> Result<(), DbError> {
+        let mut tx = self.pool.begin().await?;
+
+        for address in addresses {
+            sqlx::query(
+                r#"
+                INSERT OR IGNORE INTO wallet_addresses (
+                    wallet_id, script_type, chain, idx, address, script_pub_key, derivation_path, used
+                )
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(wallet_id)
+            .bind(address.script_type.as_str())
+            .bind(address.chain.index())
+            .bind(address.index)
+            .bind(&address.address)
+            .bind(&address.script_pub_key)
+            .bind(&address.derivation_path)
+            .bind(address.used)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn list_addresses(&self, wallet_id: &str, filter: &AddressFilter) -> Result<Vec<WalletAddress>, DbError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM wallet_addresses
+            WHERE wallet_id = ?1
+              AND (?2 IS NULL OR script_type = ?2)
+              AND (?3 IS NULL OR chain = ?3)
+              AND (?4 IS NULL OR used = ?4)
+            ORDER BY script_type, chain, idx
+            "#,
+        )
+        .bind(wallet_id)
+        .bind(filter.script_type.map(|t| t.as_str()))
+        .bind(filter.chain.map(|c| c.index()))
+        .bind(filter.used)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(address_from_row).collect()
+    }
+
+    async fn reserve_index(
+        &self,
+        wallet_id: &str,
+        script_type: ScriptType,
+        chain: AddressChain,
+    ) -> Result<Option<u32>, DbError> {
+        // One statement, so concurrent callers never get the same index
+        let row = sqlx::query(
+            r#"
+            WITH used AS (
+                SELECT COALESCE(MAX(idx) + 1, 0) AS first_unused FROM wallet_addresses
+                WHERE wallet_id = ? AND script_type = ? AND chain = ? AND used = 1
+            )
+            UPDATE wallet_address_chains
+            SET issued = MAX(issued, (SELECT first_unused FROM used)) + 1
+            WHERE wallet_id = ? AND script_type = ? AND chain = ?
+                AND MAX(issued, (SELECT first_unused FROM used)) - (SELECT first_unused FROM used) < gap_limit
+            RETURNING issued - 1 AS idx
+            "#,
+        )
+        .bind(wallet_id)
+        .bind(script_type.as_str())
+        .bind(chain.index())
+        .bind(wallet_id)
+        .bind(script_type.as_str())
+        .bind(chain.index())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|r| r.get::<i64, _>("idx") as u32))
+    }
+
+    async fn mark_used(&self, wallet_id: &str, addresses: &[String]) -> Result<u64, DbError> {
+        let addresses = serde_json::to_string(addresses)
+            .map_err(|e| DbError::InvalidInput(e.to_string()))?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE wallet_addresses SET used = 1
+            WHERE wallet_id = ? AND used = 0 AND address IN (SELECT value FROM json_each(?))
+            "#,
+        )
+        .bind(wallet_id)
+        .bind(addresses)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
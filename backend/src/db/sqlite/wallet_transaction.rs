@@ -0,0 +1,216 @@
+use async_trait::async_trait;
+use sqlx::{Row, SqlitePool, sqlite::SqliteRow};
+
+use crate::db::traits::{DbError, WalletTransactionRepository};
+use crate::domain::transaction::{TransactionFilter, TxDirection, TxSyncCursor, WalletTransaction};
+
+/// SQLite implementation of WalletTransactionRepository
+pub struct SqliteWalletTransactionRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteWalletTransactionRepository {
+    /// Create a new SQLite repository from an existing pool
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Filter clause shared by `list` and `count`; binds ?1 to ?5
+const FILTER: &str = r#"
+    WHERE t.wallet_id = ?1
+      AND (?2 IS NULL OR t.direction = ?2)
+      AND (?3 IS NULL OR COALESCE(t.block_time, t.first_seen) >= ?3)
+      AND (?4 IS NULL OR COALESCE(t.block_time, t.first_seen) <= ?4)
+      AND (?5 IS NULL OR t.label LIKE '%' || ?5 || '%')
+"#;
+
+fn transaction_from_row(r: &SqliteRow) -> Result<WalletTransaction, DbError> {
+    let direction: String = r.get("direction");
+    let counterparties: String = r.get("counterparties");
+
+    Ok(WalletTransaction {
+        txid: r.get("txid"),
+        direction: TxDirection::parse(&direction)
+            .ok_or_else(|| DbError::InvalidInput(format!("Unknown direction '{}'", direction)))?,
+        net_sat: r.get("net_sat"),
+        fee_sat: r.get::<Option<i64>, _>("fee_sat").map(|f| f as u64),
+        confirmations: r.get::<i64, _>("confirmations").max(0) as u64,
+        block_height: r.get::<Option<i64>, _>("block_height").map(|h| h as u64),
+        block_hash: r.get("block_hash"),
+        block_time: r.get("block_time"),
+        first_seen: r.get("first_seen"),
+        counterparties: serde_json::from_str(&counterparties)
+            .map_err(|e| DbError::InvalidInput(e.to_string()))?,
+        replaceable: r.get::<Option<i64>, _>("replaceable").map(|r| r == 1),
+        replaced_by: r.get("linked_replaced_by"),
+        replaces: r.get("linked_replaces"),
+        conflicted: r.get::<i64, _>("conflicted") == 1,
+        label: r.get("label"),
+    })
+}
+
+#[async_trait]
+impl WalletTransactionRepository for SqliteWalletTransactionRepository {
+    async fn upsert(&self, wallet_id: &str, tx: &WalletTransaction) -> Result<(), DbError> {
+        let counterparties = serde_json::to_string(&tx.counterparties)
+            .map_err(|e| DbError::InvalidInput(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO wallet_transactions (
+                wallet_id, txid, direction, net_sat, fee_sat, block_height, block_hash, block_time,
+                first_seen, counterparties, replaceable, replaced_by, replaces, conflicted
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (wallet_id, txid) DO UPDATE SET
+                direction = excluded.direction,
+                net_sat = excluded.net_sat,
+                fee_sat = excluded.fee_sat,
+                block_height = excluded.block_height,
+                block_hash = excluded.block_hash,
+                block_time = excluded.block_time,
+                first_seen = MIN(first_seen, excluded.first_seen),
+                counterparties = excluded.counterparties,
+                replaceable = excluded.replaceable,
+                replaced_by = excluded.replaced_by,
+                replaces = excluded.replaces,
+                conflicted = excluded.conflicted
+            "#,
+        )
+        .bind(wallet_id)
+        .bind(&tx.txid)
+        .bind(tx.direction.as_str())
+        .bind(tx.net_sat)
+        .bind(tx.fee_sat.map(|f| f as i64))
+        .bind(tx.block_height.map(|h| h as i64))
+        .bind(&tx.block_hash)
+        .bind(tx.block_time)
+        .bind(tx.first_seen)
+        .bind(counterparties)
+        .bind(tx.replaceable)
+        .bind(&tx.replaced_by)
+        .bind(&tx.replaces)
+        .bind(tx.conflicted)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        wallet_id: &str,
+        filter: &TransactionFilter,
+        offset: i64,
+        limit: i64,
+    ) -> Result<Vec<WalletTransaction>, DbError> {
+        let sql = format!(
+            r#"
+            SELECT t.*,
+                CASE WHEN t.block_height IS NULL OR s.tip_height IS NULL THEN 0
+                     ELSE s.tip_height - t.block_height + 1 END AS confirmations,
+                -- The node wallet only links replacements it made itself, so
+                -- those broadcast from the PSBT pipeline come from the sends
+                COALESCE(t.replaced_by, (
+                    SELECT p.txid FROM pending_sends p
+                    WHERE p.wallet_id = t.wallet_id AND p.replaces = t.txid AND p.stage = 'broadcast'
+                    ORDER BY p.updated_at DESC LIMIT 1
+                )) AS linked_replaced_by,
+                COALESCE(t.replaces, (
+                    SELECT p.replaces FROM pending_sends p
+                    WHERE p.wallet_id = t.wallet_id AND p.txid = t.txid AND p.stage = 'broadcast'
+                )) AS linked_replaces
+            FROM wallet_transactions t
+            LEFT JOIN wallet_tx_sync s ON s.wallet_id = t.wallet_id
+            {}
+            ORDER BY t.block_height IS NOT NULL, t.block_height DESC, t.first_seen DESC, t.txid
+            LIMIT ?6 OFFSET ?7
+            "#,
+            FILTER
+        );
+
+        let rows = sqlx::query(&sql)
+            .bind(wallet_id)
+            .bind(filter.direction.map(|d| d.as_str()))
+            .bind(filter.from)
+            .bind(filter.to)
+            .bind(&filter.label)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(transaction_from_row).collect()
+    }
+
+    async fn count(&self, wallet_id: &str, filter: &TransactionFilter) -> Result<u64, DbError> {
+        let sql = format!("SELECT COUNT(*) AS n FROM wallet_transactions t {}", FILTER);
+
+        let row = sqlx::query(&sql)
+            .bind(wallet_id)
+            .bind(filter.direction.map(|d| d.as_str()))
+            .bind(filter.from)
+            .bind(filter.to)
+            .bind(&filter.label)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(row.get::<i64, _>("n") as u64)
+    }
+
+    async fn unconfirmed_txids(&self, wallet_id: &str) -> Result<Vec<String>, DbError> {
+        let rows = sqlx::query(
+            "SELECT txid FROM wallet_transactions WHERE wallet_id = ? AND block_height IS NULL",
+        )
+        .bind(wallet_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(|r| r.get("txid")).collect())
+    }
+
+    async fn cursor(&self, wallet_id: &str) -> Result<Option<TxSyncCursor>, DbError> {
+        let row = sqlx::query("SELECT last_block, tip_height, refreshed_at FROM wallet_tx_sync WHERE wallet_id = ?")
+            .bind(wallet_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.map(|r| TxSyncCursor {
+            last_block: r.get("last_block"),
+            tip_height: r.get::<i64, _>("tip_height") as u64,
+            refreshed_at: r.get("refreshed_at"),
+        }))
+    }
+
+    async fn save_cursor(&self, wallet_id: &str, cursor: &TxSyncCursor) -> Result<(), DbError> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO wallet_tx_sync (wallet_id, last_block, tip_height, refreshed_at)
+            VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(wallet_id)
+        .bind(&cursor.last_block)
+        .bind(cursor.tip_height as i64)
+        .bind(cursor.refreshed_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn set_label(&self, wallet_id: &str, txid: &str, label: Option<&str>) -> Result<(), DbError> {
+        let result = sqlx::query("UPDATE wallet_transactions SET label = ? WHERE wallet_id = ? AND txid = ?")
+            .bind(label)
+            .bind(wallet_id)
+            .bind(txid)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use async_trait::async_trait;
+use sqlx::{Row, SqlitePool};
+
+use crate::db::traits::{DbError, WalletUtxoRepository};
+use crate::domain::utxo::UtxoMeta;
+
+/// SQLite implementation of WalletUtxoRepository
+pub struct SqliteWalletUtxoRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteWalletUtxoRepository {
+    /// Create a new SQLite repository from an existing pool
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl WalletUtxoRepository for SqliteWalletUtxoRepository {
+    async fn list_meta(&self, wallet_id: &str) -> Result<Vec<UtxoMeta>, DbError> {
+        let rows = sqlx::query("SELECT txid, vout, frozen, label FROM wallet_utxo_meta WHERE wallet_id = ?")
+            .bind(wallet_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows
+            .iter()
+            .map(|r| UtxoMeta {
+                txid: r.get("txid"),
+                vout: r.get::<i64, _>("vout") as u32,
+                frozen: r.get::<i64, _>("frozen") == 1,
+                label: r.get("label"),
+            })
+            .collect())
+    }
+
+    async fn set_meta(&self, wallet_id: &str, meta: &UtxoMeta) -> Result<(), DbError> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO wallet_utxo_meta (wallet_id, txid, vout, frozen, label)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(wallet_id)
+        .bind(&meta.txid)
+        .bind(meta.vout)
+        .bind(meta.frozen)
+        .bind(&meta.label)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -1,6 +1,21 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
+use crate::domain::address::{AddressHistoryEntry, AddressTotals, AddressUtxo, IndexedBlock};
+use crate::domain::block_stats::BlockStats;
+use crate::domain::chain::{ChainTip, ReorgEvent, TipObservation};
+use crate::domain::header::BlockHeader;
+use crate::domain::metrics::{Metric, MetricPoint, MetricSample};
+use crate::domain::send::PendingSend;
+use crate::domain::transaction::{TransactionFilter, TxSyncCursor, WalletTransaction};
+use crate::domain::utxo::UtxoMeta;
+use crate::domain::utxoset::{UtxoSetHashType, UtxoSetInfo};
+use crate::crypto::EncryptedSecret;
+use crate::domain::wallet::{
+    AddressChain, AddressChainState, AddressFilter, NewWallet, NodeWalletSync, ScriptType, Wallet,
+    WalletAddress,
+};
+
 // ============================================================================
 // Domain Models
 // ============================================================================
@@ -88,3 +103,269 @@ pub trait NodeConfigRepository: Send + Sync {
     /// Test if a connection can be established with the given configuration
     async fn test_connection(&self, config: &NodeConfig) -> Result<bool, DbError>;
 }
+
+/// Repository trait for chain tip history and reorg events
+#[async_trait]
+pub trait ChainTipRepository: Send + Sync {
+    /// Record a new best-block observation for a node
+    async fn record_best_tip(&self, tip: &TipObservation) -> Result<(), DbError>;
+
+    /// Get the most recent best-block observation for a node
+    async fn latest_best_tip(&self, node_id: &str) -> Result<Option<TipObservation>, DbError>;
+
+    /// List recent best-block observations for a node, newest first
+    async fn list_tip_history(&self, node_id: &str, limit: i64) -> Result<Vec<TipObservation>, DbError>;
+
+    /// Keep only the newest `keep` observations for a node
+    async fn prune_tip_history(&self, node_id: &str, keep: i64) -> Result<(), DbError>;
+
+    /// Replace the stored `getchaintips` snapshot for a node
+    async fn replace_chain_tips(&self, node_id: &str, tips: &[ChainTip]) -> Result<(), DbError>;
+
+    /// Get the stored `getchaintips` snapshot for a node
+    async fn list_chain_tips(&self, node_id: &str) -> Result<Vec<ChainTip>, DbError>;
+
+    /// Store a detected reorg
+    async fn record_reorg(&self, event: &ReorgEvent) -> Result<(), DbError>;
+
+    /// List reorgs, newest first, optionally restricted to one node
+    async fn list_reorgs(&self, node_id: Option<&str>, limit: i64) -> Result<Vec<ReorgEvent>, DbError>;
+}
+
+/// Repository trait for the local block header index
+#[async_trait]
+pub trait HeaderRepository: Send + Sync {
+    /// Get the highest indexed header
+    async fn tip(&self) -> Result<Option<BlockHeader>, DbError>;
+
+    /// Get an indexed header by height
+    async fn get_by_height(&self, height: u64) -> Result<Option<BlockHeader>, DbError>;
+
+    /// Get an indexed header by block hash
+    async fn get_by_hash(&self, hash: &str) -> Result<Option<BlockHeader>, DbError>;
+
+    /// Get the last header whose timestamp is at or before `timestamp`
+    async fn get_at_time(&self, timestamp: i64) -> Result<Option<BlockHeader>, DbError>;
+
+    /// List headers in an inclusive height range, ascending
+    async fn list_range(&self, from: u64, to: u64) -> Result<Vec<BlockHeader>, DbError>;
+
+    /// Insert a batch of consecutive headers
+    async fn insert_batch(&self, headers: &[BlockHeader]) -> Result<(), DbError>;
+
+    /// Delete every header at or above `height` (used when rolling back a reorg)
+    async fn delete_from_height(&self, height: u64) -> Result<u64, DbError>;
+}
+
+/// Repository trait for the optional scriptPubKey address index
+#[async_trait]
+pub trait AddressIndexRepository: Send + Sync {
+    /// Get the height and hash of the last indexed block
+    async fn tip(&self) -> Result<Option<(u64, String)>, DbError>;
+
+    /// Get the hash of the indexed block at a height
+    async fn block_hash_at(&self, height: u64) -> Result<Option<String>, DbError>;
+
+    /// Atomically store a block's outputs and mark the outputs it spends
+    async fn apply_block(&self, block: &IndexedBlock) -> Result<(), DbError>;
+
+    /// Undo every block at or above `height`
+    async fn rollback_from(&self, height: u64) -> Result<(), DbError>;
+
+    /// Received/sent totals and transaction count for a scriptPubKey (hex)
+    async fn totals(&self, script_pub_key: &str) -> Result<AddressTotals, DbError>;
+
+    /// Unspent outputs paying to a scriptPubKey (hex)
+    async fn list_utxos(&self, script_pub_key: &str) -> Result<Vec<AddressUtxo>, DbError>;
+
+    /// Per-transaction history for a scriptPubKey (hex), newest first
+    async fn list_history(&self, script_pub_key: &str, offset: i64, limit: i64) -> Result<Vec<AddressHistoryEntry>, DbError>;
+
+    /// The subset of `script_pub_keys` that received at least one indexed output
+    async fn filter_seen(&self, script_pub_keys: &[String]) -> Result<Vec<String>, DbError>;
+}
+
+/// Repository trait for sampled node metrics
+#[async_trait]
+pub trait MetricsRepository: Send + Sync {
+    /// Store one sample
+    async fn record_sample(&self, sample: &MetricSample) -> Result<(), DbError>;
+
+    /// Aggregate a metric into `bucket_secs` buckets over an inclusive time range, ascending
+    async fn series(
+        &self,
+        node_id: &str,
+        metric: Metric,
+        from: i64,
+        to: i64,
+        bucket_secs: i64,
+    ) -> Result<Vec<MetricPoint>, DbError>;
+
+    /// Delete samples taken before `cutoff`
+    async fn prune_before(&self, cutoff: i64) -> Result<(), DbError>;
+}
+
+/// Repository trait for cached per-block statistics
+#[async_trait]
+pub trait BlockStatsRepository: Send + Sync {
+    /// Get cached statistics for the given block hashes; missing hashes are skipped
+    async fn get_many(&self, hashes: &[String]) -> Result<Vec<BlockStats>, DbError>;
+
+    /// Cache statistics for a block, dropping entries for other blocks at the same height
+    async fn store(&self, stats: &BlockStats) -> Result<(), DbError>;
+}
+
+/// Repository trait for cached gettxoutsetinfo results
+#[async_trait]
+pub trait UtxoSetRepository: Send + Sync {
+    /// Cache a result under its best block and hash type
+    async fn store(&self, info: &UtxoSetInfo) -> Result<(), DbError>;
+
+    /// Get the cached result for a block
+    async fn get(&self, best_block: &str, hash_type: UtxoSetHashType) -> Result<Option<UtxoSetInfo>, DbError>;
+
+    /// List the newest cached results for a hash type, newest first
+    async fn list_recent(&self, hash_type: UtxoSetHashType, limit: i64) -> Result<Vec<UtxoSetInfo>, DbError>;
+}
+
+/// Repository trait for wallet metadata and account keys
+#[async_trait]
+pub trait WalletRepository: Send + Sync {
+    /// Create a wallet together with its accounts
+    async fn create(&self, wallet: NewWallet) -> Result<Wallet, DbError>;
+
+    /// Get a wallet by ID
+    async fn get(&self, id: &str) -> Result<Option<Wallet>, DbError>;
+
+    /// Get a wallet by name (case-insensitive)
+    async fn get_by_name(&self, name: &str) -> Result<Option<Wallet>, DbError>;
+
+    /// List all wallets, newest first
+    async fn list(&self) -> Result<Vec<Wallet>, DbError>;
+
+    /// Get a wallet's encrypted seed
+    async fn get_secret(&self, wallet_id: &str) -> Result<Option<EncryptedSecret>, DbError>;
+}
+
+/// Repository trait for derived wallet addresses and their gap-limit state
+#[async_trait]
+pub trait WalletAddressRepository: Send + Sync {
+    /// Get the state of an account chain, if any address was derived on it
+    async fn chain_state(
+        &self,
+        wallet_id: &str,
+        script_type: ScriptType,
+        chain: AddressChain,
+    ) -> Result<Option<AddressChainState>, DbError>;
+
+    /// Create the state of an account chain; an existing one is kept as it is
+    async fn create_chain_state(
+        &self,
+        wallet_id: &str,
+        script_type: ScriptType,
+        chain: AddressChain,
+        state: AddressChainState,
+    ) -> Result<(), DbError>;
+
+    /// Store derived addresses, keeping existing ones as they are
+    async fn store_addresses(&self, wallet_id: &str, addresses: &[WalletAddress]) -> Result<(), DbError>;
+
+    /// List a wallet's addresses ordered by script type, chain and index
+    async fn list_addresses(&self, wallet_id: &str, filter: &AddressFilter) -> Result<Vec<WalletAddress>, DbError>;
+
+    /// Reserve the next index of an account chain: the one past every issued
+    /// and used address, as long as fewer than the gap limit would be left
+    /// unused behind it. None when the gap limit is reached.
+    async fn reserve_index(
+        &self,
+        wallet_id: &str,
+        script_type: ScriptType,
+        chain: AddressChain,
+    ) -> Result<Option<u32>, DbError>;
+
+    /// Flag addresses as used; returns how many changed
+    async fn mark_used(&self, wallet_id: &str, addresses: &[String]) -> Result<u64, DbError>;
+}
+
+/// Repository trait for the node wallets mirroring ohla wallets
+#[async_trait]
+pub trait NodeWalletRepository: Send + Sync {
+    async fn get(&self, wallet_id: &str) -> Result<Option<NodeWalletSync>, DbError>;
+
+    /// Register a wallet's node wallet; an existing registration is kept as it is
+    async fn enrol(&self, wallet_id: &str, node_wallet: &str, rescan_from: i64) -> Result<(), DbError>;
+
+    /// Change the timestamp the next import rescans from
+    async fn set_rescan_from(&self, wallet_id: &str, rescan_from: i64) -> Result<(), DbError>;
+
+    /// Record the outcome of an import; `node_id` is None when it never reached the node
+    async fn record_import(
+        &self,
+        wallet_id: &str,
+        node_id: Option<&str>,
+        synced_at: i64,
+        error: Option<&str>,
+    ) -> Result<(), DbError>;
+}
+
+/// Repository trait for user metadata on wallet outpoints
+#[async_trait]
+pub trait WalletUtxoRepository: Send + Sync {
+    /// All metadata of a wallet, including outpoints spent since
+    async fn list_meta(&self, wallet_id: &str) -> Result<Vec<UtxoMeta>, DbError>;
+
+    /// Create or replace the metadata of an outpoint
+    async fn set_meta(&self, wallet_id: &str, meta: &UtxoMeta) -> Result<(), DbError>;
+}
+
+/// Repository trait for the locally stored wallet history
+#[async_trait]
+pub trait WalletTransactionRepository: Send + Sync {
+    /// Insert or refresh a transaction; its label and first-seen time are kept
+    async fn upsert(&self, wallet_id: &str, tx: &WalletTransaction) -> Result<(), DbError>;
+
+    /// A page of transactions, newest first. Confirmations are counted
+    /// against the tip stored with the sync cursor.
+    async fn list(
+        &self,
+        wallet_id: &str,
+        filter: &TransactionFilter,
+        offset: i64,
+        limit: i64,
+    ) -> Result<Vec<WalletTransaction>, DbError>;
+
+    async fn count(&self, wallet_id: &str, filter: &TransactionFilter) -> Result<u64, DbError>;
+
+    /// Txids without a block, re-checked on every refresh
+    async fn unconfirmed_txids(&self, wallet_id: &str) -> Result<Vec<String>, DbError>;
+
+    async fn cursor(&self, wallet_id: &str) -> Result<Option<TxSyncCursor>, DbError>;
+
+    async fn save_cursor(&self, wallet_id: &str, cursor: &TxSyncCursor) -> Result<(), DbError>;
+
+    /// Set or clear the label of a stored transaction
+    async fn set_label(&self, wallet_id: &str, txid: &str, label: Option<&str>) -> Result<(), DbError>;
+}
+
+/// Repository trait for sends moving through the PSBT pipeline
+#[async_trait]
+pub trait PendingSendRepository: Send + Sync {
+    async fn create(&self, send: &PendingSend) -> Result<(), DbError>;
+
+    async fn get(&self, id: &str) -> Result<Option<PendingSend>, DbError>;
+
+    /// The send, not abandoned, whose PSBT has this unsigned txid
+    async fn find_by_unsigned_txid(&self, unsigned_txid: &str) -> Result<Option<PendingSend>, DbError>;
+
+    /// A wallet's sends, newest first
+    async fn list(&self, wallet_id: &str) -> Result<Vec<PendingSend>, DbError>;
+
+    /// Store the stage, PSBT, transaction and error of a send
+    async fn update(&self, send: &PendingSend) -> Result<(), DbError>;
+
+    /// Outpoints spent by sends not yet broadcast or abandoned
+    async fn reserved_outpoints(&self, wallet_id: &str) -> Result<Vec<String>, DbError>;
+
+    /// Change addresses of sends that were not abandoned
+    async fn change_addresses(&self, wallet_id: &str) -> Result<Vec<String>, DbError>;
+}
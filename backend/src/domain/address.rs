@@ -0,0 +1,69 @@
+use serde::Serialize;
+
+/// An output created in an indexed block
+#[derive(Debug, Clone)]
+pub struct IndexedOutput {
+    pub txid: String,
+    pub vout: u32,
+    pub script_pub_key: String,
+    pub address: Option<String>,
+    pub value_sat: u64,
+}
+
+/// An input spending a previously created output
+#[derive(Debug, Clone)]
+pub struct IndexedSpend {
+    pub prev_txid: String,
+    pub prev_vout: u32,
+    pub txid: String,
+    pub vin: u32,
+}
+
+/// Everything the address index extracts from one block
+#[derive(Debug, Clone)]
+pub struct IndexedBlock {
+    pub height: u64,
+    pub hash: String,
+    pub outputs: Vec<IndexedOutput>,
+    pub spends: Vec<IndexedSpend>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AddressUtxo {
+    pub txid: String,
+    pub vout: u32,
+    pub value_sat: u64,
+    pub height: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AddressHistoryEntry {
+    pub txid: String,
+    pub height: u64,
+    pub received_sat: u64,
+    pub sent_sat: u64,
+    pub net_sat: i64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AddressTotals {
+    pub total_received_sat: u64,
+    pub total_sent_sat: u64,
+    pub tx_count: u64,
+}
+
+#[derive(Serialize)]
+pub struct AddressDetails {
+    pub address: String,
+    pub script_pub_key: String,
+    pub balance_sat: u64,
+    pub total_received_sat: u64,
+    pub total_sent_sat: u64,
+    pub tx_count: u64,
+    pub utxos: Vec<AddressUtxo>,
+    pub history: Vec<AddressHistoryEntry>,
+    pub page: u32,
+    pub page_size: u32,
+    /// Height the index has been built up to
+    pub indexed_height: Option<u64>,
+}
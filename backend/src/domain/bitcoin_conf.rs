@@ -0,0 +1,36 @@
+use serde::Serialize;
+
+/// Where the proposed RPC credentials came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RpcAuthSource {
+    /// rpcuser / rpcpassword
+    Password,
+    /// The `.cookie` file in the network's datadir
+    Cookie,
+    /// No usable credentials (e.g. only `rpcauth`, whose password cannot be recovered)
+    Missing,
+}
+
+/// A node configuration derived from one network of a bitcoin.conf
+#[derive(Debug, Clone, Serialize)]
+pub struct ProposedNodeConfig {
+    /// Chain name as reported by getblockchaininfo (`main`, `test`, `testnet4`, `signet`, `regtest`)
+    pub network: String,
+    pub name: String,
+    pub rpc_url: String,
+    pub rpc_user: String,
+    pub rpc_password: String,
+    pub auth_source: RpcAuthSource,
+    pub warnings: Vec<String>,
+    /// Matching stored configuration, created now or already present
+    pub node_config_id: Option<String>,
+    pub created: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BitcoinConfImport {
+    pub proposals: Vec<ProposedNodeConfig>,
+    /// Issues that are not specific to one network
+    pub warnings: Vec<String>,
+}
@@ -0,0 +1,53 @@
+use serde::Serialize;
+
+/// Maximum block weight (BIP141)
+pub const MAX_BLOCK_WEIGHT: u64 = 4_000_000;
+
+/// Figures for one block, from `getblockstats` plus input types from `getblock`
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockStats {
+    pub height: u64,
+    pub hash: String,
+    pub time: i64,
+    /// Including the coinbase
+    pub tx_count: u64,
+    pub total_fee_sat: u64,
+    pub subsidy_sat: u64,
+    /// sat/vB
+    pub median_feerate: f64,
+    /// sat/vB
+    pub avg_feerate: f64,
+    pub total_size: u64,
+    pub total_weight: u64,
+    /// `total_weight` over the 4M weight limit
+    pub fullness: f64,
+    pub segwit_tx_count: u64,
+    /// Non-coinbase inputs
+    pub inputs: u64,
+    pub segwit_inputs: u64,
+    /// Unknown when the node does not report prevouts (`getblock` verbosity 3, Core 23+)
+    pub taproot_inputs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockStatsSummary {
+    pub blocks: u64,
+    pub tx_count: u64,
+    pub total_fee_sat: u64,
+    pub total_subsidy_sat: u64,
+    /// Median of the per-block median feerates (sat/vB)
+    pub median_feerate: f64,
+    /// Total fees over total virtual size of the range's non-coinbase transactions (sat/vB)
+    pub avg_feerate: f64,
+    pub avg_fullness: f64,
+    pub segwit_input_share: Option<f64>,
+    pub taproot_input_share: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainStats {
+    pub from: u64,
+    pub to: u64,
+    pub summary: BlockStatsSummary,
+    pub blocks: Vec<BlockStats>,
+}
@@ -0,0 +1,55 @@
+use serde::Serialize;
+
+/// A best-block observation recorded by the chain monitor
+#[derive(Debug, Clone, Serialize)]
+pub struct TipObservation {
+    pub node_id: String,
+    pub height: u64,
+    pub hash: String,
+    pub observed_at: i64,
+}
+
+/// An entry from `getchaintips` for a tracked node
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainTip {
+    pub height: u64,
+    pub hash: String,
+    pub branch_len: u64,
+    /// active, valid-fork, valid-headers, headers-only or invalid
+    pub status: String,
+}
+
+#[derive(Serialize)]
+pub struct NodeChainTips {
+    pub node_id: String,
+    pub node_name: String,
+    pub best_tip: Option<TipObservation>,
+    pub tips: Vec<ChainTip>,
+}
+
+/// A reorganization detected on a tracked node
+#[derive(Debug, Clone, Serialize)]
+pub struct ReorgEvent {
+    pub id: String,
+    pub node_id: String,
+    /// Last block shared by the old and new chains
+    pub fork_height: u64,
+    pub fork_hash: String,
+    /// Number of blocks disconnected from the old chain
+    pub depth: u64,
+    pub old_tip_height: u64,
+    pub old_tip_hash: String,
+    pub new_tip_height: u64,
+    pub new_tip_hash: String,
+    /// Disconnected block hashes, old tip first
+    pub orphaned_hashes: Vec<String>,
+    pub detected_at: i64,
+}
+
+/// Events broadcast by the chain monitor to interested services
+#[derive(Debug, Clone)]
+#[allow(unused)]
+pub enum ChainEvent {
+    NewTip(TipObservation),
+    Reorg(ReorgEvent),
+}
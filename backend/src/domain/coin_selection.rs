@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::utxo::Amount;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelectionAlgorithm {
+    /// Branch-and-Bound: an input set matching the target closely enough to need no change
+    Bnb,
+    /// Bitcoin Core's knapsack solver: randomized subset sums aiming for modest change
+    Knapsack,
+    /// Largest effective values first until the target is covered
+    LargestFirst,
+    /// Random coins until the target plus a change output is covered
+    SingleRandomDraw,
+}
+
+impl SelectionAlgorithm {
+    pub const ALL: [SelectionAlgorithm; 4] = [
+        SelectionAlgorithm::Bnb,
+        SelectionAlgorithm::Knapsack,
+        SelectionAlgorithm::LargestFirst,
+        SelectionAlgorithm::SingleRandomDraw,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SelectionAlgorithm::Bnb => "bnb",
+            SelectionAlgorithm::Knapsack => "knapsack",
+            SelectionAlgorithm::LargestFirst => "largest_first",
+            SelectionAlgorithm::SingleRandomDraw => "single_random_draw",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.as_str() == s)
+    }
+}
+
+/// An input picked by coin selection
+#[derive(Debug, Clone, Serialize)]
+pub struct SelectedInput {
+    /// txid:vout
+    pub outpoint: String,
+    pub value: Amount,
+    /// Value minus the fee of spending it at the target feerate
+    pub effective_value_sat: i64,
+    /// Picked by the caller rather than the algorithm
+    pub must_spend: bool,
+}
+
+/// Inputs picked by an algorithm with the resulting fee and change
+#[derive(Debug, Clone, Serialize)]
+pub struct CoinSelection {
+    pub algorithm: SelectionAlgorithm,
+    pub inputs: Vec<SelectedInput>,
+    pub input_total: Amount,
+    pub fee: Amount,
+    /// None when the excess was too small for a change output and went to fees
+    pub change: Option<Amount>,
+    pub vsize: u64,
+    /// Fee paid now above the long-term feerate, plus the cost of change or the
+    /// excess dropped to fees; lower is better
+    pub waste_sat: i64,
+}
+
+/// What one algorithm would pick, or why it found no solution
+#[derive(Debug, Serialize)]
+pub struct SelectionOutcome {
+    pub algorithm: SelectionAlgorithm,
+    pub selection: Option<CoinSelection>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CoinSelectionPreview {
+    pub wallet_id: String,
+    /// sat/vB
+    pub feerate: f64,
+    /// sat/vB
+    pub long_term_feerate: f64,
+    /// Sum of the recipient amounts
+    pub target: Amount,
+    /// Value of the outputs considered: the pool plus must-spend outpoints
+    pub available: Amount,
+    pub excluded_frozen: usize,
+    pub excluded_unsafe: usize,
+    /// Spent by sends not yet broadcast or abandoned
+    pub excluded_reserved: usize,
+    pub outcomes: Vec<SelectionOutcome>,
+    /// The successful algorithm with the lowest waste
+    pub recommended: Option<SelectionAlgorithm>,
+}
@@ -0,0 +1,56 @@
+use serde::Serialize;
+
+/// Soft fork deployment state at the active node's tip
+#[derive(Debug, Clone, Serialize)]
+pub struct DeploymentInfo {
+    /// `getdeploymentinfo`, or `getblockchaininfo` on nodes older than Core 23
+    pub source: &'static str,
+    pub hash: String,
+    pub height: u64,
+    pub deployments: Vec<Deployment>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Deployment {
+    pub name: String,
+    /// `buried` or `bip9`
+    #[serde(rename = "type")]
+    pub deployment_type: String,
+    pub active: bool,
+    /// Activation height, once known
+    pub height: Option<u64>,
+    pub bip9: Option<Bip9Deployment>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Bip9Deployment {
+    pub bit: Option<u8>,
+    pub start_time: i64,
+    pub timeout: i64,
+    pub min_activation_height: Option<u64>,
+    /// `defined`, `started`, `locked_in`, `active` or `failed`
+    pub status: String,
+    /// Height of the first block with this status
+    pub since: u64,
+    pub status_next: Option<String>,
+    /// Signalling in the period containing the tip (while started or locked in)
+    pub current_period: Option<SignallingPeriod>,
+    /// Per-block signalling of the current period, `#` signalling and `-` not
+    pub signalling: Option<String>,
+    /// Completed periods, newest first
+    pub previous_periods: Vec<SignallingPeriod>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SignallingPeriod {
+    pub start_height: u64,
+    pub length: u64,
+    /// Signalling blocks needed for lock-in; absent once locked in
+    pub threshold: Option<u64>,
+    pub elapsed: u64,
+    pub count: u64,
+    /// Share of elapsed blocks that signalled
+    pub ratio: f64,
+    /// Whether the threshold can still be reached this period
+    pub possible: Option<bool>,
+}
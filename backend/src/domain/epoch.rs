@@ -0,0 +1,51 @@
+use serde::Serialize;
+
+/// Difficulty epoch progress with retarget, halving and hashrate projections
+#[derive(Debug, Clone, Serialize)]
+pub struct EpochInfo {
+    pub network: String,
+    pub height: u64,
+    pub difficulty: f64,
+    /// Blocks per difficulty epoch (2016)
+    pub epoch_length: u64,
+    pub epoch_start_height: u64,
+    pub blocks_into_epoch: u64,
+    /// Fraction of the epoch mined, 0.0 - 1.0
+    pub progress: f64,
+    /// False on regtest, where difficulty never changes
+    pub retargeting: bool,
+    pub retarget: Option<RetargetProjection>,
+    pub halving: HalvingProjection,
+    pub hashrate: Vec<HashrateWindow>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RetargetProjection {
+    pub next_retarget_height: u64,
+    pub blocks_remaining: u64,
+    /// Mean block interval so far this epoch; absent until the epoch has a second block
+    pub avg_block_interval_secs: Option<f64>,
+    pub projected_difficulty: Option<f64>,
+    /// Percentage change from the current difficulty
+    pub projected_adjustment_pct: Option<f64>,
+    pub estimated_secs_remaining: u64,
+    pub estimated_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HalvingProjection {
+    pub interval: u64,
+    pub next_halving_height: u64,
+    pub blocks_remaining: u64,
+    pub current_subsidy_sat: u64,
+    pub next_subsidy_sat: u64,
+    /// At the target block spacing; absent on regtest, where blocks are mined on demand
+    pub estimated_secs_remaining: Option<u64>,
+    pub estimated_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HashrateWindow {
+    pub blocks: u64,
+    pub hashes_per_second: f64,
+}
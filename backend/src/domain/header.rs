@@ -0,0 +1,27 @@
+use serde::Serialize;
+
+/// A block header stored in the local header index
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockHeader {
+    pub height: u64,
+    pub hash: String,
+    pub previous_hash: Option<String>,
+    pub version: i64,
+    pub merkle_root: String,
+    pub time: i64,
+    pub median_time: i64,
+    pub nonce: u64,
+    pub bits: String,
+    pub difficulty: f64,
+    pub chainwork: String,
+    pub tx_count: u64,
+}
+
+#[derive(Serialize)]
+pub struct HeaderIndexStatus {
+    /// Height of the highest indexed header, if any
+    pub indexed_height: Option<u64>,
+    pub indexed_tip_hash: Option<String>,
+    pub node_height: u64,
+    pub synced: bool,
+}
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+/// Networks the managed node may run on; real-money networks are not offered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ManagedNetwork {
+    Regtest,
+    Signet,
+}
+
+impl ManagedNetwork {
+    /// Name used by bitcoind for the config section and datadir subdirectory
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ManagedNetwork::Regtest => "regtest",
+            ManagedNetwork::Signet => "signet",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ManagedNodeState {
+    Stopped,
+    Starting,
+    Running,
+    Stopping,
+    /// The process exited without being asked to
+    Exited,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ManagedNodeStatus {
+    pub state: ManagedNodeState,
+    pub network: Option<ManagedNetwork>,
+    pub pid: Option<u32>,
+    pub data_dir: Option<String>,
+    pub rpc_url: Option<String>,
+    /// Node configuration created for the managed node
+    pub node_config_id: Option<String>,
+    pub started_at: Option<i64>,
+    pub exit_code: Option<i32>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogSource {
+    /// Captured stdout/stderr of the process
+    #[default]
+    Stdout,
+    /// bitcoind's own debug.log
+    Debug,
+}
+
+#[derive(Serialize)]
+pub struct ManagedNodeLogs {
+    pub source: LogSource,
+    pub path: String,
+    pub lines: Vec<String>,
+}
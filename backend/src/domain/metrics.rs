@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+/// One observation of a node, taken by the metrics sampler
+#[derive(Debug, Clone)]
+pub struct MetricSample {
+    pub node_id: String,
+    pub sampled_at: i64,
+    pub block_height: u64,
+    pub headers: u64,
+    pub mempool_size: u64,
+    pub mempool_bytes: u64,
+    pub peer_count: u64,
+    /// Cumulative counters from `getnettotals`
+    pub bytes_recv: u64,
+    pub bytes_sent: u64,
+    pub verification_progress: f64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Metric {
+    BlockHeight,
+    Headers,
+    MempoolSize,
+    MempoolBytes,
+    PeerCount,
+    BytesRecv,
+    BytesSent,
+    VerificationProgress,
+}
+
+impl Metric {
+    /// Column of `node_metrics` holding this metric
+    pub fn column(&self) -> &'static str {
+        match self {
+            Metric::BlockHeight => "block_height",
+            Metric::Headers => "headers",
+            Metric::MempoolSize => "mempool_size",
+            Metric::MempoolBytes => "mempool_bytes",
+            Metric::PeerCount => "peer_count",
+            Metric::BytesRecv => "bytes_recv",
+            Metric::BytesSent => "bytes_sent",
+            Metric::VerificationProgress => "verification_progress",
+        }
+    }
+}
+
+/// Aggregate of the samples in one time bucket
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricPoint {
+    /// Start of the bucket (unix seconds)
+    pub timestamp: i64,
+    pub avg: f64,
+    pub min: f64,
+    pub max: f64,
+    pub samples: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricSeries {
+    pub node_id: String,
+    pub metric: Metric,
+    pub from: i64,
+    pub to: i64,
+    pub resolution_secs: i64,
+    pub points: Vec<MetricPoint>,
+}
@@ -1 +1,21 @@
+pub mod address;
+pub mod bitcoin_conf;
+pub mod block_stats;
+pub mod chain;
+pub mod coin_selection;
+pub mod deployment;
+pub mod epoch;
+pub mod header;
+pub mod managed_node;
+pub mod metrics;
+pub mod network;
 pub mod node;
+pub mod regtest;
+pub mod scan;
+pub mod scenario;
+pub mod search;
+pub mod send;
+pub mod transaction;
+pub mod utxo;
+pub mod utxoset;
+pub mod wallet;
@@ -0,0 +1,111 @@
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct PeerInfo {
+    pub id: i64,
+    pub address: String,
+    /// ipv4, ipv6, onion, i2p, cjdns or not_publicly_routable
+    pub network: String,
+    /// inbound or outbound
+    pub direction: &'static str,
+    /// outbound-full-relay, block-relay-only, inbound, manual, addr-fetch or feeler
+    pub connection_type: String,
+    pub services: PeerServices,
+    pub ping_ms: Option<f64>,
+    pub min_ping_ms: Option<f64>,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub sync: PeerSync,
+    pub version: i64,
+    pub user_agent: String,
+    pub connected_since: i64,
+}
+
+#[derive(Serialize)]
+pub struct PeerServices {
+    pub hex: String,
+    pub names: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct PeerSync {
+    pub starting_height: Option<i64>,
+    pub synced_headers: Option<i64>,
+    pub synced_blocks: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct NetworkInfo {
+    pub version: u64,
+    pub user_agent: String,
+    pub protocol_version: u64,
+    pub local_services: PeerServices,
+    pub local_relay: bool,
+    pub time_offset: i64,
+    pub network_active: bool,
+    pub connections: ConnectionCounts,
+    pub networks: Vec<NetworkReachability>,
+    pub relay_fee: f64,
+    pub incremental_fee: f64,
+    pub local_addresses: Vec<LocalAddress>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct ConnectionCounts {
+    pub total: u64,
+    pub inbound: Option<u64>,
+    pub outbound: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct NetworkReachability {
+    pub name: String,
+    pub limited: bool,
+    pub reachable: bool,
+    pub proxy: String,
+}
+
+#[derive(Serialize)]
+pub struct LocalAddress {
+    pub address: String,
+    pub port: u16,
+    pub score: i64,
+}
+
+#[derive(Serialize)]
+pub struct BannedPeer {
+    pub subnet: String,
+    pub banned_at: i64,
+    pub banned_until: i64,
+}
+
+#[derive(Serialize)]
+pub struct NetworkActive {
+    pub network_active: bool,
+}
+
+/// Classify a peer address into the network it was reached on.
+/// Bitcoin Core reports this directly since v0.21; older nodes only give us the address.
+pub fn classify_peer_network(addr: &str, reported: Option<&str>) -> String {
+    if let Some(network) = reported {
+        return network.to_string();
+    }
+
+    let host = match addr.rsplit_once(':') {
+        Some((host, _)) if !addr.ends_with(']') => host,
+        _ => addr,
+    };
+
+    if host.ends_with(".onion") {
+        "onion".to_string()
+    } else if host.ends_with(".i2p") {
+        "i2p".to_string()
+    } else if host.starts_with("[fc") {
+        "cjdns".to_string()
+    } else if host.starts_with('[') {
+        "ipv6".to_string()
+    } else {
+        "ipv4".to_string()
+    }
+}
@@ -0,0 +1,27 @@
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct MinedBlocks {
+    pub block_hashes: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct GeneratedBlock {
+    pub hash: String,
+}
+
+#[derive(Serialize)]
+pub struct SimulatedReorg {
+    /// First block disconnected from the old chain
+    pub invalidated_hash: String,
+    pub depth: u64,
+    /// Blocks mined on the replacement chain
+    pub block_hashes: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct FaucetPayment {
+    pub txid: String,
+    pub address: String,
+    pub amount_sat: u64,
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+/// A descriptor to scan for, optionally with a derivation range for ranged descriptors
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanDescriptor {
+    pub desc: String,
+    /// `[begin, end]` or a single end index (Bitcoin Core defaults to 1000)
+    pub range: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScanJobStatus {
+    Running,
+    Completed,
+    Aborted,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScannedUtxo {
+    pub txid: String,
+    pub vout: u32,
+    pub script_pub_key: String,
+    pub descriptor: String,
+    pub amount_sat: u64,
+    pub height: u64,
+    pub coinbase: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanResult {
+    /// Number of UTXOs in the set at the time of the scan
+    pub txouts_scanned: u64,
+    pub height: u64,
+    pub best_block: String,
+    pub utxos: Vec<ScannedUtxo>,
+    pub total_amount_sat: u64,
+}
+
+/// A tracked `scantxoutset` run
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanJob {
+    pub id: String,
+    pub status: ScanJobStatus,
+    /// Percentage reported by `scantxoutset status` while running
+    pub progress: Option<f64>,
+    pub descriptors: Vec<ScanDescriptor>,
+    pub started_at: i64,
+    pub finished_at: Option<i64>,
+    pub result: Option<ScanResult>,
+    pub error: Option<String>,
+}
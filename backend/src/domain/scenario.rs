@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+
+/// A declarative regtest scenario, written in YAML or JSON
+#[derive(Debug, Clone, Deserialize)]
+pub struct Scenario {
+    pub name: String,
+    pub description: Option<String>,
+    pub steps: Vec<ScenarioStep>,
+}
+
+/// One step of a scenario. Wallets are node wallets created on demand;
+/// `save_as` stores a txid under a name that later steps refer to with `tx`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ScenarioStep {
+    /// Create (or load) a node wallet
+    CreateWallet { wallet: String },
+    /// Mine blocks to a wallet or an explicit address
+    Mine {
+        blocks: u64,
+        wallet: Option<String>,
+        address: Option<String>,
+    },
+    /// Send from one wallet to another wallet or to an address
+    Send {
+        from: String,
+        to_wallet: Option<String>,
+        to_address: Option<String>,
+        amount_sat: u64,
+        /// Signal BIP125 replaceability
+        replaceable: Option<bool>,
+        save_as: Option<String>,
+    },
+    /// Replace a previously saved transaction with a higher-fee version
+    BumpFee {
+        wallet: String,
+        tx: String,
+        save_as: Option<String>,
+    },
+    /// Replace the last `depth` blocks with a longer branch
+    Reorg {
+        depth: u64,
+        wallet: Option<String>,
+        address: Option<String>,
+    },
+    /// Set the node clock (0 returns to system time)
+    SetMockTime { timestamp: i64 },
+    /// Check a wallet balance in satoshis
+    AssertBalance {
+        wallet: String,
+        equals_sat: Option<u64>,
+        min_sat: Option<u64>,
+        max_sat: Option<u64>,
+        /// Count untrusted pending funds as well as confirmed ones
+        #[serde(default)]
+        include_pending: bool,
+    },
+    /// Check the confirmation count of a saved transaction
+    AssertConfirmations {
+        wallet: String,
+        tx: String,
+        min: Option<i64>,
+        max: Option<i64>,
+    },
+}
+
+impl ScenarioStep {
+    pub fn action(&self) -> &'static str {
+        match self {
+            ScenarioStep::CreateWallet { .. } => "create_wallet",
+            ScenarioStep::Mine { .. } => "mine",
+            ScenarioStep::Send { .. } => "send",
+            ScenarioStep::BumpFee { .. } => "bump_fee",
+            ScenarioStep::Reorg { .. } => "reorg",
+            ScenarioStep::SetMockTime { .. } => "set_mock_time",
+            ScenarioStep::AssertBalance { .. } => "assert_balance",
+            ScenarioStep::AssertConfirmations { .. } => "assert_confirmations",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+    Passed,
+    Failed,
+}
+
+/// Streamed after each step
+#[derive(Debug, Clone, Serialize)]
+pub struct StepResult {
+    pub index: usize,
+    pub action: &'static str,
+    pub status: StepStatus,
+    /// Step-specific output (block hashes, txids, balances, ...)
+    pub output: serde_json::Value,
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// Streamed once the scenario stops
+#[derive(Debug, Clone, Serialize)]
+pub struct ScenarioSummary {
+    pub name: String,
+    pub passed: bool,
+    pub steps_total: usize,
+    pub steps_run: usize,
+    pub duration_ms: u64,
+}
+
+#[derive(Serialize)]
+pub struct ScenarioValidation {
+    pub name: String,
+    pub description: Option<String>,
+    pub steps: Vec<&'static str>,
+}
@@ -0,0 +1,55 @@
+use serde::Serialize;
+
+/// What a search query resolved to
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SearchResult {
+    Block {
+        hash: String,
+        height: u64,
+        /// -1 when the block is not on the active chain
+        confirmations: i64,
+        time: i64,
+        n_tx: u64,
+    },
+    Transaction {
+        txid: String,
+        /// None while the transaction is in the mempool
+        block_hash: Option<String>,
+        confirmations: u64,
+    },
+    Address {
+        address: String,
+        script_pub_key: Option<String>,
+        is_script: bool,
+        witness_version: Option<u32>,
+    },
+    ExtendedKey {
+        key: String,
+        /// Version prefix, e.g. "xpub", "zpub", "tpub"
+        prefix: String,
+        network: String,
+        /// Script type implied by a SLIP-132 prefix
+        script_type: Option<String>,
+    },
+    Descriptor {
+        descriptor: String,
+        checksum: String,
+        is_range: bool,
+        is_solvable: bool,
+    },
+    Node {
+        id: String,
+        name: String,
+        network: String,
+        is_active: bool,
+    },
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchResponse {
+    pub query: String,
+    pub result: SearchResult,
+    /// Canonical path of the resource for the frontend to navigate to
+    pub path: String,
+}
@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::coin_selection::SelectionAlgorithm;
+
+/// Where a send is in the build, sign, finalize, broadcast pipeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SendStage {
+    /// Unsigned PSBT built; its inputs are reserved
+    Created,
+    /// Signed by the wallet, possibly only partially
+    Signed,
+    /// Final transaction extracted and ready to broadcast
+    Finalized,
+    Broadcast,
+    /// Given up before broadcast; its inputs are released
+    Abandoned,
+}
+
+impl SendStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SendStage::Created => "created",
+            SendStage::Signed => "signed",
+            SendStage::Finalized => "finalized",
+            SendStage::Broadcast => "broadcast",
+            SendStage::Abandoned => "abandoned",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "created" => Some(SendStage::Created),
+            "signed" => Some(SendStage::Signed),
+            "finalized" => Some(SendStage::Finalized),
+            "broadcast" => Some(SendStage::Broadcast),
+            "abandoned" => Some(SendStage::Abandoned),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendRecipient {
+    pub address: String,
+    pub amount_sat: u64,
+}
+
+/// A send on its way through the PSBT pipeline, persisted at every stage so
+/// it can be resumed after a restart
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingSend {
+    pub id: String,
+    pub wallet_id: String,
+    pub stage: SendStage,
+    /// Txid of the unsigned transaction, which identifies the PSBT at every stage
+    pub unsigned_txid: String,
+    /// Latest PSBT, base64
+    pub psbt: String,
+    /// Set once finalized
+    pub tx_hex: Option<String>,
+    pub txid: Option<String>,
+    /// txid:vout of every input
+    pub inputs: Vec<String>,
+    pub recipients: Vec<SendRecipient>,
+    pub change_address: Option<String>,
+    pub change_sat: Option<u64>,
+    pub fee_sat: u64,
+    /// sat/vB
+    pub feerate: f64,
+    pub vsize: u64,
+    pub algorithm: Option<SelectionAlgorithm>,
+    /// Txid of the wallet transaction this send replaces (BIP125)
+    pub replaces: Option<String>,
+    /// Why the last signing, finalizing or broadcast attempt failed
+    pub last_error: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// What a replacement does with the original's payments
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BumpMode {
+    /// Keep the payments; the higher fee comes out of change or extra inputs
+    Bump,
+    /// Drop the payments and send everything back to the wallet
+    Cancel,
+}
+
+/// A BIP125 replacement of a wallet transaction, built as a new send
+#[derive(Debug, Serialize)]
+pub struct FeeBump {
+    pub mode: BumpMode,
+    pub original_txid: String,
+    pub original_fee_sat: u64,
+    pub original_vsize: u64,
+    /// sat/vB
+    pub original_feerate: f64,
+    /// Fees of the transactions it evicts plus the incremental relay fee for its own size
+    pub min_fee_sat: u64,
+    pub send: PendingSend,
+}
+
+/// Result of finalizing a PSBT
+#[derive(Debug, Serialize)]
+pub struct FinalizedTransaction {
+    /// Set when the PSBT belongs to a stored send
+    pub send_id: Option<String>,
+    pub txid: String,
+    pub tx_hex: String,
+}
+
+/// Result of a broadcast that the mempool accepted
+#[derive(Debug, Serialize)]
+pub struct BroadcastResult {
+    /// Set when the transaction belongs to a stored send
+    pub send_id: Option<String>,
+    pub txid: String,
+}
@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TxDirection {
+    Incoming,
+    Outgoing,
+    /// We funded it and every output is ours
+    #[serde(rename = "self")]
+    SelfTransfer,
+}
+
+impl TxDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TxDirection::Incoming => "incoming",
+            TxDirection::Outgoing => "outgoing",
+            TxDirection::SelfTransfer => "self",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "incoming" => Some(TxDirection::Incoming),
+            "outgoing" => Some(TxDirection::Outgoing),
+            "self" => Some(TxDirection::SelfTransfer),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WalletTransaction {
+    pub txid: String,
+    pub direction: TxDirection,
+    /// Change of the wallet balance, fee included
+    pub net_sat: i64,
+    /// Only known when we funded the inputs
+    pub fee_sat: Option<u64>,
+    /// 0 while unconfirmed or conflicted
+    pub confirmations: u64,
+    pub block_height: Option<u64>,
+    pub block_hash: Option<String>,
+    pub block_time: Option<i64>,
+    /// When the node wallet first saw the transaction
+    pub first_seen: i64,
+    /// Outputs we paid for outgoing transactions, input addresses for incoming ones
+    pub counterparties: Vec<String>,
+    /// BIP125 signalling; None when unknown
+    pub replaceable: Option<bool>,
+    pub replaced_by: Option<String>,
+    pub replaces: Option<String>,
+    /// Double-spent by a transaction on the active chain
+    pub conflicted: bool,
+    pub label: Option<String>,
+}
+
+/// How far the stored history of a wallet is refreshed
+#[derive(Debug, Clone)]
+pub struct TxSyncCursor {
+    pub last_block: String,
+    pub tip_height: u64,
+    pub refreshed_at: i64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TransactionFilter {
+    pub direction: Option<TxDirection>,
+    /// Inclusive bounds on block time, or first-seen time while unconfirmed
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+    /// Case-insensitive substring of the label
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TransactionPage {
+    pub transactions: Vec<WalletTransaction>,
+    pub page: u32,
+    pub page_size: u32,
+    pub total: u64,
+    /// Block the stored history is refreshed up to
+    pub synced_block: Option<String>,
+}
@@ -0,0 +1,67 @@
+use serde::Serialize;
+
+use crate::domain::wallet::ScriptType;
+use crate::utils::amount::sats_to_btc;
+
+/// An amount reported in both satoshis and BTC
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Amount {
+    pub sat: u64,
+    pub btc: f64,
+}
+
+impl Amount {
+    pub fn from_sat(sat: u64) -> Self {
+        Self {
+            sat,
+            btc: sats_to_btc(sat),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct WalletBalance {
+    pub wallet_id: String,
+    /// Data source the balance came from, e.g. "node_wallet"
+    pub source: &'static str,
+    /// Outputs with at least one confirmation
+    pub confirmed: Amount,
+    /// Unconfirmed outputs of transactions we funded (e.g. change)
+    pub trusted_pending: Amount,
+    /// Unconfirmed outputs from third parties
+    pub untrusted_pending: Amount,
+    /// Coinbase outputs younger than 100 blocks
+    pub immature: Amount,
+    /// Confirmed plus trusted pending: what can be spent right away
+    pub spendable: Amount,
+    pub total: Amount,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WalletUtxo {
+    /// txid:vout
+    pub outpoint: String,
+    pub txid: String,
+    pub vout: u32,
+    pub value: Amount,
+    pub address: Option<String>,
+    pub script_pub_key: String,
+    pub script_type: Option<ScriptType>,
+    /// e.g. m/84'/0'/0'/0/5
+    pub derivation_path: Option<String>,
+    pub confirmations: u32,
+    /// False for unconfirmed outputs from third parties
+    pub safe: bool,
+    /// Frozen outputs are never picked by coin selection
+    pub frozen: bool,
+    pub label: Option<String>,
+}
+
+/// User metadata attached to an outpoint
+#[derive(Debug, Clone, Serialize)]
+pub struct UtxoMeta {
+    pub txid: String,
+    pub vout: u32,
+    pub frozen: bool,
+    pub label: Option<String>,
+}
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+/// `hash_type` argument of gettxoutsetinfo
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UtxoSetHashType {
+    #[default]
+    #[serde(rename = "hash_serialized_3")]
+    HashSerialized3,
+    Muhash,
+    None,
+}
+
+impl UtxoSetHashType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UtxoSetHashType::HashSerialized3 => "hash_serialized_3",
+            UtxoSetHashType::Muhash => "muhash",
+            UtxoSetHashType::None => "none",
+        }
+    }
+}
+
+/// A gettxoutsetinfo result, cached per block hash and hash type
+#[derive(Debug, Clone, Serialize)]
+pub struct UtxoSetInfo {
+    pub height: u64,
+    pub best_block: String,
+    pub hash_type: UtxoSetHashType,
+    pub hash: Option<String>,
+    pub txouts: u64,
+    pub bogosize: u64,
+    pub total_amount_sat: u64,
+    /// Not reported when answered from coinstatsindex
+    pub transactions: Option<u64>,
+    pub disk_size: Option<u64>,
+    pub computed_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UtxoSetJob {
+    pub hash_type: UtxoSetHashType,
+    pub started_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UtxoSetSummary {
+    /// Newest cached result on the node's active chain, if any
+    pub result: Option<UtxoSetInfo>,
+    pub age_secs: Option<i64>,
+    /// Blocks mined since the result's best block
+    pub blocks_behind: Option<u64>,
+    /// Background computation in progress
+    pub job: Option<UtxoSetJob>,
+    /// Error of the last background computation, if it failed
+    pub last_error: Option<String>,
+}
@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+
+use zeroize::Zeroizing;
+
+use crate::crypto::EncryptedSecret;
+
+/// Single-key script types with a BIP43 purpose
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ScriptType {
+    P2pkh,
+    P2shP2wpkh,
+    P2wpkh,
+    P2tr,
+}
+
+impl ScriptType {
+    pub const ALL: [ScriptType; 4] = [
+        ScriptType::P2pkh,
+        ScriptType::P2shP2wpkh,
+        ScriptType::P2wpkh,
+        ScriptType::P2tr,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScriptType::P2pkh => "p2pkh",
+            ScriptType::P2shP2wpkh => "p2sh-p2wpkh",
+            ScriptType::P2wpkh => "p2wpkh",
+            ScriptType::P2tr => "p2tr",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.as_str() == value)
+    }
+
+    /// BIP44, BIP49, BIP84 and BIP86 purpose
+    pub fn purpose(&self) -> u32 {
+        match self {
+            ScriptType::P2pkh => 44,
+            ScriptType::P2shP2wpkh => 49,
+            ScriptType::P2wpkh => 84,
+            ScriptType::P2tr => 86,
+        }
+    }
+}
+
+/// BIP44 chain: receiving addresses or change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AddressChain {
+    External,
+    Internal,
+}
+
+impl AddressChain {
+    pub const ALL: [AddressChain; 2] = [AddressChain::External, AddressChain::Internal];
+
+    /// Index in the derivation path (0 external, 1 change)
+    pub fn index(&self) -> u32 {
+        match self {
+            AddressChain::External => 0,
+            AddressChain::Internal => 1,
+        }
+    }
+
+    pub fn from_index(index: u32) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.index() == index)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WalletKind {
+    /// Keys derived from a BIP39 mnemonic
+    Seed,
+    /// Public keys only; signing is refused
+    WatchOnly,
+}
+
+impl WalletKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WalletKind::Seed => "seed",
+            WalletKind::WatchOnly => "watch_only",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "seed" => Some(WalletKind::Seed),
+            "watch_only" => Some(WalletKind::WatchOnly),
+            _ => None,
+        }
+    }
+}
+
+/// Account-level extended public key of a wallet
+#[derive(Debug, Clone, Serialize)]
+pub struct WalletAccount {
+    pub script_type: ScriptType,
+    /// e.g. m/84'/0'/0'; just "m" for watch-only keys of unknown origin
+    pub derivation_path: String,
+    pub xpub: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Wallet {
+    pub id: String,
+    pub name: String,
+    /// Chain name as reported by getblockchaininfo
+    pub network: String,
+    pub kind: WalletKind,
+    /// Hex fingerprint of the BIP32 master key
+    pub master_fingerprint: Option<String>,
+    pub accounts: Vec<WalletAccount>,
+    /// Whether an encrypted seed is stored, i.e. the wallet can be unlocked
+    pub has_seed: bool,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewWallet {
+    pub name: String,
+    pub network: String,
+    pub kind: WalletKind,
+    pub master_fingerprint: Option<String>,
+    pub accounts: Vec<WalletAccount>,
+    /// BIP39 seed encrypted under the wallet password
+    pub secret: Option<EncryptedSecret>,
+}
+
+/// A newly generated wallet with the mnemonic, which is only ever returned once
+#[derive(Debug, Serialize)]
+pub struct CreatedWallet {
+    pub wallet: Wallet,
+    pub mnemonic: Zeroizing<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WalletLockStatus {
+    pub wallet_id: String,
+    pub unlocked: bool,
+    /// When the wallet locks again, if it is unlocked
+    pub unlocked_until: Option<i64>,
+}
+
+/// Gap limit and issuance pointer of one account chain
+#[derive(Debug, Clone, Copy)]
+pub struct AddressChainState {
+    /// Unused addresses derived past the last used one
+    pub gap_limit: u32,
+    /// Number of addresses handed out, i.e. the next index to issue
+    pub issued: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WalletAddress {
+    pub address: String,
+    pub script_type: ScriptType,
+    pub chain: AddressChain,
+    pub index: u32,
+    /// e.g. m/84'/0'/0'/0/5
+    pub derivation_path: String,
+    pub script_pub_key: String,
+    /// Whether the address has appeared on chain
+    pub used: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AddressFilter {
+    pub script_type: Option<ScriptType>,
+    pub chain: Option<AddressChain>,
+    pub used: Option<bool>,
+}
+
+/// Output descriptor of one account chain
+#[derive(Debug, Serialize)]
+pub struct WalletDescriptor {
+    pub script_type: ScriptType,
+    pub chain: AddressChain,
+    pub descriptor: String,
+}
+
+/// Import of a wallet's descriptors into its watch-only node wallet
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeWalletSync {
+    pub wallet_id: String,
+    /// Name of the Bitcoin Core wallet, e.g. ohla-<wallet id>
+    pub node_wallet: String,
+    /// Node configuration the descriptors were last imported into ("env" for the fallback node)
+    pub node_id: Option<String>,
+    /// Descriptor timestamp: the rescan starts at blocks from this time (0 = genesis)
+    pub rescan_from: i64,
+    pub synced_at: Option<i64>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RescanProgress {
+    pub duration_secs: u64,
+    /// 0.0 - 1.0
+    pub progress: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NodeWalletStatus {
+    pub wallet_id: String,
+    pub node_wallet: String,
+    pub active_node_id: String,
+    /// Descriptors are imported into the active node without errors
+    pub synced: bool,
+    /// An importdescriptors call is in flight
+    pub importing: bool,
+    /// Rescan reported by getwalletinfo, if one is running
+    pub scanning: Option<RescanProgress>,
+    pub rescan_from: i64,
+    pub synced_at: Option<i64>,
+    pub last_error: Option<String>,
+}
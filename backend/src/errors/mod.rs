@@ -25,6 +25,14 @@ pub enum AppError {
     NotFound(String),
     /// Database errors
     DatabaseError(String),
+    /// Invalid request input
+    BadRequest(String),
+    /// Caller is not allowed to perform this action
+    Forbidden(String),
+    /// Feature is disabled or not ready yet
+    ServiceUnavailable(String),
+    /// Request conflicts with work already in progress
+    Conflict(String),
 }
 
 impl AppError {
@@ -39,6 +47,10 @@ impl AppError {
             AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::NotFound(_) => StatusCode::NOT_FOUND,
             AppError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            AppError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            AppError::Forbidden(_) => StatusCode::FORBIDDEN,
+            AppError::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
         }
     }
 
@@ -53,6 +65,10 @@ impl AppError {
             AppError::Internal(_) => "internal_server_error",
             AppError::NotFound(_) => "not_found",
             AppError::DatabaseError(_) => "database_error",
+            AppError::BadRequest(_) => "bad_request",
+            AppError::Forbidden(_) => "forbidden",
+            AppError::ServiceUnavailable(_) => "service_unavailable",
+            AppError::Conflict(_) => "conflict",
         }
     }
 
@@ -79,6 +95,10 @@ impl AppError {
             AppError::DatabaseError(msg) => {
                 format!("Database error: {}", msg)
             }
+            AppError::BadRequest(msg) => msg.clone(),
+            AppError::Forbidden(msg) => msg.clone(),
+            AppError::ServiceUnavailable(msg) => msg.clone(),
+            AppError::Conflict(msg) => msg.clone(),
         }
     }
 
@@ -1,5 +1,7 @@
 mod app;
+mod auth;
 mod config;
+mod crypto;
 mod db;
 mod domain;
 mod errors;
@@ -11,8 +13,10 @@ mod utils;
 
 use crate::app::create_app;
 use crate::config::env::load_env;
+use crate::services::wallet_keyring::WalletKeyring;
 use crate::state::app_state::AppState;
 use std::net::SocketAddr;
+use std::sync::Arc;
 use tokio::signal;
 
 #[tokio::main]
@@ -21,6 +25,16 @@ async fn main() {
     load_env();
 
     let app_state = AppState::initialize().await;
+
+    // Background services
+    services::chain_monitor::spawn(app_state.clone());
+    services::header_indexer::spawn(app_state.clone());
+    services::address_indexer::spawn(app_state.clone());
+    services::metrics_sampler::spawn(app_state.clone());
+    services::node_wallets::spawn(app_state.clone());
+
+    let node_process = app_state.node_process.clone();
+    let wallet_keys = app_state.wallet_keys.clone();
     let app = create_app(app_state);
 
     let addr = SocketAddr::from(([127, 0, 0, 1], 9507));
@@ -31,12 +45,42 @@ async fn main() {
 
     // Start server with graceful shutdown
     axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
+        .with_graceful_shutdown(shutdown_signal(wallet_keys))
         .await
         .expect("Server failed");
+
+    // Don't leave a managed bitcoind running behind us
+    node_process.shutdown().await;
 }
 
-async fn shutdown_signal() {
-    let _ = signal::ctrl_c().await;
+/// Wait for Ctrl-C or SIGTERM (systemd, `docker stop`). Decrypted wallet seeds
+/// are zeroized right away, since open connections such as scenario event
+/// streams can hold up the graceful shutdown that follows.
+async fn shutdown_signal(wallet_keys: Arc<WalletKeyring>) {
+    let ctrl_c = async {
+        let _ = signal::ctrl_c().await;
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match signal::unix::signal(signal::unix::SignalKind::terminate()) {
+            Ok(mut stream) => {
+                stream.recv().await;
+            }
+            Err(e) => {
+                eprintln!("⚠️  Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
     println!("🛑 Shutdown signal received");
+
+    wallet_keys.lock_all().await;
 }
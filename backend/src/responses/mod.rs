@@ -41,7 +41,6 @@ impl<T: Serialize> ApiResponse<T> {
     }
 
     /// Create a successful response with custom status code
-    #[allow(dead_code)]
     pub fn success_with_status(status: StatusCode, data: T, message: impl Into<String>) -> Self {
         Self {
             status: status.as_u16(),
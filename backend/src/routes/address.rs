@@ -0,0 +1,36 @@
+use axum::{
+    Router,
+    extract::{Path, Query, State},
+    routing::get,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::domain::address::AddressDetails;
+use crate::errors::AppError;
+use crate::responses::ApiResponse;
+use crate::services::address_indexer;
+use crate::state::app_state::AppState;
+
+#[derive(Deserialize)]
+struct PageQuery {
+    page: Option<u32>,
+    page_size: Option<u32>,
+}
+
+/// GET /address/:address - Balance, UTXOs and paginated history from the address index
+async fn get_address(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(query): Query<PageQuery>,
+) -> Result<ApiResponse<AddressDetails>, AppError> {
+    let page = query.page.unwrap_or(1).max(1);
+    let page_size = query.page_size.unwrap_or(25).clamp(1, 100);
+
+    let details = address_indexer::address_details(&state, &address, page, page_size).await?;
+    Ok(ApiResponse::success(details, "Address details retrieved successfully"))
+}
+
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new().route("/address/{address}", get(get_address))
+}
@@ -0,0 +1,198 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, post},
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::auth::WriteAccess;
+use crate::db::{
+    ChainTipRepository, NodeConfigRepository, SqliteBlockStatsRepository, SqliteChainTipRepository,
+    SqliteNodeConfigRepository, SqliteUtxoSetRepository,
+};
+use crate::domain::block_stats::ChainStats;
+use crate::domain::chain::{NodeChainTips, ReorgEvent, TipObservation};
+use crate::domain::deployment::DeploymentInfo;
+use crate::domain::epoch::EpochInfo;
+use crate::domain::utxoset::{UtxoSetHashType, UtxoSetInfo, UtxoSetJob, UtxoSetSummary};
+use crate::errors::AppError;
+use crate::responses::ApiResponse;
+use crate::services::{block_stats, deployments, epoch, utxoset};
+use crate::state::app_state::AppState;
+
+#[derive(Deserialize)]
+struct NodeFilter {
+    node_id: Option<String>,
+    limit: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct HeightRange {
+    from: u64,
+    to: u64,
+}
+
+#[derive(Deserialize)]
+struct DeploymentsQuery {
+    /// Completed signalling periods to include per BIP9 deployment
+    #[serde(default)]
+    periods: u64,
+}
+
+#[derive(Deserialize)]
+struct UtxoSetQuery {
+    #[serde(default)]
+    hash_type: UtxoSetHashType,
+}
+
+#[derive(Deserialize)]
+struct UtxoSetHeightQuery {
+    #[serde(default = "default_index_hash_type")]
+    hash_type: UtxoSetHashType,
+}
+
+fn default_index_hash_type() -> UtxoSetHashType {
+    UtxoSetHashType::Muhash
+}
+
+/// GET /chain/tips - Current chain tips for every tracked node
+async fn get_chain_tips(
+    State(state): State<Arc<AppState>>,
+    Query(filter): Query<NodeFilter>,
+) -> Result<ApiResponse<Vec<NodeChainTips>>, AppError> {
+    let node_repo = SqliteNodeConfigRepository::new(state.db_pool.clone());
+    let tip_repo = SqliteChainTipRepository::new(state.db_pool.clone());
+
+    let mut result = Vec::new();
+    for node in node_repo.list().await? {
+        if filter.node_id.as_ref().is_some_and(|id| *id != node.id) {
+            continue;
+        }
+
+        result.push(NodeChainTips {
+            best_tip: tip_repo.latest_best_tip(&node.id).await?,
+            tips: tip_repo.list_chain_tips(&node.id).await?,
+            node_id: node.id,
+            node_name: node.name,
+        });
+    }
+
+    Ok(ApiResponse::success(result, "Chain tips retrieved successfully"))
+}
+
+/// GET /chain/tips/history?node_id= - Recent best-block history for a node
+async fn get_tip_history(
+    State(state): State<Arc<AppState>>,
+    Query(filter): Query<NodeFilter>,
+) -> Result<ApiResponse<Vec<TipObservation>>, AppError> {
+    let node_id = filter
+        .node_id
+        .ok_or_else(|| AppError::BadRequest("node_id is required".to_string()))?;
+
+    let repo = SqliteChainTipRepository::new(state.db_pool.clone());
+    let history = repo
+        .list_tip_history(&node_id, filter.limit.unwrap_or(100).clamp(1, 1000))
+        .await?;
+    Ok(ApiResponse::success(history, "Tip history retrieved successfully"))
+}
+
+/// GET /chain/reorgs - Detected reorgs, newest first
+async fn get_reorgs(
+    State(state): State<Arc<AppState>>,
+    Query(filter): Query<NodeFilter>,
+) -> Result<ApiResponse<Vec<ReorgEvent>>, AppError> {
+    let repo = SqliteChainTipRepository::new(state.db_pool.clone());
+    let reorgs = repo
+        .list_reorgs(filter.node_id.as_deref(), filter.limit.unwrap_or(50).clamp(1, 1000))
+        .await?;
+    Ok(ApiResponse::success(reorgs, "Reorg history retrieved successfully"))
+}
+
+/// GET /chain/stats?from=&to= - Aggregated getblockstats over a height range
+async fn get_chain_stats(
+    State(state): State<Arc<AppState>>,
+    Query(range): Query<HeightRange>,
+) -> Result<ApiResponse<ChainStats>, AppError> {
+    let rpc = state.active_rpc().await?;
+    let repo = SqliteBlockStatsRepository::new(state.db_pool.clone());
+
+    let stats = block_stats::range_stats(&rpc, &repo, range.from, range.to).await?;
+    Ok(ApiResponse::success(stats, "Block statistics retrieved successfully"))
+}
+
+/// GET /chain/epoch - Difficulty epoch progress with retarget and halving projections
+async fn get_epoch(
+    State(state): State<Arc<AppState>>,
+) -> Result<ApiResponse<EpochInfo>, AppError> {
+    let rpc = state.active_rpc().await?;
+    let info = epoch::epoch_info(&rpc).await?;
+    Ok(ApiResponse::success(info, "Epoch information retrieved successfully"))
+}
+
+/// GET /chain/deployments - Soft fork deployment state and BIP9 signalling
+async fn get_deployments(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<DeploymentsQuery>,
+) -> Result<ApiResponse<DeploymentInfo>, AppError> {
+    let rpc = state.active_rpc().await?;
+    let info = deployments::deployment_info(&rpc, query.periods).await?;
+    Ok(ApiResponse::success(info, "Deployment information retrieved successfully"))
+}
+
+/// GET /chain/utxoset?hash_type= - Last cached UTXO set summary on the active chain
+async fn get_utxoset(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<UtxoSetQuery>,
+) -> Result<ApiResponse<UtxoSetSummary>, AppError> {
+    let rpc = state.active_rpc().await?;
+    let summary = state
+        .utxoset_jobs
+        .summary(rpc, state.db_pool.clone(), query.hash_type)
+        .await?;
+    Ok(ApiResponse::success(summary, "UTXO set summary retrieved successfully"))
+}
+
+/// POST /chain/utxoset/refresh - Start computing the UTXO set summary at the tip
+async fn refresh_utxoset(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Json(query): Json<UtxoSetQuery>,
+) -> Result<ApiResponse<UtxoSetJob>, AppError> {
+    let rpc = state.active_rpc().await?;
+    let job = state
+        .utxoset_jobs
+        .start(rpc, state.db_pool.clone(), query.hash_type)
+        .await?;
+    Ok(ApiResponse::success_with_status(
+        StatusCode::ACCEPTED,
+        job,
+        "UTXO set computation started",
+    ))
+}
+
+/// GET /chain/utxoset/{height}?hash_type= - UTXO set summary at a height from coinstatsindex
+async fn get_utxoset_at_height(
+    State(state): State<Arc<AppState>>,
+    Path(height): Path<u64>,
+    Query(query): Query<UtxoSetHeightQuery>,
+) -> Result<ApiResponse<UtxoSetInfo>, AppError> {
+    let rpc = state.active_rpc().await?;
+    let repo = SqliteUtxoSetRepository::new(state.db_pool.clone());
+    let info = utxoset::at_height(&rpc, &repo, query.hash_type, height).await?;
+    Ok(ApiResponse::success(info, "UTXO set summary retrieved successfully"))
+}
+
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/chain/tips", get(get_chain_tips))
+        .route("/chain/tips/history", get(get_tip_history))
+        .route("/chain/reorgs", get(get_reorgs))
+        .route("/chain/stats", get(get_chain_stats))
+        .route("/chain/epoch", get(get_epoch))
+        .route("/chain/deployments", get(get_deployments))
+        .route("/chain/utxoset", get(get_utxoset))
+        .route("/chain/utxoset/refresh", post(refresh_utxoset))
+        .route("/chain/utxoset/{height}", get(get_utxoset_at_height))
+}
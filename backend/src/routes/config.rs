@@ -6,9 +6,12 @@ use axum::{
 };
 use std::sync::Arc;
 
+use crate::auth::WriteAccess;
 use crate::db::{NodeConfigRepository, NodeConfig, NewNodeConfig, SqliteNodeConfigRepository};
+use crate::domain::bitcoin_conf::BitcoinConfImport;
 use crate::errors::AppError;
 use crate::responses::ApiResponse;
+use crate::services::bitcoin_conf;
 use crate::state::app_state::AppState;
 
 /// POST /config/nodes - Create a new node configuration
@@ -114,12 +117,54 @@ async fn test_node_connection(
     Ok(ApiResponse::success(response, "Connection test completed"))
 }
 
+#[derive(serde::Deserialize)]
+struct ImportBitcoinConfPayload {
+    /// Text of a bitcoin.conf
+    content: Option<String>,
+    /// Path of a bitcoin.conf on the backend host; its directory is used as the datadir
+    path: Option<String>,
+    /// Store the proposals instead of only returning them
+    #[serde(default)]
+    create: bool,
+}
+
+/// POST /config/nodes/import-bitcoin-conf - Propose (or create) node configurations from a bitcoin.conf
+async fn import_bitcoin_conf(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<ImportBitcoinConfPayload>,
+) -> Result<ApiResponse<BitcoinConfImport>, AppError> {
+    let mut import = match (payload.content, payload.path) {
+        (Some(content), None) => bitcoin_conf::import(&content, None).await,
+        (None, Some(path)) => {
+            let path = std::path::PathBuf::from(path);
+            let content = tokio::fs::read_to_string(&path).await.map_err(|e| {
+                AppError::BadRequest(format!("Cannot read {}: {}", path.display(), e))
+            })?;
+            bitcoin_conf::import(&content, path.parent()).await
+        }
+        _ => {
+            return Err(AppError::BadRequest(
+                "Provide either content or path".to_string(),
+            ));
+        }
+    };
+
+    if payload.create {
+        let repo = SqliteNodeConfigRepository::new(state.db_pool.clone());
+        bitcoin_conf::persist(&repo, &mut import).await?;
+    }
+
+    Ok(ApiResponse::success(import, "bitcoin.conf imported successfully"))
+}
+
 pub fn routes() -> Router<Arc<AppState>> {
     Router::new()
         .route("/config/nodes", post(create_node_config))
         .route("/config/nodes", get(list_node_configs))
         .route("/config/nodes/active", get(get_active_node_config))
         .route("/config/nodes/test", post(test_node_connection))
+        .route("/config/nodes/import-bitcoin-conf", post(import_bitcoin_conf))
         .route("/config/nodes/{id}", get(get_node_config))
         .route("/config/nodes/{id}/activate", put(set_active_node_config))
         .route("/config/nodes/{id}", delete(delete_node_config))
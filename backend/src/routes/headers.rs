@@ -0,0 +1,101 @@
+use axum::{
+    Router,
+    extract::{Path, Query, State},
+    routing::get,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::db::{HeaderRepository, SqliteHeaderRepository};
+use crate::domain::header::{BlockHeader, HeaderIndexStatus};
+use crate::errors::AppError;
+use crate::responses::ApiResponse;
+use crate::services::header_indexer;
+use crate::state::app_state::AppState;
+
+/// Maximum number of headers returned by a range query (one difficulty epoch)
+const MAX_RANGE: u64 = 2016;
+
+#[derive(Deserialize)]
+struct RangeQuery {
+    from: u64,
+    to: u64,
+}
+
+#[derive(Deserialize)]
+struct TimeQuery {
+    timestamp: i64,
+}
+
+/// GET /headers/status - Header index progress versus the active node
+async fn get_status(
+    State(state): State<Arc<AppState>>,
+) -> Result<ApiResponse<HeaderIndexStatus>, AppError> {
+    let status = header_indexer::status(&state).await?;
+    Ok(ApiResponse::success(status, "Header index status retrieved successfully"))
+}
+
+/// GET /headers?from=&to= - Indexed headers in a height range
+async fn get_range(
+    State(state): State<Arc<AppState>>,
+    Query(range): Query<RangeQuery>,
+) -> Result<ApiResponse<Vec<BlockHeader>>, AppError> {
+    if range.to < range.from || range.to - range.from >= MAX_RANGE {
+        return Err(AppError::BadRequest(format!(
+            "Range must satisfy from <= to and span at most {} blocks",
+            MAX_RANGE
+        )));
+    }
+
+    let repo = SqliteHeaderRepository::new(state.db_pool.clone());
+    let headers = repo.list_range(range.from, range.to).await?;
+    Ok(ApiResponse::success(headers, "Block headers retrieved successfully"))
+}
+
+/// GET /headers/height/:height - Indexed header at a height
+async fn get_by_height(
+    State(state): State<Arc<AppState>>,
+    Path(height): Path<u64>,
+) -> Result<ApiResponse<BlockHeader>, AppError> {
+    let repo = SqliteHeaderRepository::new(state.db_pool.clone());
+    let header = repo
+        .get_by_height(height)
+        .await?
+        .ok_or(AppError::NotFound(format!("No indexed header at height {}", height)))?;
+    Ok(ApiResponse::success(header, "Block header retrieved successfully"))
+}
+
+/// GET /headers/hash/:hash - Indexed header by block hash
+async fn get_by_hash(
+    State(state): State<Arc<AppState>>,
+    Path(hash): Path<String>,
+) -> Result<ApiResponse<BlockHeader>, AppError> {
+    let repo = SqliteHeaderRepository::new(state.db_pool.clone());
+    let header = repo
+        .get_by_hash(&hash)
+        .await?
+        .ok_or(AppError::NotFound(format!("No indexed header with hash {}", hash)))?;
+    Ok(ApiResponse::success(header, "Block header retrieved successfully"))
+}
+
+/// GET /headers/at-time?timestamp= - Last indexed block mined at or before a unix timestamp
+async fn get_at_time(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<TimeQuery>,
+) -> Result<ApiResponse<BlockHeader>, AppError> {
+    let repo = SqliteHeaderRepository::new(state.db_pool.clone());
+    let header = repo.get_at_time(query.timestamp).await?.ok_or(AppError::NotFound(format!(
+        "No indexed block at or before timestamp {}",
+        query.timestamp
+    )))?;
+    Ok(ApiResponse::success(header, "Block header retrieved successfully"))
+}
+
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/headers", get(get_range))
+        .route("/headers/status", get(get_status))
+        .route("/headers/height/{height}", get(get_by_height))
+        .route("/headers/hash/{hash}", get(get_by_hash))
+        .route("/headers/at-time", get(get_at_time))
+}
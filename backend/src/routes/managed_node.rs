@@ -0,0 +1,83 @@
+use axum::{
+    Json, Router,
+    extract::{Query, State},
+    routing::{get, post},
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::auth::WriteAccess;
+use crate::domain::managed_node::{LogSource, ManagedNetwork, ManagedNodeLogs, ManagedNodeStatus};
+use crate::errors::AppError;
+use crate::responses::ApiResponse;
+use crate::state::app_state::AppState;
+
+/// Upper bound on log lines per request
+const MAX_LOG_LINES: usize = 2000;
+
+#[derive(Deserialize)]
+struct StartPayload {
+    network: ManagedNetwork,
+    /// Make the managed node the active node configuration
+    #[serde(default)]
+    activate: bool,
+}
+
+#[derive(Deserialize)]
+struct LogsQuery {
+    #[serde(default)]
+    source: LogSource,
+    network: Option<ManagedNetwork>,
+    lines: Option<usize>,
+}
+
+/// GET /managed-node - Get the managed bitcoind status
+async fn get_status(
+    State(state): State<Arc<AppState>>,
+) -> Result<ApiResponse<ManagedNodeStatus>, AppError> {
+    let status = state.node_process.status().await;
+    Ok(ApiResponse::success(status, "Managed node status retrieved successfully"))
+}
+
+/// POST /managed-node/start - Spawn bitcoind and wait until RPC is ready
+async fn start_node(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<StartPayload>,
+) -> Result<ApiResponse<ManagedNodeStatus>, AppError> {
+    let status = state
+        .node_process
+        .start(state.db_pool.clone(), payload.network, payload.activate)
+        .await?;
+    Ok(ApiResponse::success(status, "Managed node started successfully"))
+}
+
+/// POST /managed-node/stop - Stop the managed bitcoind
+async fn stop_node(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+) -> Result<ApiResponse<ManagedNodeStatus>, AppError> {
+    let status = state.node_process.stop().await?;
+    Ok(ApiResponse::success(status, "Managed node stopped successfully"))
+}
+
+/// GET /managed-node/logs - Tail the captured stdout or debug.log
+async fn get_logs(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<LogsQuery>,
+) -> Result<ApiResponse<ManagedNodeLogs>, AppError> {
+    let lines = query.lines.unwrap_or(200).clamp(1, MAX_LOG_LINES);
+    let logs = state
+        .node_process
+        .logs(query.source, query.network, lines)
+        .await?;
+    Ok(ApiResponse::success(logs, "Managed node logs retrieved successfully"))
+}
+
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/managed-node", get(get_status))
+        .route("/managed-node/start", post(start_node))
+        .route("/managed-node/stop", post(stop_node))
+        .route("/managed-node/logs", get(get_logs))
+}
@@ -0,0 +1,108 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    routing::get,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::db::{MetricsRepository, NodeConfigRepository, SqliteMetricsRepository, SqliteNodeConfigRepository};
+use crate::domain::metrics::{Metric, MetricSeries};
+use crate::errors::AppError;
+use crate::responses::ApiResponse;
+use crate::state::app_state::AppState;
+
+/// Upper bound on points per series; coarser buckets are used past it
+const MAX_POINTS: i64 = 1000;
+/// Points aimed for when the resolution is chosen automatically
+const AUTO_POINTS: i64 = 300;
+
+#[derive(Deserialize)]
+struct HistoryQuery {
+    metric: Metric,
+    /// Defaults to the active node
+    node_id: Option<String>,
+    /// Unix seconds; defaults to 24 hours before `to`
+    from: Option<i64>,
+    /// Unix seconds; defaults to now
+    to: Option<i64>,
+    /// Bucket size such as `60`, `5m`, `1h`, `1d` or `auto`
+    resolution: Option<String>,
+}
+
+/// GET /metrics/history - Downsampled series of one sampled metric
+async fn get_history(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<HistoryQuery>,
+) -> Result<ApiResponse<MetricSeries>, AppError> {
+    let node_id = match query.node_id {
+        Some(node_id) => node_id,
+        None => SqliteNodeConfigRepository::new(state.db_pool.clone())
+            .get_active()
+            .await?
+            .map(|config| config.id)
+            .ok_or_else(|| AppError::BadRequest("node_id is required when no node is active".to_string()))?,
+    };
+
+    let out_of_range = || AppError::BadRequest("from and to are out of range".to_string());
+    let to = query.to.unwrap_or_else(|| Utc::now().timestamp());
+    let from = match query.from {
+        Some(from) => from,
+        None => to.checked_sub(86_400).ok_or_else(out_of_range)?,
+    };
+    if from >= to {
+        return Err(AppError::BadRequest("from must be before to".to_string()));
+    }
+
+    let span = to.checked_sub(from).ok_or_else(out_of_range)?;
+    let resolution_secs = match query.resolution.as_deref() {
+        None | Some("auto") => (span / AUTO_POINTS).max(1),
+        Some(value) => parse_resolution(value)?,
+    }
+    .max(span / MAX_POINTS + i64::from(span % MAX_POINTS != 0));
+
+    let repo = SqliteMetricsRepository::new(state.db_pool.clone());
+    let points = repo
+        .series(&node_id, query.metric, from, to, resolution_secs)
+        .await?;
+
+    Ok(ApiResponse::success(
+        MetricSeries {
+            node_id,
+            metric: query.metric,
+            from,
+            to,
+            resolution_secs,
+            points,
+        },
+        "Metric history retrieved successfully",
+    ))
+}
+
+/// Parse `90`, `30s`, `5m`, `1h` or `1d` into seconds
+fn parse_resolution(value: &str) -> Result<i64, AppError> {
+    let invalid = || AppError::BadRequest(format!("Invalid resolution '{}'", value));
+
+    let (number, unit) = match value.char_indices().last() {
+        Some((index, c)) if c.is_ascii_alphabetic() => (&value[..index], c),
+        _ => (value, 's'),
+    };
+    let multiplier = match unit {
+        's' => 1,
+        'm' => 60,
+        'h' => 3_600,
+        'd' => 86_400,
+        _ => return Err(invalid()),
+    };
+    let number: i64 = number.parse().map_err(|_| invalid())?;
+    if number <= 0 {
+        return Err(invalid());
+    }
+
+    number.checked_mul(multiplier).ok_or_else(invalid)
+}
+
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new().route("/metrics/history", get(get_history))
+}
@@ -1,3 +1,15 @@
+pub mod address;
+pub mod chain;
 pub mod config;
+pub mod headers;
 pub mod health;
+pub mod managed_node;
+pub mod metrics;
+pub mod network;
 pub mod node;
+pub mod regtest;
+pub mod scenario;
+pub mod search;
+pub mod send;
+pub mod utxo;
+pub mod wallet;
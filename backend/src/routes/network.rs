@@ -0,0 +1,158 @@
+use axum::{
+    Json, Router,
+    extract::State,
+    routing::{delete, get, post, put},
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::auth::WriteAccess;
+use crate::domain::network::{BannedPeer, NetworkActive, NetworkInfo, PeerInfo};
+use crate::errors::AppError;
+use crate::responses::ApiResponse;
+use crate::state::app_state::AppState;
+
+#[derive(Deserialize)]
+struct AddNodePayload {
+    address: String,
+    /// add, remove or onetry (defaults to add)
+    command: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct DisconnectNodePayload {
+    address: Option<String>,
+    peer_id: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct SetBanPayload {
+    subnet: String,
+    /// add or remove (defaults to add)
+    command: Option<String>,
+    /// Ban duration in seconds, or a unix timestamp when `absolute` is set
+    bantime: Option<i64>,
+    #[serde(default)]
+    absolute: bool,
+}
+
+#[derive(Deserialize)]
+struct SetNetworkActivePayload {
+    active: bool,
+}
+
+/// GET /network/peers - List connected peers
+async fn get_peers(
+    State(state): State<Arc<AppState>>,
+) -> Result<ApiResponse<Vec<PeerInfo>>, AppError> {
+    let peers = state.bitcoin.get_peers().await?;
+    Ok(ApiResponse::success(peers, "Peer information retrieved successfully"))
+}
+
+/// GET /network/info - Get P2P network state
+async fn get_network_info(
+    State(state): State<Arc<AppState>>,
+) -> Result<ApiResponse<NetworkInfo>, AppError> {
+    let info = state.bitcoin.get_network_summary().await?;
+    Ok(ApiResponse::success(info, "Network information retrieved successfully"))
+}
+
+/// POST /network/peers/add - Add, remove or try a manual peer connection
+async fn add_node(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<AddNodePayload>,
+) -> Result<ApiResponse<()>, AppError> {
+    let command = payload.command.as_deref().unwrap_or("add");
+    if !matches!(command, "add" | "remove" | "onetry") {
+        return Err(AppError::BadRequest(format!(
+            "Invalid addnode command '{}', expected add, remove or onetry",
+            command
+        )));
+    }
+
+    state.bitcoin.add_node(&payload.address, command).await?;
+    Ok(ApiResponse::success((), "Addnode command executed successfully"))
+}
+
+/// POST /network/peers/disconnect - Disconnect a peer by address or peer id
+async fn disconnect_node(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<DisconnectNodePayload>,
+) -> Result<ApiResponse<()>, AppError> {
+    match (payload.address, payload.peer_id) {
+        (Some(address), None) => state.bitcoin.disconnect_node_by_address(&address).await?,
+        (None, Some(peer_id)) => state.bitcoin.disconnect_node_by_id(peer_id).await?,
+        _ => {
+            return Err(AppError::BadRequest(
+                "Provide exactly one of address or peer_id".to_string(),
+            ));
+        }
+    }
+
+    Ok(ApiResponse::success((), "Peer disconnected successfully"))
+}
+
+/// GET /network/bans - List banned subnets
+async fn list_banned(
+    State(state): State<Arc<AppState>>,
+) -> Result<ApiResponse<Vec<BannedPeer>>, AppError> {
+    let banned = state.bitcoin.get_banned_peers().await?;
+    Ok(ApiResponse::success(banned, "Banned peers retrieved successfully"))
+}
+
+/// POST /network/bans - Add or remove a subnet ban
+async fn set_ban(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<SetBanPayload>,
+) -> Result<ApiResponse<()>, AppError> {
+    let command = payload.command.as_deref().unwrap_or("add");
+    if !matches!(command, "add" | "remove") {
+        return Err(AppError::BadRequest(format!(
+            "Invalid setban command '{}', expected add or remove",
+            command
+        )));
+    }
+
+    state
+        .bitcoin
+        .set_ban(&payload.subnet, command, payload.bantime, payload.absolute)
+        .await?;
+    Ok(ApiResponse::success((), "Ban list updated successfully"))
+}
+
+/// DELETE /network/bans - Clear all bans
+async fn clear_banned(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+) -> Result<ApiResponse<()>, AppError> {
+    state.bitcoin.clear_banned().await?;
+    Ok(ApiResponse::success((), "Ban list cleared successfully"))
+}
+
+/// PUT /network/active - Enable or disable all P2P network activity
+async fn set_network_active(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<SetNetworkActivePayload>,
+) -> Result<ApiResponse<NetworkActive>, AppError> {
+    let network_active = state.bitcoin.set_network_active(payload.active).await?;
+    Ok(ApiResponse::success(
+        NetworkActive { network_active },
+        "Network activity updated successfully",
+    ))
+}
+
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/network/peers", get(get_peers))
+        .route("/network/info", get(get_network_info))
+        .route("/network/peers/add", post(add_node))
+        .route("/network/peers/disconnect", post(disconnect_node))
+        .route("/network/bans", get(list_banned))
+        .route("/network/bans", post(set_ban))
+        .route("/network/bans", delete(clear_banned))
+        .route("/network/active", put(set_network_active))
+}
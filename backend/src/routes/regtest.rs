@@ -0,0 +1,188 @@
+use axum::{Json, Router, extract::State, routing::post};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::auth::WriteAccess;
+use crate::domain::regtest::{FaucetPayment, GeneratedBlock, MinedBlocks, SimulatedReorg};
+use crate::errors::AppError;
+use crate::responses::ApiResponse;
+use crate::services::regtest;
+use crate::state::app_state::AppState;
+
+/// Upper bound on blocks mined per request
+const MAX_BLOCKS_PER_REQUEST: u64 = 1000;
+
+#[derive(Deserialize)]
+struct MinePayload {
+    blocks: u64,
+    /// Coinbase destination; defaults to a new address from the node wallet
+    address: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GenerateBlockPayload {
+    /// Coinbase destination address or descriptor
+    output: String,
+    /// Txids from the mempool or raw transaction hex, in block order
+    #[serde(default)]
+    transactions: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct BlockHashPayload {
+    block_hash: String,
+}
+
+#[derive(Deserialize)]
+struct ReorgPayload {
+    depth: u64,
+    address: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct MockTimePayload {
+    /// Unix timestamp; 0 returns to system time
+    timestamp: i64,
+}
+
+#[derive(Deserialize)]
+struct FaucetPayload {
+    address: String,
+    amount_sat: u64,
+}
+
+/// POST /regtest/mine - Mine blocks with generatetoaddress
+async fn mine(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<MinePayload>,
+) -> Result<ApiResponse<MinedBlocks>, AppError> {
+    if payload.blocks == 0 || payload.blocks > MAX_BLOCKS_PER_REQUEST {
+        return Err(AppError::BadRequest(format!(
+            "blocks must be between 1 and {}",
+            MAX_BLOCKS_PER_REQUEST
+        )));
+    }
+
+    let rpc = state.active_rpc().await?;
+    regtest::ensure_regtest(&rpc).await?;
+
+    let block_hashes = regtest::mine_blocks(&rpc, payload.blocks, payload.address).await?;
+    Ok(ApiResponse::success(
+        MinedBlocks { block_hashes },
+        "Blocks mined successfully",
+    ))
+}
+
+/// POST /regtest/mine/block - Mine one block with chosen transactions via generateblock
+async fn generate_block(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<GenerateBlockPayload>,
+) -> Result<ApiResponse<GeneratedBlock>, AppError> {
+    let rpc = state.active_rpc().await?;
+    regtest::ensure_regtest(&rpc).await?;
+
+    let block = rpc.generate_block(&payload.output, &payload.transactions).await?;
+    Ok(ApiResponse::success(
+        GeneratedBlock { hash: block.hash },
+        "Block generated successfully",
+    ))
+}
+
+/// POST /regtest/invalidate - Mark a block and its descendants invalid
+async fn invalidate_block(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<BlockHashPayload>,
+) -> Result<ApiResponse<()>, AppError> {
+    let rpc = state.active_rpc().await?;
+    regtest::ensure_regtest(&rpc).await?;
+
+    rpc.invalidate_block(&payload.block_hash).await?;
+    Ok(ApiResponse::success((), "Block invalidated successfully"))
+}
+
+/// POST /regtest/reconsider - Remove the invalid mark from a block
+async fn reconsider_block(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<BlockHashPayload>,
+) -> Result<ApiResponse<()>, AppError> {
+    let rpc = state.active_rpc().await?;
+    regtest::ensure_regtest(&rpc).await?;
+
+    rpc.reconsider_block(&payload.block_hash).await?;
+    Ok(ApiResponse::success((), "Block reconsidered successfully"))
+}
+
+/// POST /regtest/reorg - Replace the last `depth` blocks with a longer branch
+async fn simulate_reorg(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<ReorgPayload>,
+) -> Result<ApiResponse<SimulatedReorg>, AppError> {
+    if payload.depth >= MAX_BLOCKS_PER_REQUEST {
+        return Err(AppError::BadRequest(format!(
+            "depth must be below {}",
+            MAX_BLOCKS_PER_REQUEST
+        )));
+    }
+
+    let rpc = state.active_rpc().await?;
+    regtest::ensure_regtest(&rpc).await?;
+
+    let reorg = regtest::simulate_reorg(&rpc, payload.depth, payload.address).await?;
+    Ok(ApiResponse::success(reorg, "Reorg simulated successfully"))
+}
+
+/// POST /regtest/mocktime - Set the node clock
+async fn set_mock_time(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<MockTimePayload>,
+) -> Result<ApiResponse<()>, AppError> {
+    let rpc = state.active_rpc().await?;
+    regtest::ensure_regtest(&rpc).await?;
+
+    rpc.set_mock_time(payload.timestamp).await?;
+    Ok(ApiResponse::success((), "Mock time updated successfully"))
+}
+
+/// POST /regtest/faucet - Send funds from the miner wallet to any address
+async fn faucet(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<FaucetPayload>,
+) -> Result<ApiResponse<FaucetPayment>, AppError> {
+    if payload.amount_sat == 0 {
+        return Err(AppError::BadRequest("amount_sat must be positive".to_string()));
+    }
+
+    let rpc = state.active_rpc().await?;
+    regtest::ensure_regtest(&rpc).await?;
+
+    let txid = regtest::miner_wallet(&rpc)
+        .await?
+        .send_to_address(&payload.address, payload.amount_sat, None)
+        .await?;
+    Ok(ApiResponse::success(
+        FaucetPayment {
+            txid,
+            address: payload.address,
+            amount_sat: payload.amount_sat,
+        },
+        "Faucet payment sent successfully",
+    ))
+}
+
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/regtest/mine", post(mine))
+        .route("/regtest/mine/block", post(generate_block))
+        .route("/regtest/invalidate", post(invalidate_block))
+        .route("/regtest/reconsider", post(reconsider_block))
+        .route("/regtest/reorg", post(simulate_reorg))
+        .route("/regtest/mocktime", post(set_mock_time))
+        .route("/regtest/faucet", post(faucet))
+}
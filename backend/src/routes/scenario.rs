@@ -0,0 +1,72 @@
+use axum::{
+    Json, Router,
+    extract::State,
+    response::sse::{Event, KeepAlive, Sse},
+    routing::post,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio_stream::{Stream, StreamExt, wrappers::ReceiverStream};
+
+use crate::auth::WriteAccess;
+use crate::domain::scenario::ScenarioValidation;
+use crate::errors::AppError;
+use crate::responses::ApiResponse;
+use crate::services::regtest;
+use crate::services::scenario::{self, ScenarioEvent};
+use crate::state::app_state::AppState;
+
+#[derive(Deserialize)]
+struct ScenarioPayload {
+    /// Scenario document in YAML or JSON
+    content: String,
+}
+
+/// POST /scenarios/validate - Parse a scenario without running it
+async fn validate_scenario(
+    _: WriteAccess,
+    Json(payload): Json<ScenarioPayload>,
+) -> Result<ApiResponse<ScenarioValidation>, AppError> {
+    let scenario = scenario::parse_scenario(&payload.content)?;
+
+    Ok(ApiResponse::success(
+        ScenarioValidation {
+            name: scenario.name,
+            description: scenario.description,
+            steps: scenario.steps.iter().map(|s| s.action()).collect(),
+        },
+        "Scenario is valid",
+    ))
+}
+
+/// POST /scenarios/run - Run a scenario against the active regtest node,
+/// streaming a `step` event per step and a final `complete` event
+async fn run_scenario(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<ScenarioPayload>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, AppError> {
+    let scenario = scenario::parse_scenario(&payload.content)?;
+
+    let rpc = state.active_rpc().await?;
+    regtest::ensure_regtest(&rpc).await?;
+
+    println!("🎬 Running scenario '{}' ({} steps)", scenario.name, scenario.steps.len());
+
+    let (tx, rx) = mpsc::channel(16);
+    tokio::spawn(scenario::run(rpc, scenario, tx));
+
+    let stream = ReceiverStream::new(rx).map(|event| match event {
+        ScenarioEvent::Step(result) => Event::default().event("step").json_data(result),
+        ScenarioEvent::Finished(summary) => Event::default().event("complete").json_data(summary),
+    });
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/scenarios/validate", post(validate_scenario))
+        .route("/scenarios/run", post(run_scenario))
+}
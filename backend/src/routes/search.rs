@@ -0,0 +1,31 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    routing::get,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::domain::search::SearchResponse;
+use crate::errors::AppError;
+use crate::responses::ApiResponse;
+use crate::services::search;
+use crate::state::app_state::AppState;
+
+#[derive(Deserialize)]
+struct SearchQuery {
+    q: String,
+}
+
+/// GET /search?q= - Resolve a height, hash, txid, address, xpub, descriptor or node name
+async fn search_query(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<SearchQuery>,
+) -> Result<ApiResponse<SearchResponse>, AppError> {
+    let response = search::search(&state, &query.q).await?;
+    Ok(ApiResponse::success(response, "Search completed successfully"))
+}
+
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new().route("/search", get(search_query))
+}
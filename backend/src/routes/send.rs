@@ -0,0 +1,50 @@
+use axum::{Json, Router, extract::State, routing::post};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::auth::WriteAccess;
+use crate::domain::send::{BroadcastResult, FinalizedTransaction};
+use crate::errors::AppError;
+use crate::responses::ApiResponse;
+use crate::services::psbt;
+use crate::state::app_state::AppState;
+
+#[derive(Deserialize)]
+struct FinalizePayload {
+    send_id: Option<String>,
+    /// base64; any fully signed PSBT
+    psbt: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct BroadcastPayload {
+    send_id: Option<String>,
+    /// Any final transaction
+    tx_hex: Option<String>,
+}
+
+/// POST /psbt/finalize - Finalize a signed PSBT and extract the transaction
+async fn finalize_psbt(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<FinalizePayload>,
+) -> Result<ApiResponse<FinalizedTransaction>, AppError> {
+    let finalized = psbt::finalize(&state, payload.send_id.as_deref(), payload.psbt.as_deref()).await?;
+    Ok(ApiResponse::success(finalized, "PSBT finalized successfully"))
+}
+
+/// POST /tx/broadcast - Check a transaction with testmempoolaccept, then broadcast it
+async fn broadcast_transaction(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<BroadcastPayload>,
+) -> Result<ApiResponse<BroadcastResult>, AppError> {
+    let result = psbt::broadcast(&state, payload.send_id.as_deref(), payload.tx_hex.as_deref()).await?;
+    Ok(ApiResponse::success(result, "Transaction broadcast successfully"))
+}
+
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/psbt/finalize", post(finalize_psbt))
+        .route("/tx/broadcast", post(broadcast_transaction))
+}
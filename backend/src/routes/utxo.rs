@@ -0,0 +1,79 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{delete, get, post},
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::auth::WriteAccess;
+use crate::domain::scan::{ScanDescriptor, ScanJob};
+use crate::errors::AppError;
+use crate::responses::ApiResponse;
+use crate::state::app_state::AppState;
+
+#[derive(Deserialize)]
+struct ScanPayload {
+    #[serde(default)]
+    addresses: Vec<String>,
+    #[serde(default)]
+    descriptors: Vec<ScanDescriptor>,
+}
+
+/// POST /utxo/scan - Start a scantxoutset job for addresses and/or descriptors
+async fn start_scan(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<ScanPayload>,
+) -> Result<ApiResponse<ScanJob>, AppError> {
+    let descriptors = payload
+        .addresses
+        .into_iter()
+        .map(|address| ScanDescriptor {
+            desc: format!("addr({})", address),
+            range: None,
+        })
+        .chain(payload.descriptors)
+        .collect();
+
+    let rpc = state.active_rpc().await?;
+    let job = state.scan_jobs.start(rpc, descriptors).await?;
+    Ok(ApiResponse::success_with_status(
+        StatusCode::ACCEPTED,
+        job,
+        "UTXO set scan started",
+    ))
+}
+
+/// GET /utxo/scan - List tracked scan jobs
+async fn list_scans(State(state): State<Arc<AppState>>) -> ApiResponse<Vec<ScanJob>> {
+    ApiResponse::success(state.scan_jobs.list().await, "Scan jobs retrieved successfully")
+}
+
+/// GET /utxo/scan/:id - Poll a scan job's progress and result
+async fn get_scan(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<ApiResponse<ScanJob>, AppError> {
+    let job = state.scan_jobs.get(&id).await?;
+    Ok(ApiResponse::success(job, "Scan job retrieved successfully"))
+}
+
+/// DELETE /utxo/scan/:id - Abort a running scan job
+async fn abort_scan(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<ApiResponse<ScanJob>, AppError> {
+    let job = state.scan_jobs.abort(&id).await?;
+    Ok(ApiResponse::success(job, "Scan job aborted"))
+}
+
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/utxo/scan", post(start_scan))
+        .route("/utxo/scan", get(list_scans))
+        .route("/utxo/scan/{id}", get(get_scan))
+        .route("/utxo/scan/{id}", delete(abort_scan))
+}
@@ -0,0 +1,656 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{delete, get, post, put},
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use zeroize::Zeroizing;
+
+use crate::auth::WriteAccess;
+use crate::db::{SqliteWalletRepository, WalletRepository};
+use crate::domain::coin_selection::{CoinSelectionPreview, SelectionAlgorithm};
+use crate::domain::send::{BumpMode, FeeBump, PendingSend};
+use crate::domain::transaction::{TransactionFilter, TransactionPage, TxDirection};
+use crate::domain::utxo::{UtxoMeta, WalletBalance, WalletUtxo};
+use crate::domain::wallet::{
+    AddressChain, AddressFilter, CreatedWallet, NodeWalletStatus, ScriptType, Wallet, WalletAddress,
+    WalletDescriptor, WalletLockStatus,
+};
+use crate::errors::AppError;
+use crate::responses::ApiResponse;
+use crate::services::wallet::{self, WatchOnlySource};
+use crate::services::spend::{self, Recipient, SpendOptions};
+use crate::services::{balance, fee_bump, node_wallets, psbt, transaction_history, wallet_addresses};
+use crate::state::app_state::AppState;
+
+#[derive(Deserialize)]
+struct CreatePayload {
+    name: String,
+    network: String,
+    #[serde(default = "default_word_count")]
+    word_count: usize,
+    /// Optional BIP39 passphrase ("25th word")
+    #[serde(default)]
+    passphrase: Zeroizing<String>,
+    /// Encrypts the stored seed
+    password: Zeroizing<String>,
+}
+
+fn default_word_count() -> usize {
+    12
+}
+
+#[derive(Deserialize)]
+struct ImportPayload {
+    name: String,
+    network: String,
+    mnemonic: Zeroizing<String>,
+    #[serde(default)]
+    passphrase: Zeroizing<String>,
+    password: Zeroizing<String>,
+    /// Unix time before the wallet's first transaction; limits the node rescan
+    #[serde(default)]
+    birthday: i64,
+}
+
+#[derive(Deserialize)]
+struct WatchOnlyPayload {
+    name: String,
+    network: String,
+    /// Account-level xpub/ypub/zpub/tpub/upub/vpub
+    xpub: Option<String>,
+    script_type: Option<ScriptType>,
+    master_fingerprint: Option<String>,
+    derivation_path: Option<String>,
+    /// Single-key output descriptors, as an alternative to `xpub`
+    #[serde(default)]
+    descriptors: Vec<String>,
+    /// Unix time before the wallet's first transaction; limits the node rescan
+    #[serde(default)]
+    birthday: i64,
+}
+
+#[derive(Deserialize)]
+struct UtxoMetaPayload {
+    frozen: Option<bool>,
+    /// An empty label removes it
+    label: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RecipientPayload {
+    address: String,
+    amount_sat: u64,
+}
+
+#[derive(Deserialize)]
+struct CoinSelectionPayload {
+    recipients: Vec<RecipientPayload>,
+    /// sat/vB
+    feerate: f64,
+    /// sat/vB; defaults to LONG_TERM_FEERATE
+    long_term_feerate: Option<f64>,
+    /// txid:vout outpoints to spend whatever the algorithms pick
+    #[serde(default)]
+    must_spend: Vec<String>,
+    change_script_type: Option<ScriptType>,
+}
+
+#[derive(Deserialize)]
+struct CreatePsbtPayload {
+    recipients: Vec<RecipientPayload>,
+    /// sat/vB
+    feerate: f64,
+    /// sat/vB; defaults to LONG_TERM_FEERATE
+    long_term_feerate: Option<f64>,
+    #[serde(default)]
+    must_spend: Vec<String>,
+    change_script_type: Option<ScriptType>,
+    /// Defaults to the algorithm with the lowest waste
+    algorithm: Option<SelectionAlgorithm>,
+    /// Signal BIP125 replaceability
+    #[serde(default = "default_rbf")]
+    rbf: bool,
+}
+
+fn default_rbf() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+struct SignPsbtPayload {
+    send_id: Option<String>,
+    /// base64; a PSBT of one of the wallet's sends, e.g. updated by a co-signer
+    psbt: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TransactionQuery {
+    page: Option<u32>,
+    page_size: Option<u32>,
+    direction: Option<TxDirection>,
+    /// Unix time bounds, inclusive
+    from: Option<i64>,
+    to: Option<i64>,
+    label: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LabelPayload {
+    /// An empty label removes it
+    label: String,
+}
+
+#[derive(Deserialize)]
+struct BumpPayload {
+    /// sat/vB
+    feerate: f64,
+    /// Defaults to bump
+    mode: Option<BumpMode>,
+}
+
+#[derive(Deserialize)]
+struct NodeSyncPayload {
+    /// Unix time to rescan from; the stored value is kept when omitted
+    rescan_from: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct UnlockPayload {
+    password: Zeroizing<String>,
+    /// Defaults to (and is capped at) WALLET_UNLOCK_TIMEOUT_SECS
+    timeout_secs: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct AddressQuery {
+    script_type: Option<ScriptType>,
+    chain: Option<AddressChain>,
+    used: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct NewAddressPayload {
+    #[serde(default = "default_script_type")]
+    script_type: ScriptType,
+    #[serde(default = "default_chain")]
+    chain: AddressChain,
+}
+
+fn default_script_type() -> ScriptType {
+    ScriptType::P2wpkh
+}
+
+fn default_chain() -> AddressChain {
+    AddressChain::External
+}
+
+/// POST /wallet/create - Generate a mnemonic and create a wallet from it
+async fn create_wallet(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<CreatePayload>,
+) -> Result<ApiResponse<CreatedWallet>, AppError> {
+    let repo = SqliteWalletRepository::new(state.db_pool.clone());
+    let mnemonic = wallet::generate_mnemonic(payload.word_count)?;
+    let created = wallet::create_seed_wallet(
+        &repo,
+        &payload.name,
+        &payload.network,
+        &mnemonic,
+        &payload.passphrase,
+        &payload.password,
+    )
+    .await?;
+    // A freshly generated mnemonic has no earlier history to rescan
+    node_wallets::enrol(&state, &created.id, created.created_at).await?;
+
+    Ok(ApiResponse::success(
+        CreatedWallet {
+            wallet: created,
+            mnemonic: Zeroizing::new(mnemonic.to_string()),
+        },
+        "Wallet created successfully",
+    ))
+}
+
+/// POST /wallet/import - Create a wallet from an existing mnemonic
+async fn import_wallet(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<ImportPayload>,
+) -> Result<ApiResponse<Wallet>, AppError> {
+    let repo = SqliteWalletRepository::new(state.db_pool.clone());
+    let mnemonic = wallet::parse_mnemonic(&payload.mnemonic)?;
+    let imported = wallet::create_seed_wallet(
+        &repo,
+        &payload.name,
+        &payload.network,
+        &mnemonic,
+        &payload.passphrase,
+        &payload.password,
+    )
+    .await?;
+    node_wallets::enrol(&state, &imported.id, payload.birthday).await?;
+
+    Ok(ApiResponse::success(imported, "Wallet imported successfully"))
+}
+
+/// POST /wallet/import-watch-only - Create a watch-only wallet from an extended public key or descriptors
+async fn import_watch_only(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<WatchOnlyPayload>,
+) -> Result<ApiResponse<Wallet>, AppError> {
+    let source = match (payload.xpub, payload.descriptors.is_empty()) {
+        (Some(key), true) => WatchOnlySource::ExtendedKey {
+            key,
+            script_type: payload.script_type,
+            master_fingerprint: payload.master_fingerprint,
+            derivation_path: payload.derivation_path,
+        },
+        (None, false) => WatchOnlySource::Descriptors(payload.descriptors),
+        _ => {
+            return Err(AppError::BadRequest(
+                "Provide either xpub or descriptors".to_string(),
+            ));
+        }
+    };
+
+    let repo = SqliteWalletRepository::new(state.db_pool.clone());
+    let wallet = wallet::create_watch_only_wallet(&repo, &payload.name, &payload.network, source).await?;
+    node_wallets::enrol(&state, &wallet.id, payload.birthday).await?;
+    Ok(ApiResponse::success(wallet, "Watch-only wallet imported successfully"))
+}
+
+/// GET /wallet/list - List all wallets
+async fn list_wallets(
+    State(state): State<Arc<AppState>>,
+) -> Result<ApiResponse<Vec<Wallet>>, AppError> {
+    let repo = SqliteWalletRepository::new(state.db_pool.clone());
+    let wallets = repo.list().await?;
+    Ok(ApiResponse::success(wallets, "Wallets retrieved successfully"))
+}
+
+/// GET /wallet/:id - Get a wallet with its account keys
+async fn get_wallet(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<ApiResponse<Wallet>, AppError> {
+    let repo = SqliteWalletRepository::new(state.db_pool.clone());
+    let wallet = wallet::load(&repo, &id).await?;
+    Ok(ApiResponse::success(wallet, "Wallet retrieved successfully"))
+}
+
+/// GET /wallet/:id/descriptors - Output descriptors of every account chain
+async fn get_descriptors(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<ApiResponse<Vec<WalletDescriptor>>, AppError> {
+    let repo = SqliteWalletRepository::new(state.db_pool.clone());
+    let wallet = wallet::load(&repo, &id).await?;
+    let descriptors = wallet::wallet_descriptors(&wallet)?;
+    Ok(ApiResponse::success(descriptors, "Wallet descriptors retrieved successfully"))
+}
+
+/// GET /wallet/:id/balance - Confirmed, pending and immature balance
+async fn get_balance(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<ApiResponse<WalletBalance>, AppError> {
+    let repo = SqliteWalletRepository::new(state.db_pool.clone());
+    let wallet = wallet::load(&repo, &id).await?;
+    let balance = balance::balance(&state, &wallet).await?;
+    Ok(ApiResponse::success(balance, "Wallet balance retrieved successfully"))
+}
+
+/// GET /wallet/:id/utxos - Unspent outputs with derivation paths and metadata
+async fn list_utxos(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<ApiResponse<Vec<WalletUtxo>>, AppError> {
+    let repo = SqliteWalletRepository::new(state.db_pool.clone());
+    let wallet = wallet::load(&repo, &id).await?;
+    let utxos = balance::list_utxos(&state, &wallet).await?;
+    Ok(ApiResponse::success(utxos, "Wallet UTXOs retrieved successfully"))
+}
+
+/// PUT /wallet/:id/utxos/:txid/:vout - Freeze or label an outpoint
+async fn update_utxo(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Path((id, txid, vout)): Path<(String, String, u32)>,
+    Json(payload): Json<UtxoMetaPayload>,
+) -> Result<ApiResponse<UtxoMeta>, AppError> {
+    let repo = SqliteWalletRepository::new(state.db_pool.clone());
+    let wallet = wallet::load(&repo, &id).await?;
+    let meta =
+        balance::update_utxo_meta(&state.db_pool, &wallet, &txid, vout, payload.frozen, payload.label).await?;
+    Ok(ApiResponse::success(meta, "UTXO updated successfully"))
+}
+
+/// POST /wallet/:id/coin-selection/preview - What each coin selection algorithm would pick and the resulting fee
+async fn preview_coin_selection(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(payload): Json<CoinSelectionPayload>,
+) -> Result<ApiResponse<CoinSelectionPreview>, AppError> {
+    let repo = SqliteWalletRepository::new(state.db_pool.clone());
+    let wallet = wallet::load(&repo, &id).await?;
+    let recipients: Vec<Recipient> = payload
+        .recipients
+        .into_iter()
+        .map(|r| Recipient {
+            address: r.address,
+            amount_sat: r.amount_sat,
+        })
+        .collect();
+    let options = SpendOptions {
+        feerate: payload.feerate,
+        long_term_feerate: payload.long_term_feerate,
+        must_spend: payload.must_spend,
+        change_script_type: payload.change_script_type,
+    };
+
+    let preview = spend::preview(&state, &wallet, &recipients, &options).await?;
+    Ok(ApiResponse::success(preview, "Coin selection preview generated successfully"))
+}
+
+/// POST /wallet/:id/psbt - Select coins and build an unsigned PSBT, stored as a new send
+async fn create_psbt(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(payload): Json<CreatePsbtPayload>,
+) -> Result<ApiResponse<PendingSend>, AppError> {
+    let repo = SqliteWalletRepository::new(state.db_pool.clone());
+    let wallet = wallet::load(&repo, &id).await?;
+    let recipients: Vec<Recipient> = payload
+        .recipients
+        .into_iter()
+        .map(|r| Recipient {
+            address: r.address,
+            amount_sat: r.amount_sat,
+        })
+        .collect();
+    let options = SpendOptions {
+        feerate: payload.feerate,
+        long_term_feerate: payload.long_term_feerate,
+        must_spend: payload.must_spend,
+        change_script_type: payload.change_script_type,
+    };
+
+    let send = psbt::create(&state, &wallet, &recipients, &options, payload.algorithm, payload.rbf).await?;
+    Ok(ApiResponse::success_with_status(
+        StatusCode::CREATED,
+        send,
+        "PSBT created successfully",
+    ))
+}
+
+/// GET /wallet/:id/psbt - Sends of the wallet at every stage, newest first
+async fn list_psbts(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<ApiResponse<Vec<PendingSend>>, AppError> {
+    let repo = SqliteWalletRepository::new(state.db_pool.clone());
+    let wallet = wallet::load(&repo, &id).await?;
+    let sends = psbt::list(&state, &wallet).await?;
+    Ok(ApiResponse::success(sends, "Sends retrieved successfully"))
+}
+
+/// GET /wallet/:id/psbt/:send_id - One send with its latest PSBT
+async fn get_psbt(
+    State(state): State<Arc<AppState>>,
+    Path((id, send_id)): Path<(String, String)>,
+) -> Result<ApiResponse<PendingSend>, AppError> {
+    let repo = SqliteWalletRepository::new(state.db_pool.clone());
+    let wallet = wallet::load(&repo, &id).await?;
+    let send = psbt::get(&state, &wallet, &send_id).await?;
+    Ok(ApiResponse::success(send, "Send retrieved successfully"))
+}
+
+/// DELETE /wallet/:id/psbt/:send_id - Abandon a send before broadcast, releasing its inputs
+async fn abandon_psbt(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Path((id, send_id)): Path<(String, String)>,
+) -> Result<ApiResponse<PendingSend>, AppError> {
+    let repo = SqliteWalletRepository::new(state.db_pool.clone());
+    let wallet = wallet::load(&repo, &id).await?;
+    let send = psbt::abandon(&state, &wallet, &send_id).await?;
+    Ok(ApiResponse::success(send, "Send abandoned successfully"))
+}
+
+/// POST /wallet/:id/psbt/sign - Sign a send's PSBT with the unlocked wallet
+async fn sign_psbt(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(payload): Json<SignPsbtPayload>,
+) -> Result<ApiResponse<PendingSend>, AppError> {
+    let repo = SqliteWalletRepository::new(state.db_pool.clone());
+    let wallet = wallet::load(&repo, &id).await?;
+    let send = psbt::sign(&state, &wallet, payload.send_id.as_deref(), payload.psbt.as_deref()).await?;
+    Ok(ApiResponse::success(send, "PSBT signed successfully"))
+}
+
+/// GET /wallet/:id/transactions - Paginated history with net amounts and fees
+async fn list_transactions(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Query(query): Query<TransactionQuery>,
+) -> Result<ApiResponse<TransactionPage>, AppError> {
+    let page = query.page.unwrap_or(1).max(1);
+    let page_size = query.page_size.unwrap_or(25).clamp(1, 100);
+    let filter = TransactionFilter {
+        direction: query.direction,
+        from: query.from,
+        to: query.to,
+        label: query.label.filter(|l| !l.is_empty()),
+    };
+
+    let repo = SqliteWalletRepository::new(state.db_pool.clone());
+    let wallet = wallet::load(&repo, &id).await?;
+    let transactions = transaction_history::list(&state, &wallet, &filter, page, page_size).await?;
+    Ok(ApiResponse::success(transactions, "Wallet transactions retrieved successfully"))
+}
+
+/// PUT /wallet/:id/transactions/:txid - Label a transaction
+async fn label_transaction(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Path((id, txid)): Path<(String, String)>,
+    Json(payload): Json<LabelPayload>,
+) -> Result<ApiResponse<()>, AppError> {
+    let repo = SqliteWalletRepository::new(state.db_pool.clone());
+    let wallet = wallet::load(&repo, &id).await?;
+    transaction_history::set_label(&state, &wallet, &txid, &payload.label).await?;
+    Ok(ApiResponse::success((), "Transaction label updated successfully"))
+}
+
+/// POST /wallet/:id/transactions/:txid/bump - Build a BIP125 replacement paying a higher fee, or cancelling the payments
+async fn bump_transaction(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Path((id, txid)): Path<(String, String)>,
+    Json(payload): Json<BumpPayload>,
+) -> Result<ApiResponse<FeeBump>, AppError> {
+    let repo = SqliteWalletRepository::new(state.db_pool.clone());
+    let wallet = wallet::load(&repo, &id).await?;
+    let mode = payload.mode.unwrap_or(BumpMode::Bump);
+    let bump = fee_bump::bump(&state, &wallet, &txid, payload.feerate, mode).await?;
+    Ok(ApiResponse::success_with_status(
+        StatusCode::CREATED,
+        bump,
+        "Replacement PSBT created successfully",
+    ))
+}
+
+/// GET /wallet/:id/node-sync - Import and rescan state of the wallet's node wallet
+async fn get_node_sync(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<ApiResponse<NodeWalletStatus>, AppError> {
+    let repo = SqliteWalletRepository::new(state.db_pool.clone());
+    let wallet = wallet::load(&repo, &id).await?;
+    let status = node_wallets::status(&state, &wallet).await?;
+    Ok(ApiResponse::success(status, "Node wallet status retrieved successfully"))
+}
+
+/// POST /wallet/:id/node-sync - Import the wallet's descriptors into the active node again
+async fn resync_node_wallet(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(payload): Json<NodeSyncPayload>,
+) -> Result<ApiResponse<NodeWalletStatus>, AppError> {
+    let repo = SqliteWalletRepository::new(state.db_pool.clone());
+    let wallet = wallet::load(&repo, &id).await?;
+    let status = node_wallets::resync(&state, &wallet, payload.rescan_from).await?;
+    Ok(ApiResponse::success_with_status(
+        StatusCode::ACCEPTED,
+        status,
+        "Node wallet import started",
+    ))
+}
+
+/// DELETE /wallet/:id/node-sync - Abort the node wallet's rescan
+async fn abort_node_rescan(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<ApiResponse<NodeWalletStatus>, AppError> {
+    let repo = SqliteWalletRepository::new(state.db_pool.clone());
+    let wallet = wallet::load(&repo, &id).await?;
+    let status = node_wallets::abort_rescan(&state, &wallet).await?;
+    Ok(ApiResponse::success(status, "Node wallet rescan aborted"))
+}
+
+/// POST /wallet/:id/unlock - Decrypt the wallet seed and keep it in memory for a while
+async fn unlock_wallet(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(payload): Json<UnlockPayload>,
+) -> Result<ApiResponse<WalletLockStatus>, AppError> {
+    let repo = SqliteWalletRepository::new(state.db_pool.clone());
+    let status = wallet::unlock(&repo, &state.wallet_keys, &id, payload.password, payload.timeout_secs).await?;
+    Ok(ApiResponse::success(status, "Wallet unlocked successfully"))
+}
+
+/// POST /wallet/:id/lock - Zeroize and forget the decrypted wallet seed
+async fn lock_wallet(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<ApiResponse<WalletLockStatus>, AppError> {
+    state.wallet_keys.lock(&id).await;
+    Ok(ApiResponse::success(
+        WalletLockStatus {
+            wallet_id: id,
+            unlocked: false,
+            unlocked_until: None,
+        },
+        "Wallet locked successfully",
+    ))
+}
+
+/// GET /wallet/:id/lock - Whether the wallet is unlocked and until when
+async fn get_lock_status(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<ApiResponse<WalletLockStatus>, AppError> {
+    let unlocked_until = state.wallet_keys.unlocked_until(&id).await;
+    Ok(ApiResponse::success(
+        WalletLockStatus {
+            wallet_id: id,
+            unlocked: unlocked_until.is_some(),
+            unlocked_until,
+        },
+        "Wallet lock status retrieved successfully",
+    ))
+}
+
+/// GET /wallet/:id/addresses?script_type=&chain=&used= - Derived addresses within the gap limit
+async fn list_addresses(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Query(query): Query<AddressQuery>,
+) -> Result<ApiResponse<Vec<WalletAddress>>, AppError> {
+    let repo = SqliteWalletRepository::new(state.db_pool.clone());
+    let wallet = wallet::load(&repo, &id).await?;
+    let filter = AddressFilter {
+        script_type: query.script_type,
+        chain: query.chain,
+        used: query.used,
+    };
+
+    let addresses = wallet_addresses::list_addresses(&state, &wallet, &filter).await?;
+    Ok(ApiResponse::success(addresses, "Wallet addresses retrieved successfully"))
+}
+
+/// GET /wallet/:id/addresses/unused?script_type=&chain= - Derived addresses not seen on chain
+async fn list_unused_addresses(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Query(query): Query<AddressQuery>,
+) -> Result<ApiResponse<Vec<WalletAddress>>, AppError> {
+    let repo = SqliteWalletRepository::new(state.db_pool.clone());
+    let wallet = wallet::load(&repo, &id).await?;
+    let filter = AddressFilter {
+        script_type: query.script_type,
+        chain: query.chain,
+        used: Some(false),
+    };
+
+    let addresses = wallet_addresses::list_addresses(&state, &wallet, &filter).await?;
+    Ok(ApiResponse::success(addresses, "Unused wallet addresses retrieved successfully"))
+}
+
+/// POST /wallet/:id/addresses/new - Issue the next fresh address
+async fn new_address(
+    _: WriteAccess,
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(payload): Json<NewAddressPayload>,
+) -> Result<ApiResponse<WalletAddress>, AppError> {
+    let repo = SqliteWalletRepository::new(state.db_pool.clone());
+    let wallet = wallet::load(&repo, &id).await?;
+
+    let address =
+        wallet_addresses::next_address(&state, &wallet, payload.script_type, payload.chain).await?;
+    Ok(ApiResponse::success(address, "Address issued successfully"))
+}
+
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/wallet/create", post(create_wallet))
+        .route("/wallet/import", post(import_wallet))
+        .route("/wallet/import-watch-only", post(import_watch_only))
+        .route("/wallet/list", get(list_wallets))
+        .route("/wallet/{id}", get(get_wallet))
+        .route("/wallet/{id}/descriptors", get(get_descriptors))
+        .route("/wallet/{id}/balance", get(get_balance))
+        .route("/wallet/{id}/utxos", get(list_utxos))
+        .route("/wallet/{id}/utxos/{txid}/{vout}", put(update_utxo))
+        .route("/wallet/{id}/coin-selection/preview", post(preview_coin_selection))
+        .route("/wallet/{id}/psbt", post(create_psbt))
+        .route("/wallet/{id}/psbt", get(list_psbts))
+        .route("/wallet/{id}/psbt/sign", post(sign_psbt))
+        .route("/wallet/{id}/psbt/{send_id}", get(get_psbt))
+        .route("/wallet/{id}/psbt/{send_id}", delete(abandon_psbt))
+        .route("/wallet/{id}/transactions", get(list_transactions))
+        .route("/wallet/{id}/transactions/{txid}", put(label_transaction))
+        .route("/wallet/{id}/transactions/{txid}/bump", post(bump_transaction))
+        .route("/wallet/{id}/node-sync", get(get_node_sync))
+        .route("/wallet/{id}/node-sync", post(resync_node_wallet))
+        .route("/wallet/{id}/node-sync", delete(abort_node_rescan))
+        .route("/wallet/{id}/unlock", post(unlock_wallet))
+        .route("/wallet/{id}/lock", post(lock_wallet))
+        .route("/wallet/{id}/lock", get(get_lock_status))
+        .route("/wallet/{id}/addresses", get(list_addresses))
+        .route("/wallet/{id}/addresses/new", post(new_address))
+        .route("/wallet/{id}/addresses/unused", get(list_unused_addresses))
+}
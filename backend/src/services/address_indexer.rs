@@ -0,0 +1,197 @@
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::config::indexer::AddressIndexConfig;
+use crate::db::{AddressIndexRepository, SqliteAddressIndexRepository};
+use crate::domain::address::{AddressDetails, IndexedBlock, IndexedOutput, IndexedSpend};
+use crate::errors::AppError;
+use crate::services::bitcoin_rpc::{BitcoinRpc, BlockVerboseRaw};
+use crate::state::app_state::AppState;
+use crate::utils::amount::btc_to_sats;
+
+/// Spawn the optional background task that walks blocks from the active node
+/// into the local address index. Does nothing unless the index is enabled.
+pub fn spawn(state: AppState) {
+    let config = AddressIndexConfig::from_env();
+    if !config.enabled {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(config.poll_secs.max(1)));
+        let mut events = state.chain_events.subscribe();
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                event = events.recv() => {
+                    if let Err(RecvError::Closed) = event {
+                        return;
+                    }
+                }
+            }
+
+            match sync_once(&state).await {
+                Ok(0) => {}
+                Ok(added) => println!("🗂️  Address index: {} new blocks", added),
+                Err(e) => eprintln!("⚠️  Address index sync failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Undo blocks the active node no longer has on its chain, then index new
+/// blocks up to its tip. Returns the number of blocks indexed.
+pub async fn sync_once(state: &AppState) -> Result<u64, AppError> {
+    let rpc = state.active_rpc().await?;
+    let repo = SqliteAddressIndexRepository::new(state.db_pool.clone());
+    let node_height = rpc.get_block_count().await?;
+
+    rollback_divergent(&rpc, &repo, node_height).await?;
+
+    let tip = repo.tip().await?;
+    let mut next = tip.as_ref().map_or(0, |(height, _)| height + 1);
+    let mut previous_hash = tip.map(|(_, hash)| hash);
+    let mut added = 0;
+
+    while next <= node_height {
+        let hash = rpc.get_block_hash(next).await?;
+        let block = rpc.get_block_verbose(&hash).await?;
+
+        // A reorg raced us; the next round rolls it back
+        if block.previousblockhash != previous_hash {
+            break;
+        }
+
+        previous_hash = Some(block.hash.clone());
+        repo.apply_block(&extract_block(block)).await?;
+        added += 1;
+        next += 1;
+    }
+
+    Ok(added)
+}
+
+/// Address balance, UTXOs and a page of history from the local index
+pub async fn address_details(
+    state: &AppState,
+    address: &str,
+    page: u32,
+    page_size: u32,
+) -> Result<AddressDetails, AppError> {
+    if !AddressIndexConfig::from_env().enabled {
+        return Err(AppError::ServiceUnavailable(
+            "Address index is disabled; set ADDRESS_INDEX_ENABLED=true to build it".to_string(),
+        ));
+    }
+
+    let rpc = state.active_rpc().await?;
+    let validation = rpc.validate_address(address).await?;
+    if !validation.isvalid {
+        return Err(AppError::BadRequest(format!(
+            "'{}' is not a valid address for the active node's network",
+            address
+        )));
+    }
+
+    let script_pub_key = validation.script_pub_key.ok_or_else(|| {
+        AppError::Internal(format!("Node returned no scriptPubKey for '{}'", address))
+    })?;
+
+    let repo = SqliteAddressIndexRepository::new(state.db_pool.clone());
+    let totals = repo.totals(&script_pub_key).await?;
+    let utxos = repo.list_utxos(&script_pub_key).await?;
+    let offset = (page.saturating_sub(1) as i64) * page_size as i64;
+    let history = repo.list_history(&script_pub_key, offset, page_size as i64).await?;
+    let indexed_height = repo.tip().await?.map(|(height, _)| height);
+
+    Ok(AddressDetails {
+        address: address.to_string(),
+        script_pub_key,
+        balance_sat: totals.total_received_sat - totals.total_sent_sat,
+        total_received_sat: totals.total_received_sat,
+        total_sent_sat: totals.total_sent_sat,
+        tx_count: totals.tx_count,
+        utxos,
+        history,
+        page,
+        page_size,
+        indexed_height,
+    })
+}
+
+async fn rollback_divergent(
+    rpc: &BitcoinRpc,
+    repo: &SqliteAddressIndexRepository,
+    node_height: u64,
+) -> Result<(), AppError> {
+    let Some((tip_height, _)) = repo.tip().await? else {
+        return Ok(());
+    };
+
+    // Different genesis: the active node is on another network
+    if repo.block_hash_at(0).await? != Some(rpc.get_block_hash(0).await?) {
+        repo.rollback_from(0).await?;
+        return Ok(());
+    }
+
+    let mut height = tip_height;
+    loop {
+        let matches = height <= node_height
+            && repo.block_hash_at(height).await? == Some(rpc.get_block_hash(height).await?);
+        if matches {
+            break;
+        }
+        height -= 1;
+    }
+
+    if height < tip_height {
+        repo.rollback_from(height + 1).await?;
+        println!(
+            "🔀 Address index: rolled back {} blocks to height {}",
+            tip_height - height,
+            height
+        );
+    }
+
+    Ok(())
+}
+
+fn extract_block(block: BlockVerboseRaw) -> IndexedBlock {
+    let mut outputs = Vec::new();
+    let mut spends = Vec::new();
+
+    for tx in block.tx {
+        for (vin, input) in tx.vin.into_iter().enumerate() {
+            if let (Some(prev_txid), Some(prev_vout)) = (input.txid, input.vout) {
+                spends.push(IndexedSpend {
+                    prev_txid,
+                    prev_vout,
+                    txid: tx.txid.clone(),
+                    vin: vin as u32,
+                });
+            }
+        }
+
+        for output in tx.vout {
+            // OP_RETURN outputs are provably unspendable and carry no address
+            if output.script_pub_key.script_type == "nulldata" {
+                continue;
+            }
+            outputs.push(IndexedOutput {
+                txid: tx.txid.clone(),
+                vout: output.n,
+                script_pub_key: output.script_pub_key.hex,
+                address: output.script_pub_key.address,
+                value_sat: btc_to_sats(output.value),
+            });
+        }
+    }
+
+    IndexedBlock {
+        height: block.height,
+        hash: block.hash,
+        outputs,
+        spends,
+    }
+}
@@ -0,0 +1,333 @@
+use async_trait::async_trait;
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+
+use crate::config::constants::env_keys;
+use crate::config::env::var_or;
+use crate::config::indexer::AddressIndexConfig;
+use crate::db::{
+    AddressIndexRepository, SqliteAddressIndexRepository, SqliteWalletAddressRepository,
+    SqliteWalletUtxoRepository, WalletAddressRepository, WalletUtxoRepository,
+};
+use crate::domain::utxo::{Amount, UtxoMeta, WalletBalance, WalletUtxo};
+use crate::domain::wallet::{AddressFilter, Wallet, WalletAddress};
+use crate::errors::AppError;
+use crate::services::bitcoin_rpc::BitcoinRpc;
+use crate::services::{node_wallets, wallet_addresses};
+use crate::state::app_state::AppState;
+use crate::utils::amount::btc_to_sats;
+
+/// Balance buckets in satoshis, as reported by a data source
+#[derive(Debug, Default)]
+pub struct BalanceBuckets {
+    pub confirmed: u64,
+    pub trusted_pending: u64,
+    pub untrusted_pending: u64,
+    pub immature: u64,
+}
+
+/// An unspent output as reported by a data source
+#[derive(Debug)]
+pub struct SourceUtxo {
+    pub txid: String,
+    pub vout: u32,
+    pub value_sat: u64,
+    pub address: Option<String>,
+    pub script_pub_key: Option<String>,
+    /// Derivation path known to the source, used for addresses we have not derived yet
+    pub derivation_path: Option<String>,
+    pub confirmations: u32,
+    pub safe: bool,
+}
+
+/// Where a wallet's balance and UTXOs come from. The node wallet and the
+/// local address index are built in; an Electrum server would be another one.
+#[async_trait]
+pub trait WalletDataSource: Send + Sync {
+    /// Reported as `source` with each balance
+    fn name(&self) -> &'static str;
+
+    async fn balance(&self) -> Result<BalanceBuckets, AppError>;
+
+    /// Unspent outputs, including unconfirmed ones
+    async fn utxos(&self) -> Result<Vec<SourceUtxo>, AppError>;
+}
+
+/// The watch-only Core wallet the wallet's descriptors are imported into
+pub struct NodeWalletSource {
+    rpc: BitcoinRpc,
+    node_wallet: String,
+}
+
+impl NodeWalletSource {
+    pub fn new(rpc: &BitcoinRpc, wallet_id: &str) -> Self {
+        let node_wallet = node_wallets::node_wallet_name(wallet_id);
+        Self {
+            rpc: rpc.for_wallet(&node_wallet),
+            node_wallet,
+        }
+    }
+
+    fn map_error(&self, e: AppError) -> AppError {
+        match e {
+            // -18: wallet not loaded
+            AppError::BitcoinRpcError { code: -18, .. } => AppError::ServiceUnavailable(format!(
+                "Node wallet '{}' is not loaded on the active node yet; check /wallet/{{id}}/node-sync",
+                self.node_wallet
+            )),
+            e => e,
+        }
+    }
+}
+
+#[async_trait]
+impl WalletDataSource for NodeWalletSource {
+    fn name(&self) -> &'static str {
+        "node_wallet"
+    }
+
+    async fn balance(&self) -> Result<BalanceBuckets, AppError> {
+        let balances = self.rpc.get_balances().await.map_err(|e| self.map_error(e))?;
+        // Core's trusted bucket mixes confirmed outputs with our own unconfirmed change
+        let confirmed: u64 = self
+            .rpc
+            .list_unspent(1)
+            .await
+            .map_err(|e| self.map_error(e))?
+            .iter()
+            .map(|u| btc_to_sats(u.amount))
+            .sum();
+        let trusted = btc_to_sats(balances.mine.trusted);
+
+        Ok(BalanceBuckets {
+            confirmed,
+            trusted_pending: trusted.saturating_sub(confirmed),
+            untrusted_pending: btc_to_sats(balances.mine.untrusted_pending),
+            immature: btc_to_sats(balances.mine.immature),
+        })
+    }
+
+    async fn utxos(&self) -> Result<Vec<SourceUtxo>, AppError> {
+        let unspent = self.rpc.list_unspent(0).await.map_err(|e| self.map_error(e))?;
+
+        Ok(unspent
+            .into_iter()
+            .map(|u| SourceUtxo {
+                derivation_path: u.desc.as_deref().and_then(origin_path),
+                txid: u.txid,
+                vout: u.vout,
+                value_sat: btc_to_sats(u.amount),
+                address: u.address,
+                script_pub_key: Some(u.script_pub_key),
+                confirmations: u.confirmations,
+                safe: u.safe,
+            })
+            .collect())
+    }
+}
+
+/// Confirmed outputs to the wallet's derived addresses in the local address
+/// index. The index holds no mempool and no coinbase flags, so pending and
+/// immature balances are always zero.
+pub struct AddressIndexSource {
+    pool: SqlitePool,
+    addresses: Vec<WalletAddress>,
+}
+
+impl AddressIndexSource {
+    pub fn new(pool: SqlitePool, addresses: Vec<WalletAddress>) -> Self {
+        Self { pool, addresses }
+    }
+}
+
+#[async_trait]
+impl WalletDataSource for AddressIndexSource {
+    fn name(&self) -> &'static str {
+        "address_index"
+    }
+
+    async fn balance(&self) -> Result<BalanceBuckets, AppError> {
+        Ok(BalanceBuckets {
+            confirmed: self.utxos().await?.iter().map(|u| u.value_sat).sum(),
+            ..Default::default()
+        })
+    }
+
+    async fn utxos(&self) -> Result<Vec<SourceUtxo>, AppError> {
+        let repo = SqliteAddressIndexRepository::new(self.pool.clone());
+        let Some((tip_height, _)) = repo.tip().await? else {
+            return Ok(vec![]);
+        };
+
+        let mut utxos = Vec::new();
+        for address in &self.addresses {
+            for utxo in repo.list_utxos(&address.script_pub_key).await? {
+                utxos.push(SourceUtxo {
+                    txid: utxo.txid,
+                    vout: utxo.vout,
+                    value_sat: utxo.value_sat,
+                    address: Some(address.address.clone()),
+                    script_pub_key: Some(address.script_pub_key.clone()),
+                    derivation_path: None,
+                    confirmations: (tip_height + 1).saturating_sub(utxo.height) as u32,
+                    safe: true,
+                });
+            }
+        }
+        Ok(utxos)
+    }
+}
+
+/// The data source selected by WALLET_DATA_SOURCE
+pub async fn data_source(state: &AppState, wallet: &Wallet) -> Result<Box<dyn WalletDataSource>, AppError> {
+    let source: String = var_or(env_keys::WALLET_DATA_SOURCE, "node_wallet".to_string());
+
+    match source.as_str() {
+        "node_wallet" => {
+            let rpc = state.active_rpc().await?;
+            Ok(Box::new(NodeWalletSource::new(&rpc, &wallet.id)))
+        }
+        "address_index" => {
+            if !AddressIndexConfig::from_env().enabled {
+                return Err(AppError::ServiceUnavailable(
+                    "Address index is disabled; set ADDRESS_INDEX_ENABLED=true to build it".to_string(),
+                ));
+            }
+            // Extend the gap-limit lookahead so new deposits are covered
+            wallet_addresses::sync_addresses(state, wallet).await?;
+            let addresses = derived_addresses(&state.db_pool, &wallet.id)
+                .await?
+                .into_values()
+                .collect();
+            Ok(Box::new(AddressIndexSource::new(state.db_pool.clone(), addresses)))
+        }
+        other => Err(AppError::Internal(format!(
+            "Unknown {} '{}'; use node_wallet or address_index",
+            env_keys::WALLET_DATA_SOURCE,
+            other
+        ))),
+    }
+}
+
+/// Balance of a wallet in confirmed, pending and immature buckets
+pub async fn balance(state: &AppState, wallet: &Wallet) -> Result<WalletBalance, AppError> {
+    let source = data_source(state, wallet).await?;
+    let buckets = source.balance().await?;
+    let spendable = buckets.confirmed + buckets.trusted_pending;
+
+    Ok(WalletBalance {
+        wallet_id: wallet.id.clone(),
+        source: source.name(),
+        confirmed: Amount::from_sat(buckets.confirmed),
+        trusted_pending: Amount::from_sat(buckets.trusted_pending),
+        untrusted_pending: Amount::from_sat(buckets.untrusted_pending),
+        immature: Amount::from_sat(buckets.immature),
+        spendable: Amount::from_sat(spendable),
+        total: Amount::from_sat(spendable + buckets.untrusted_pending + buckets.immature),
+    })
+}
+
+/// Unspent outputs of a wallet with their derivation paths and metadata,
+/// largest first
+pub async fn list_utxos(state: &AppState, wallet: &Wallet) -> Result<Vec<WalletUtxo>, AppError> {
+    let source = data_source(state, wallet).await?;
+    let unspent = source.utxos().await?;
+
+    let addresses = derived_addresses(&state.db_pool, &wallet.id).await?;
+    let meta: HashMap<(String, u32), UtxoMeta> = SqliteWalletUtxoRepository::new(state.db_pool.clone())
+        .list_meta(&wallet.id)
+        .await?
+        .into_iter()
+        .map(|m| ((m.txid.clone(), m.vout), m))
+        .collect();
+
+    let mut utxos: Vec<WalletUtxo> = unspent
+        .into_iter()
+        .map(|u| {
+            let derived = u.address.as_ref().and_then(|a| addresses.get(a));
+            let meta = meta.get(&(u.txid.clone(), u.vout));
+
+            WalletUtxo {
+                outpoint: format!("{}:{}", u.txid, u.vout),
+                value: Amount::from_sat(u.value_sat),
+                script_pub_key: u
+                    .script_pub_key
+                    .or_else(|| derived.map(|a| a.script_pub_key.clone()))
+                    .unwrap_or_default(),
+                script_type: derived.map(|a| a.script_type),
+                derivation_path: derived.map(|a| a.derivation_path.clone()).or(u.derivation_path),
+                confirmations: u.confirmations,
+                safe: u.safe,
+                frozen: meta.is_some_and(|m| m.frozen),
+                label: meta.and_then(|m| m.label.clone()),
+                txid: u.txid,
+                vout: u.vout,
+                address: u.address,
+            }
+        })
+        .collect();
+
+    utxos.sort_by(|a, b| b.value.sat.cmp(&a.value.sat).then_with(|| a.outpoint.cmp(&b.outpoint)));
+    Ok(utxos)
+}
+
+/// Freeze or label an outpoint. Fields left as None keep their current value;
+/// an empty label removes it.
+pub async fn update_utxo_meta(
+    pool: &SqlitePool,
+    wallet: &Wallet,
+    txid: &str,
+    vout: u32,
+    frozen: Option<bool>,
+    label: Option<String>,
+) -> Result<UtxoMeta, AppError> {
+    if txid.len() != 64 || !txid.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(AppError::BadRequest(format!("'{}' is not a txid", txid)));
+    }
+
+    let repo = SqliteWalletUtxoRepository::new(pool.clone());
+    let txid = txid.to_ascii_lowercase();
+    let mut meta = repo
+        .list_meta(&wallet.id)
+        .await?
+        .into_iter()
+        .find(|m| m.txid == txid && m.vout == vout)
+        .unwrap_or(UtxoMeta {
+            txid,
+            vout,
+            frozen: false,
+            label: None,
+        });
+
+    if let Some(frozen) = frozen {
+        meta.frozen = frozen;
+    }
+    if let Some(label) = label {
+        meta.label = Some(label.trim().to_string()).filter(|l| !l.is_empty());
+    }
+
+    repo.set_meta(&wallet.id, &meta).await?;
+    Ok(meta)
+}
+
+/// The wallet's derived addresses by address string
+async fn derived_addresses(pool: &SqlitePool, wallet_id: &str) -> Result<HashMap<String, WalletAddress>, AppError> {
+    Ok(SqliteWalletAddressRepository::new(pool.clone())
+        .list_addresses(wallet_id, &AddressFilter::default())
+        .await?
+        .into_iter()
+        .map(|a| (a.address.clone(), a))
+        .collect())
+}
+
+/// `m/...` path from the key origin of a descriptor like `wpkh([73c5da0a/84h/0h/0h/0/5]02...)`
+pub fn origin_path(desc: &str) -> Option<String> {
+    let origin = desc.split_once('[')?.1.split_once(']')?.0;
+    let path = origin.split_once('/').map_or("", |(_, path)| path);
+
+    Some(if path.is_empty() {
+        "m".to_string()
+    } else {
+        format!("m/{}", path.replace(['h', 'H'], "'"))
+    })
+}
@@ -0,0 +1,302 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::db::{NewNodeConfig, NodeConfigRepository};
+use crate::domain::bitcoin_conf::{BitcoinConfImport, ProposedNodeConfig, RpcAuthSource};
+use crate::errors::AppError;
+
+struct NetworkDefaults {
+    /// Chain name as reported by getblockchaininfo
+    chain: &'static str,
+    /// bitcoin.conf section name
+    section: &'static str,
+    /// Subdirectory of the datadir holding the cookie ("" for mainnet)
+    subdir: &'static str,
+    rpc_port: u16,
+}
+
+const NETWORKS: [NetworkDefaults; 5] = [
+    NetworkDefaults { chain: "main", section: "main", subdir: "", rpc_port: 8332 },
+    NetworkDefaults { chain: "test", section: "test", subdir: "testnet3", rpc_port: 18332 },
+    NetworkDefaults { chain: "testnet4", section: "testnet4", subdir: "testnet4", rpc_port: 48332 },
+    NetworkDefaults { chain: "signet", section: "signet", subdir: "signet", rpc_port: 38332 },
+    NetworkDefaults { chain: "regtest", section: "regtest", subdir: "regtest", rpc_port: 18443 },
+];
+
+/// Options that bitcoind only applies to mainnet when set outside a network
+/// section (those registered with `ArgsManager::NETWORK_ONLY`)
+const NETWORK_ONLY_OPTIONS: [&str; 7] = ["addnode", "connect", "port", "bind", "rpcport", "rpcbind", "wallet"];
+
+/// Options in the default section and per network section, in file order
+#[derive(Default)]
+struct ParsedConf {
+    global: Vec<(String, String)>,
+    sections: HashMap<String, Vec<(String, String)>>,
+}
+
+impl ParsedConf {
+    fn last<'a>(options: &'a [(String, String)], key: &str) -> Option<&'a str> {
+        options
+            .iter()
+            .rev()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Resolve an option for a network the way bitcoind does: the network
+    /// section wins, then the default section (network-only options excepted)
+    fn get(&self, network: &NetworkDefaults, key: &str) -> Option<&str> {
+        let section = self
+            .sections
+            .get(network.section)
+            .and_then(|options| Self::last(options, key));
+
+        section.or_else(|| {
+            if network.chain == "main" || !NETWORK_ONLY_OPTIONS.contains(&key) {
+                Self::last(&self.global, key)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Network selected in the default section (`chain=`, `regtest=1`, ...)
+    fn default_chain(&self) -> &'static str {
+        if let Some(chain) = Self::last(&self.global, "chain")
+            && let Some(network) = NETWORKS.iter().find(|n| n.chain == chain)
+        {
+            return network.chain;
+        }
+        for (flag, chain) in [("regtest", "regtest"), ("signet", "signet"), ("testnet4", "testnet4"), ("testnet", "test")] {
+            if Self::last(&self.global, flag) == Some("1") {
+                return chain;
+            }
+        }
+        "main"
+    }
+}
+
+/// Parse the text of a bitcoin.conf into one proposed node configuration per
+/// network it configures. `conf_dir` is the directory the file was read from,
+/// used as the datadir when looking for `.cookie` files if `datadir` is unset.
+pub async fn import(content: &str, conf_dir: Option<&Path>) -> BitcoinConfImport {
+    let (parsed, mut warnings) = parse(content);
+
+    // Mainnet is implied only when nothing else is configured
+    let default_chain = parsed.default_chain();
+    let include_default = default_chain != "main" || parsed.sections.is_empty();
+    let networks: Vec<&NetworkDefaults> = NETWORKS
+        .iter()
+        .filter(|n| {
+            (include_default && n.chain == default_chain) || parsed.sections.contains_key(n.section)
+        })
+        .collect();
+    if networks.is_empty() {
+        warnings.push("No known network is configured".to_string());
+    }
+
+    let datadir = parsed
+        .get(&NETWORKS[0], "datadir")
+        .map(PathBuf::from)
+        .or_else(|| conf_dir.map(Path::to_path_buf));
+
+    let mut proposals = Vec::with_capacity(networks.len());
+    for network in networks {
+        proposals.push(propose(&parsed, network, datadir.as_deref()).await);
+    }
+
+    BitcoinConfImport { proposals, warnings }
+}
+
+/// Store every proposal that has credentials, reusing identical existing configurations
+pub async fn persist(
+    repo: &impl NodeConfigRepository,
+    import: &mut BitcoinConfImport,
+) -> Result<(), AppError> {
+    let existing = repo.list().await?;
+
+    for proposal in import.proposals.iter_mut() {
+        if proposal.auth_source == RpcAuthSource::Missing {
+            continue;
+        }
+
+        if let Some(config) = existing.iter().find(|c| {
+            c.rpc_url == proposal.rpc_url
+                && c.rpc_user == proposal.rpc_user
+                && c.rpc_password == proposal.rpc_password
+        }) {
+            proposal.node_config_id = Some(config.id.clone());
+            continue;
+        }
+
+        let config = repo
+            .create(NewNodeConfig {
+                name: proposal.name.clone(),
+                rpc_url: proposal.rpc_url.clone(),
+                rpc_user: proposal.rpc_user.clone(),
+                rpc_password: proposal.rpc_password.clone(),
+                network: proposal.network.clone(),
+            })
+            .await?;
+        proposal.node_config_id = Some(config.id);
+        proposal.created = true;
+    }
+
+    Ok(())
+}
+
+fn parse(content: &str) -> (ParsedConf, Vec<String>) {
+    let mut parsed = ParsedConf::default();
+    let mut warnings = Vec::new();
+    let mut section: Option<String> = None;
+
+    for (index, raw_line) in content.lines().enumerate() {
+        // bitcoind treats everything after '#' as a comment
+        let line = raw_line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            let name = name.trim().to_string();
+            if !NETWORKS.iter().any(|n| n.section == name) {
+                warnings.push(format!("Line {}: unknown section [{}]", index + 1, name));
+            }
+            section = Some(name);
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            warnings.push(format!("Line {}: ignoring '{}'", index + 1, line));
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim().to_string());
+        if key == "includeconf" {
+            warnings.push(format!("Line {}: includeconf is not followed", index + 1));
+        }
+
+        // `regtest.rpcport=...` is shorthand for an option inside [regtest]
+        let (target, key) = match key.split_once('.') {
+            Some((prefix, key)) => (Some(prefix.to_string()), key),
+            None => (section.clone(), key),
+        };
+        match target {
+            Some(target) => parsed
+                .sections
+                .entry(target)
+                .or_default()
+                .push((key.to_string(), value)),
+            None => parsed.global.push((key.to_string(), value)),
+        }
+    }
+
+    (parsed, warnings)
+}
+
+async fn propose(
+    parsed: &ParsedConf,
+    network: &NetworkDefaults,
+    datadir: Option<&Path>,
+) -> ProposedNodeConfig {
+    let mut warnings = Vec::new();
+
+    // bitcoin-cli's rpcconnect says where clients should connect; otherwise use the bind address
+    let (bind_host, bind_port) = parsed
+        .get(network, "rpcbind")
+        .map(split_host_port)
+        .unwrap_or((None, None));
+    let host = parsed
+        .get(network, "rpcconnect")
+        .map(|value| split_host_port(value).0.unwrap_or_else(|| value.to_string()))
+        .or(bind_host)
+        .filter(|host| host != "0.0.0.0" && host != "::")
+        .unwrap_or_else(|| "127.0.0.1".to_string());
+    let port = match parsed.get(network, "rpcport") {
+        Some(port) => port.parse().unwrap_or_else(|_| {
+            warnings.push(format!("Invalid rpcport '{}', using the default", port));
+            network.rpc_port
+        }),
+        None => bind_port.unwrap_or(network.rpc_port),
+    };
+    let rpc_url = if host.contains(':') {
+        format!("http://[{}]:{}", host, port)
+    } else {
+        format!("http://{}:{}", host, port)
+    };
+
+    let (rpc_user, rpc_password, auth_source) =
+        match (parsed.get(network, "rpcuser"), parsed.get(network, "rpcpassword")) {
+            (Some(user), Some(password)) => (user.to_string(), password.to_string(), RpcAuthSource::Password),
+            _ => {
+                if parsed.get(network, "rpcauth").is_some() {
+                    warnings.push(
+                        "rpcauth only stores a salted hash; enter the matching password manually".to_string(),
+                    );
+                }
+                match read_cookie(parsed, network, datadir).await {
+                    Ok((user, password)) => {
+                        warnings.push("The cookie changes every time the node restarts".to_string());
+                        (user, password, RpcAuthSource::Cookie)
+                    }
+                    Err(reason) => {
+                        warnings.push(reason);
+                        (String::new(), String::new(), RpcAuthSource::Missing)
+                    }
+                }
+            }
+        };
+
+    ProposedNodeConfig {
+        network: network.chain.to_string(),
+        name: format!("{} ({})", network.chain, rpc_url.trim_start_matches("http://")),
+        rpc_url,
+        rpc_user,
+        rpc_password,
+        auth_source,
+        warnings,
+        node_config_id: None,
+        created: false,
+    }
+}
+
+/// Split `host`, `host:port`, `[v6]:port` or a bare IPv6 address
+fn split_host_port(value: &str) -> (Option<String>, Option<u16>) {
+    if let Some(rest) = value.strip_prefix('[') {
+        let (host, tail) = rest.split_once(']').unwrap_or((rest, ""));
+        let port = tail.strip_prefix(':').and_then(|p| p.parse().ok());
+        return (Some(host.to_string()), port);
+    }
+
+    match value.split_once(':') {
+        // More than one colon: an IPv6 address without a port
+        Some((_, rest)) if rest.contains(':') => (Some(value.to_string()), None),
+        Some((host, port)) => (Some(host.to_string()), port.parse().ok()),
+        None => (Some(value.to_string()), None),
+    }
+}
+
+async fn read_cookie(
+    parsed: &ParsedConf,
+    network: &NetworkDefaults,
+    datadir: Option<&Path>,
+) -> Result<(String, String), String> {
+    let Some(datadir) = datadir else {
+        return Err("No rpcuser/rpcpassword and no datadir to look for a cookie".to_string());
+    };
+
+    let network_dir = datadir.join(network.subdir);
+    let path = match parsed.get(network, "rpccookiefile") {
+        Some(file) => network_dir.join(file),
+        None => network_dir.join(".cookie"),
+    };
+
+    let content = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("No rpcuser/rpcpassword and cookie {} is unreadable: {}", path.display(), e))?;
+
+    content
+        .trim()
+        .split_once(':')
+        .map(|(user, password)| (user.to_string(), password.to_string()))
+        .ok_or_else(|| format!("Cookie {} is malformed", path.display()))
+}
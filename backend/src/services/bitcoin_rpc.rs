@@ -2,9 +2,16 @@ use base64::Engine;
 use reqwest::{Client, header};
 use serde::Deserialize;
 use serde_json::json;
+use std::collections::BTreeMap;
 
+use crate::db::NodeConfig;
+use crate::domain::network::{
+    BannedPeer, ConnectionCounts, LocalAddress, NetworkInfo, NetworkReachability, PeerInfo,
+    PeerServices, PeerSync, classify_peer_network,
+};
 use crate::domain::node::NodeInfo;
 use crate::errors::AppError;
+use crate::utils::amount::sats_to_btc;
 
 pub struct BitcoinRpc {
     url: String,
@@ -17,6 +24,13 @@ struct RpcResponse<T> {
     error: Option<RpcError>,
 }
 
+#[derive(Deserialize, Debug)]
+struct RpcBatchResponse<T> {
+    id: u64,
+    result: Option<T>,
+    error: Option<RpcError>,
+}
+
 #[derive(Deserialize, Debug)]
 struct RpcError {
     code: i32,
@@ -32,7 +46,467 @@ pub struct BlockchainInfo {
     pub difficulty: f64,
     pub verificationprogress: f64,
     pub pruned: bool,
+    /// Lowest height with block and undo data; only present when pruned
+    pub pruneheight: Option<u64>,
     pub initialblockdownload: bool,
+    /// Removed in Core 23 in favour of `getdeploymentinfo`
+    pub softforks: Option<BTreeMap<String, DeploymentRaw>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DeploymentInfoRaw {
+    pub hash: String,
+    pub height: u64,
+    pub deployments: BTreeMap<String, DeploymentRaw>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DeploymentRaw {
+    #[serde(rename = "type")]
+    pub deployment_type: String,
+    pub active: bool,
+    pub height: Option<u64>,
+    pub bip9: Option<Bip9Raw>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Bip9Raw {
+    pub bit: Option<u8>,
+    pub start_time: i64,
+    pub timeout: i64,
+    /// Added in Core 22
+    pub min_activation_height: Option<u64>,
+    pub status: String,
+    pub since: u64,
+    /// Added in Core 23
+    pub status_next: Option<String>,
+    pub statistics: Option<Bip9StatisticsRaw>,
+    pub signalling: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Bip9StatisticsRaw {
+    pub period: u64,
+    pub threshold: Option<u64>,
+    pub elapsed: u64,
+    pub count: u64,
+    pub possible: Option<bool>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct MempoolInfoRaw {
+    pub size: u64,
+    pub bytes: u64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct NetTotalsRaw {
+    pub totalbytesrecv: u64,
+    pub totalbytessent: u64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PeerInfoRaw {
+    pub id: i64,
+    pub addr: String,
+    /// Not reported by Bitcoin Core before v0.21
+    pub network: Option<String>,
+    pub services: String,
+    #[serde(default)]
+    pub servicesnames: Vec<String>,
+    pub inbound: bool,
+    pub connection_type: Option<String>,
+    pub pingtime: Option<f64>,
+    pub minping: Option<f64>,
+    pub bytessent: u64,
+    pub bytesrecv: u64,
+    pub conntime: i64,
+    pub version: i64,
+    pub subver: String,
+    pub startingheight: Option<i64>,
+    pub synced_headers: Option<i64>,
+    pub synced_blocks: Option<i64>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct NetworkInfoRaw {
+    pub version: u64,
+    pub subversion: String,
+    pub protocolversion: u64,
+    pub localservices: String,
+    #[serde(default)]
+    pub localservicesnames: Vec<String>,
+    pub localrelay: bool,
+    pub timeoffset: i64,
+    pub networkactive: bool,
+    pub connections: u64,
+    pub connections_in: Option<u64>,
+    pub connections_out: Option<u64>,
+    pub networks: Vec<NetworkReachabilityRaw>,
+    pub relayfee: f64,
+    pub incrementalfee: f64,
+    pub localaddresses: Vec<LocalAddressRaw>,
+    /// A string before Bitcoin Core v28, an array of strings afterwards
+    pub warnings: serde_json::Value,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct NetworkReachabilityRaw {
+    pub name: String,
+    pub limited: bool,
+    pub reachable: bool,
+    pub proxy: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct LocalAddressRaw {
+    pub address: String,
+    pub port: u16,
+    pub score: i64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BannedEntryRaw {
+    pub address: String,
+    pub ban_created: i64,
+    pub banned_until: i64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct BlockHeaderRaw {
+    pub hash: String,
+    /// -1 when the block is not on the active chain
+    pub confirmations: i64,
+    pub height: u64,
+    pub version: i64,
+    pub merkleroot: String,
+    pub time: i64,
+    pub mediantime: i64,
+    pub nonce: u64,
+    pub bits: String,
+    pub difficulty: f64,
+    pub chainwork: String,
+    #[serde(rename = "nTx")]
+    pub n_tx: u64,
+    pub previousblockhash: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ChainTipRaw {
+    pub height: u64,
+    pub hash: String,
+    pub branchlen: u64,
+    pub status: String,
+}
+
+/// `getblock` with verbosity 2
+#[derive(Deserialize, Debug)]
+pub struct BlockVerboseRaw {
+    pub hash: String,
+    pub height: u64,
+    pub previousblockhash: Option<String>,
+    pub tx: Vec<TransactionRaw>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TransactionRaw {
+    pub txid: String,
+    pub vin: Vec<TxInputRaw>,
+    pub vout: Vec<TxOutputRaw>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TxInputRaw {
+    /// Absent for coinbase inputs
+    pub txid: Option<String>,
+    pub vout: Option<u32>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TxOutputRaw {
+    pub value: f64,
+    pub n: u32,
+    #[serde(rename = "scriptPubKey")]
+    pub script_pub_key: ScriptPubKeyRaw,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ScriptPubKeyRaw {
+    pub hex: String,
+    /// Only present for standard scripts
+    pub address: Option<String>,
+    #[serde(rename = "type")]
+    pub script_type: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BlockStatsRaw {
+    pub blockhash: String,
+    pub height: u64,
+    pub time: i64,
+    pub txs: u64,
+    pub totalfee: u64,
+    pub subsidy: u64,
+    pub avgfeerate: f64,
+    /// 10th, 25th, 50th, 75th and 90th percentile feerates in sat/vB
+    pub feerate_percentiles: Vec<f64>,
+    pub total_size: u64,
+    pub total_weight: u64,
+    pub swtxs: u64,
+    /// Inputs excluding the coinbase
+    pub ins: u64,
+}
+
+/// `getblock` with verbosity 3, reduced to what input classification needs.
+/// Nodes before Core 23 answer as for verbosity 2, without `prevout`.
+#[derive(Deserialize, Debug)]
+pub struct BlockInputsRaw {
+    pub tx: Vec<TxInputsRaw>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TxInputsRaw {
+    pub vin: Vec<InputWithPrevoutRaw>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct InputWithPrevoutRaw {
+    pub coinbase: Option<String>,
+    #[serde(default)]
+    pub txinwitness: Vec<String>,
+    pub prevout: Option<PrevoutRaw>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PrevoutRaw {
+    #[serde(rename = "scriptPubKey")]
+    pub script_pub_key: ScriptPubKeyRaw,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TxOutSetInfoRaw {
+    pub height: u64,
+    pub bestblock: String,
+    pub txouts: u64,
+    pub bogosize: u64,
+    pub hash_serialized_3: Option<String>,
+    pub muhash: Option<String>,
+    pub total_amount: f64,
+    pub transactions: Option<u64>,
+    pub disk_size: Option<u64>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct IndexInfoRaw {
+    pub synced: bool,
+    pub best_block_height: u64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ValidateAddressRaw {
+    pub isvalid: bool,
+    #[serde(rename = "scriptPubKey")]
+    pub script_pub_key: Option<String>,
+    #[serde(default)]
+    pub isscript: bool,
+    pub witness_version: Option<u32>,
+}
+
+/// `getaddressinfo` of a wallet-scoped client, reduced to ownership
+#[derive(Deserialize, Debug)]
+pub struct AddressInfoRaw {
+    pub ismine: bool,
+    /// Descriptor with key origin, for addresses the wallet can solve
+    pub desc: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DescriptorInfoRaw {
+    pub descriptor: String,
+    pub checksum: String,
+    pub isrange: bool,
+    pub issolvable: bool,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RawTransactionRaw {
+    pub txid: String,
+    pub blockhash: Option<String>,
+    /// Absent while the transaction is in the mempool
+    pub confirmations: Option<u64>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ScanTxOutSetResultRaw {
+    pub success: bool,
+    pub txouts: u64,
+    pub height: u64,
+    pub bestblock: String,
+    pub unspents: Vec<ScannedUtxoRaw>,
+    pub total_amount: f64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ScannedUtxoRaw {
+    pub txid: String,
+    pub vout: u32,
+    #[serde(rename = "scriptPubKey")]
+    pub script_pub_key: String,
+    pub desc: String,
+    pub amount: f64,
+    /// Not reported by Bitcoin Core before v25
+    pub coinbase: Option<bool>,
+    pub height: u64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ScanProgressRaw {
+    pub progress: f64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GenerateBlockRaw {
+    pub hash: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct WalletBalancesRaw {
+    pub mine: BalanceBucketsRaw,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BalanceBucketsRaw {
+    pub trusted: f64,
+    pub untrusted_pending: f64,
+    pub immature: f64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UnspentRaw {
+    pub txid: String,
+    pub vout: u32,
+    pub address: Option<String>,
+    #[serde(rename = "scriptPubKey")]
+    pub script_pub_key: String,
+    pub amount: f64,
+    pub confirmations: u32,
+    /// Descriptor with key origin, e.g. wpkh([73c5da0a/84h/0h/0h/0/5]02...)
+    pub desc: Option<String>,
+    /// False for unconfirmed outputs of transactions we did not fund
+    pub safe: bool,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct WalletInfoRaw {
+    /// Added in Core 0.21; `false` while no rescan is running
+    #[serde(default)]
+    pub scanning: Option<WalletScanningRaw>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum WalletScanningRaw {
+    Active { duration: u64, progress: f64 },
+    /// `false`
+    Idle(serde::de::IgnoredAny),
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ImportDescriptorResultRaw {
+    pub success: bool,
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    pub error: Option<ImportDescriptorErrorRaw>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ImportDescriptorErrorRaw {
+    pub message: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ListSinceBlockRaw {
+    pub transactions: Vec<SinceBlockEntryRaw>,
+    /// Transactions of blocks disconnected since the given block
+    #[serde(default)]
+    pub removed: Vec<SinceBlockEntryRaw>,
+    pub lastblock: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SinceBlockEntryRaw {
+    pub txid: String,
+    /// send, receive, generate, immature or orphan
+    pub category: String,
+    /// Recipient for sends, our own address otherwise
+    pub address: Option<String>,
+}
+
+/// `gettransaction` with `verbose`, reduced to what the history needs
+#[derive(Deserialize, Debug)]
+pub struct WalletTransactionDetailRaw {
+    pub txid: String,
+    /// Net amount excluding the fee
+    pub amount: f64,
+    /// Negative; only present when the wallet funded the inputs
+    pub fee: Option<f64>,
+    /// Negative when the transaction conflicts with the active chain
+    pub confirmations: i64,
+    pub blockhash: Option<String>,
+    pub blockheight: Option<u64>,
+    pub blocktime: Option<i64>,
+    pub timereceived: i64,
+    #[serde(rename = "bip125-replaceable")]
+    pub bip125_replaceable: Option<String>,
+    pub replaced_by_txid: Option<String>,
+    pub replaces_txid: Option<String>,
+    #[serde(default)]
+    pub details: Vec<WalletTxDetailRaw>,
+    pub decoded: TransactionRaw,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct WalletTxDetailRaw {
+    pub category: String,
+    pub vout: u32,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BumpFeeRaw {
+    pub txid: String,
+    pub origfee: f64,
+    pub fee: f64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct WalletTransactionRaw {
+    pub txid: String,
+    /// Negative when the transaction conflicts with the active chain
+    pub confirmations: i64,
+    /// Serialized transaction
+    pub hex: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct MempoolEntryRaw {
+    pub vsize: u64,
+    pub fees: MempoolEntryFeesRaw,
+}
+
+/// Fees in BTC
+#[derive(Deserialize, Debug)]
+pub struct MempoolEntryFeesRaw {
+    pub base: f64,
+    /// The transaction and its in-mempool descendants
+    pub descendant: f64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct MempoolAcceptRaw {
+    pub allowed: bool,
+    /// Why the mempool would reject the transaction
+    #[serde(rename = "reject-reason")]
+    pub reject_reason: Option<String>,
 }
 
 impl BitcoinRpc {
@@ -63,6 +537,24 @@ impl BitcoinRpc {
         Self { url, client }
     }
 
+    /// Create a client whose calls are scoped to one loaded node wallet
+    /// (`<url>/wallet/<name>`), sharing the same connection pool
+    pub fn for_wallet(&self, wallet: &str) -> Self {
+        Self {
+            url: format!("{}/wallet/{}", self.url.trim_end_matches('/'), wallet),
+            client: self.client.clone(),
+        }
+    }
+
+    /// Create a client for a stored node configuration
+    pub fn from_node_config(config: &NodeConfig) -> Self {
+        Self::new(
+            config.rpc_url.clone(),
+            config.rpc_user.clone(),
+            config.rpc_password.clone(),
+        )
+    }
+
     pub async fn get_blockchain_info(&self) -> Result<BlockchainInfo, AppError> {
         self.get_rpc_request("getblockchaininfo", vec![]).await
     }
@@ -101,7 +593,551 @@ impl BitcoinRpc {
         })
     }
 
+    /// `getnetworkhashps` over each window of blocks ending at the tip, in one batched request
+    pub async fn get_network_hashps(&self, windows: &[u64]) -> Result<Vec<f64>, AppError> {
+        let params = windows.iter().map(|w| vec![json!(w)]).collect();
+        self.batch_rpc_request("getnetworkhashps", params).await
+    }
+
+    /// Deployment state at `block_hash`, or at the tip when `None` (Core 23+)
+    pub async fn get_deployment_info(&self, block_hash: Option<&str>) -> Result<DeploymentInfoRaw, AppError> {
+        let params = block_hash.map(|hash| vec![json!(hash)]).unwrap_or_default();
+        self.get_rpc_request("getdeploymentinfo", params).await
+    }
+
+    /// `gettxoutsetinfo`; `height` requires coinstatsindex and is answered from it
+    pub async fn get_tx_out_set_info(&self, hash_type: &str, height: Option<u64>) -> Result<TxOutSetInfoRaw, AppError> {
+        let mut params = vec![json!(hash_type)];
+        if let Some(height) = height {
+            params.extend([json!(height), json!(true)]);
+        }
+        self.get_rpc_request("gettxoutsetinfo", params).await
+    }
+
+    /// Status of the node's optional indexes, keyed by index name
+    pub async fn get_index_info(&self) -> Result<BTreeMap<String, IndexInfoRaw>, AppError> {
+        self.get_rpc_request("getindexinfo", vec![]).await
+    }
+
+    pub async fn get_mempool_info(&self) -> Result<MempoolInfoRaw, AppError> {
+        self.get_rpc_request("getmempoolinfo", vec![]).await
+    }
+
+    pub async fn get_net_totals(&self) -> Result<NetTotalsRaw, AppError> {
+        self.get_rpc_request("getnettotals", vec![]).await
+    }
+
+    pub async fn get_connection_count(&self) -> Result<u64, AppError> {
+        self.get_rpc_request("getconnectioncount", vec![]).await
+    }
+
+    pub async fn get_best_block_hash(&self) -> Result<String, AppError> {
+        self.get_rpc_request("getbestblockhash", vec![]).await
+    }
+
+    pub async fn get_block_hash(&self, height: u64) -> Result<String, AppError> {
+        self.get_rpc_request("getblockhash", vec![json!(height)]).await
+    }
+
+    /// Fetch block hashes for many heights in a single batched request
+    pub async fn get_block_hashes(&self, heights: &[u64]) -> Result<Vec<String>, AppError> {
+        let params = heights.iter().map(|h| vec![json!(h)]).collect();
+        self.batch_rpc_request("getblockhash", params).await
+    }
+
+    /// Fetch headers for many block hashes in a single batched request
+    pub async fn get_block_headers(&self, hashes: &[String]) -> Result<Vec<BlockHeaderRaw>, AppError> {
+        let params = hashes.iter().map(|h| vec![json!(h), json!(true)]).collect();
+        self.batch_rpc_request("getblockheader", params).await
+    }
+
+    pub async fn get_block_header(&self, hash: &str) -> Result<BlockHeaderRaw, AppError> {
+        self.get_rpc_request("getblockheader", vec![json!(hash), json!(true)])
+            .await
+    }
+
+    /// Get a block with fully decoded transactions (`getblock` verbosity 2)
+    pub async fn get_block_verbose(&self, hash: &str) -> Result<BlockVerboseRaw, AppError> {
+        self.get_rpc_request("getblock", vec![json!(hash), json!(2)]).await
+    }
+
+    /// Fetch `getblockstats` for many block hashes in a single batched request
+    pub async fn get_block_stats(&self, hashes: &[String]) -> Result<Vec<BlockStatsRaw>, AppError> {
+        let params = hashes.iter().map(|h| vec![json!(h)]).collect();
+        self.batch_rpc_request("getblockstats", params).await
+    }
+
+    pub async fn get_block_inputs(&self, hash: &str) -> Result<BlockInputsRaw, AppError> {
+        self.get_rpc_request("getblock", vec![json!(hash), json!(3)]).await
+    }
+
+    pub async fn validate_address(&self, address: &str) -> Result<ValidateAddressRaw, AppError> {
+        self.get_rpc_request("validateaddress", vec![json!(address)]).await
+    }
+
+    pub async fn get_descriptor_info(&self, descriptor: &str) -> Result<DescriptorInfoRaw, AppError> {
+        self.get_rpc_request("getdescriptorinfo", vec![json!(descriptor)]).await
+    }
+
+    /// Decoded transaction from the mempool, or from the chain when txindex is enabled
+    pub async fn get_raw_transaction(&self, txid: &str) -> Result<RawTransactionRaw, AppError> {
+        self.get_rpc_request("getrawtransaction", vec![json!(txid), json!(true)]).await
+    }
+
+    /// Start a UTXO set scan; blocks until the scan finishes or is aborted.
+    /// Each scan object is either a descriptor string or `{"desc": ..., "range": ...}`.
+    pub async fn scan_tx_out_set_start(
+        &self,
+        scan_objects: Vec<serde_json::Value>,
+    ) -> Result<ScanTxOutSetResultRaw, AppError> {
+        self.get_rpc_request("scantxoutset", vec![json!("start"), json!(scan_objects)])
+            .await
+    }
+
+    /// Progress of the running scan, `None` when no scan is in progress
+    pub async fn scan_tx_out_set_status(&self) -> Result<Option<ScanProgressRaw>, AppError> {
+        self.send_rpc_request("scantxoutset", vec![json!("status")])
+            .await
+    }
+
+    /// Abort the running scan; returns false when there was nothing to abort
+    pub async fn scan_tx_out_set_abort(&self) -> Result<bool, AppError> {
+        self.get_rpc_request("scantxoutset", vec![json!("abort")]).await
+    }
+
+    /// Mine blocks to an address (regtest); returns the new block hashes
+    pub async fn generate_to_address(&self, blocks: u64, address: &str) -> Result<Vec<String>, AppError> {
+        self.get_rpc_request("generatetoaddress", vec![json!(blocks), json!(address)])
+            .await
+    }
+
+    /// Mine one block containing exactly the given txids or raw transactions (regtest)
+    pub async fn generate_block(&self, output: &str, transactions: &[String]) -> Result<GenerateBlockRaw, AppError> {
+        self.get_rpc_request("generateblock", vec![json!(output), json!(transactions)])
+            .await
+    }
+
+    pub async fn invalidate_block(&self, hash: &str) -> Result<(), AppError> {
+        self.execute_rpc_command("invalidateblock", vec![json!(hash)])
+            .await
+    }
+
+    pub async fn reconsider_block(&self, hash: &str) -> Result<(), AppError> {
+        self.execute_rpc_command("reconsiderblock", vec![json!(hash)])
+            .await
+    }
+
+    /// Set the node's clock (regtest only); 0 returns to system time
+    pub async fn set_mock_time(&self, timestamp: i64) -> Result<(), AppError> {
+        self.execute_rpc_command("setmocktime", vec![json!(timestamp)])
+            .await
+    }
+
+    /// Get a fresh receiving address from the node's loaded wallet
+    pub async fn get_new_address(&self) -> Result<String, AppError> {
+        self.get_rpc_request("getnewaddress", vec![]).await
+    }
+
+    /// Send from the node's loaded wallet; returns the txid.
+    /// `replaceable` overrides the wallet's BIP125 default when set.
+    pub async fn send_to_address(
+        &self,
+        address: &str,
+        amount_sat: u64,
+        replaceable: Option<bool>,
+    ) -> Result<String, AppError> {
+        let mut params = vec![json!(address), json!(sats_to_btc(amount_sat))];
+        if let Some(replaceable) = replaceable {
+            params.extend([json!(""), json!(""), json!(false), json!(replaceable)]);
+        }
+
+        self.get_rpc_request("sendtoaddress", params).await
+    }
+
+    pub async fn list_wallets(&self) -> Result<Vec<String>, AppError> {
+        self.get_rpc_request("listwallets", vec![]).await
+    }
+
+    pub async fn load_wallet(&self, name: &str) -> Result<(), AppError> {
+        self.execute_rpc_command("loadwallet", vec![json!(name)])
+            .await
+    }
+
+    /// Create a descriptor wallet on the node (asked for explicitly, since
+    /// Core before v23 creates legacy wallets by default)
+    pub async fn create_wallet(
+        &self,
+        name: &str,
+        disable_private_keys: bool,
+        blank: bool,
+    ) -> Result<(), AppError> {
+        self.execute_rpc_command(
+            "createwallet",
+            vec![
+                json!(name),
+                json!(disable_private_keys),
+                json!(blank),
+                json!(""),
+                json!(false),
+                json!(true),
+            ],
+        )
+        .await
+    }
+
+    /// Wallet info of a wallet-scoped client, including rescan progress
+    pub async fn get_wallet_info(&self) -> Result<WalletInfoRaw, AppError> {
+        self.get_rpc_request("getwalletinfo", vec![]).await
+    }
+
+    /// Import descriptors into a wallet-scoped client. Blocks until the
+    /// rescan from the oldest timestamp has finished.
+    pub async fn import_descriptors(
+        &self,
+        requests: Vec<serde_json::Value>,
+    ) -> Result<Vec<ImportDescriptorResultRaw>, AppError> {
+        self.get_rpc_request("importdescriptors", vec![json!(requests)])
+            .await
+    }
+
+    /// Stop the rescan of a wallet-scoped client; false when none was running
+    pub async fn abort_rescan(&self) -> Result<bool, AppError> {
+        self.get_rpc_request("abortrescan", vec![]).await
+    }
+
+    /// Balances of a wallet-scoped client
+    pub async fn get_balances(&self) -> Result<WalletBalancesRaw, AppError> {
+        self.get_rpc_request("getbalances", vec![]).await
+    }
+
+    /// Wallet transactions in blocks after `blockhash` (all when None) and
+    /// in the mempool, plus those of blocks disconnected since
+    pub async fn list_since_block(&self, blockhash: Option<&str>) -> Result<ListSinceBlockRaw, AppError> {
+        self.get_rpc_request(
+            "listsinceblock",
+            vec![json!(blockhash), json!(1), json!(true), json!(true)],
+        )
+        .await
+    }
+
+    /// Every output the wallet received, change included (Core 24+), with
+    /// the address it paid
+    pub async fn list_received_outputs(&self) -> Result<Vec<SinceBlockEntryRaw>, AppError> {
+        let listed: ListSinceBlockRaw = self
+            .get_rpc_request(
+                "listsinceblock",
+                vec![json!(null), json!(1), json!(true), json!(false), json!(true)],
+            )
+            .await?;
+        Ok(listed
+            .transactions
+            .into_iter()
+            .filter(|entry| entry.category != "send")
+            .collect())
+    }
+
+    /// A wallet transaction with its decoded inputs and outputs
+    pub async fn get_wallet_transaction_detail(&self, txid: &str) -> Result<WalletTransactionDetailRaw, AppError> {
+        self.get_rpc_request("gettransaction", vec![json!(txid), json!(true), json!(true)])
+            .await
+    }
+
+    /// Inputs of a transaction with their prevouts (Core 25+). Without
+    /// -txindex the transaction must be in the mempool or in `blockhash`.
+    pub async fn get_transaction_inputs(&self, txid: &str, blockhash: Option<&str>) -> Result<TxInputsRaw, AppError> {
+        let mut params = vec![json!(txid), json!(2)];
+        if let Some(blockhash) = blockhash {
+            params.push(json!(blockhash));
+        }
+        self.get_rpc_request("getrawtransaction", params).await
+    }
+
+    /// Unspent outputs of a wallet-scoped client with at least `minconf`
+    /// confirmations, including unconfirmed ones from third parties
+    pub async fn list_unspent(&self, minconf: u32) -> Result<Vec<UnspentRaw>, AppError> {
+        self.get_rpc_request(
+            "listunspent",
+            vec![json!(minconf), json!(9_999_999), json!([]), json!(true)],
+        )
+        .await
+    }
+
+    /// Whether a wallet-scoped client owns an address, and its descriptor
+    pub async fn get_address_info(&self, address: &str) -> Result<AddressInfoRaw, AppError> {
+        self.get_rpc_request("getaddressinfo", vec![json!(address)]).await
+    }
+
+    /// Bump the fee of a wallet transaction via BIP125 replacement
+    pub async fn bump_fee(&self, txid: &str) -> Result<BumpFeeRaw, AppError> {
+        self.get_rpc_request("bumpfee", vec![json!(txid)]).await
+    }
+
+    pub async fn get_wallet_transaction(&self, txid: &str) -> Result<WalletTransactionRaw, AppError> {
+        self.get_rpc_request("gettransaction", vec![json!(txid)]).await
+    }
+
+    pub async fn get_mempool_entry(&self, txid: &str) -> Result<MempoolEntryRaw, AppError> {
+        self.get_rpc_request("getmempoolentry", vec![json!(txid)]).await
+    }
+
+    /// Whether the mempool would accept a raw transaction, without broadcasting it
+    pub async fn test_mempool_accept(&self, tx_hex: &str) -> Result<MempoolAcceptRaw, AppError> {
+        let results: Vec<MempoolAcceptRaw> = self
+            .get_rpc_request("testmempoolaccept", vec![json!([tx_hex])])
+            .await?;
+        results.into_iter().next().ok_or(AppError::BitcoinRpcNoResult)
+    }
+
+    /// Submit a raw transaction to the mempool and relay it; returns the txid
+    pub async fn send_raw_transaction(&self, tx_hex: &str) -> Result<String, AppError> {
+        self.get_rpc_request("sendrawtransaction", vec![json!(tx_hex)]).await
+    }
+
+    /// Ask the node to shut down; it exits shortly after replying
+    pub async fn stop(&self) -> Result<(), AppError> {
+        self.execute_rpc_command("stop", vec![]).await
+    }
+
+    pub async fn get_chain_tips(&self) -> Result<Vec<ChainTipRaw>, AppError> {
+        self.get_rpc_request("getchaintips", vec![]).await
+    }
+
+    /// Get connected peers normalized for the API
+    pub async fn get_peers(&self) -> Result<Vec<PeerInfo>, AppError> {
+        let peers = self.get_peer_info().await?;
+
+        Ok(peers
+            .into_iter()
+            .map(|peer| PeerInfo {
+                network: classify_peer_network(&peer.addr, peer.network.as_deref()),
+                direction: if peer.inbound { "inbound" } else { "outbound" },
+                connection_type: peer.connection_type.unwrap_or_else(|| {
+                    if peer.inbound { "inbound" } else { "outbound-full-relay" }.to_string()
+                }),
+                id: peer.id,
+                address: peer.addr,
+                services: PeerServices {
+                    hex: peer.services,
+                    names: peer.servicesnames,
+                },
+                ping_ms: peer.pingtime.map(|t| t * 1000.0),
+                min_ping_ms: peer.minping.map(|t| t * 1000.0),
+                bytes_sent: peer.bytessent,
+                bytes_received: peer.bytesrecv,
+                sync: PeerSync {
+                    starting_height: peer.startingheight,
+                    synced_headers: peer.synced_headers,
+                    synced_blocks: peer.synced_blocks,
+                },
+                version: peer.version,
+                user_agent: peer.subver,
+                connected_since: peer.conntime,
+            })
+            .collect())
+    }
+
+    /// Get network state normalized for the API
+    pub async fn get_network_summary(&self) -> Result<NetworkInfo, AppError> {
+        let info = self.get_network_info().await?;
+
+        let warnings = match info.warnings {
+            serde_json::Value::String(w) if w.is_empty() => vec![],
+            serde_json::Value::String(w) => vec![w],
+            serde_json::Value::Array(list) => list
+                .into_iter()
+                .filter_map(|w| w.as_str().map(str::to_string))
+                .collect(),
+            _ => vec![],
+        };
+
+        Ok(NetworkInfo {
+            version: info.version,
+            user_agent: info.subversion,
+            protocol_version: info.protocolversion,
+            local_services: PeerServices {
+                hex: info.localservices,
+                names: info.localservicesnames,
+            },
+            local_relay: info.localrelay,
+            time_offset: info.timeoffset,
+            network_active: info.networkactive,
+            connections: ConnectionCounts {
+                total: info.connections,
+                inbound: info.connections_in,
+                outbound: info.connections_out,
+            },
+            networks: info
+                .networks
+                .into_iter()
+                .map(|n| NetworkReachability {
+                    name: n.name,
+                    limited: n.limited,
+                    reachable: n.reachable,
+                    proxy: n.proxy,
+                })
+                .collect(),
+            relay_fee: info.relayfee,
+            incremental_fee: info.incrementalfee,
+            local_addresses: info
+                .localaddresses
+                .into_iter()
+                .map(|a| LocalAddress {
+                    address: a.address,
+                    port: a.port,
+                    score: a.score,
+                })
+                .collect(),
+            warnings,
+        })
+    }
+
+    /// Get banned subnets normalized for the API
+    pub async fn get_banned_peers(&self) -> Result<Vec<BannedPeer>, AppError> {
+        let banned = self.list_banned().await?;
+
+        Ok(banned
+            .into_iter()
+            .map(|b| BannedPeer {
+                subnet: b.address,
+                banned_at: b.ban_created,
+                banned_until: b.banned_until,
+            })
+            .collect())
+    }
+
+    pub async fn get_peer_info(&self) -> Result<Vec<PeerInfoRaw>, AppError> {
+        self.get_rpc_request("getpeerinfo", vec![]).await
+    }
+
+    pub async fn get_network_info(&self) -> Result<NetworkInfoRaw, AppError> {
+        self.get_rpc_request("getnetworkinfo", vec![]).await
+    }
+
+    /// `command` is one of `add`, `remove` or `onetry`
+    pub async fn add_node(&self, address: &str, command: &str) -> Result<(), AppError> {
+        self.execute_rpc_command("addnode", vec![json!(address), json!(command)])
+            .await
+    }
+
+    pub async fn disconnect_node_by_address(&self, address: &str) -> Result<(), AppError> {
+        self.execute_rpc_command("disconnectnode", vec![json!(address)])
+            .await
+    }
+
+    pub async fn disconnect_node_by_id(&self, node_id: i64) -> Result<(), AppError> {
+        self.execute_rpc_command("disconnectnode", vec![json!(""), json!(node_id)])
+            .await
+    }
+
+    /// `command` is either `add` or `remove`
+    pub async fn set_ban(
+        &self,
+        subnet: &str,
+        command: &str,
+        bantime: Option<i64>,
+        absolute: bool,
+    ) -> Result<(), AppError> {
+        self.execute_rpc_command(
+            "setban",
+            vec![json!(subnet), json!(command), json!(bantime.unwrap_or(0)), json!(absolute)],
+        )
+        .await
+    }
+
+    pub async fn list_banned(&self) -> Result<Vec<BannedEntryRaw>, AppError> {
+        self.get_rpc_request("listbanned", vec![]).await
+    }
+
+    pub async fn clear_banned(&self) -> Result<(), AppError> {
+        self.execute_rpc_command("clearbanned", vec![]).await
+    }
+
+    /// Returns the resulting network state
+    pub async fn set_network_active(&self, active: bool) -> Result<bool, AppError> {
+        self.get_rpc_request("setnetworkactive", vec![json!(active)]).await
+    }
+
     async fn get_rpc_request<T>(&self, method: &str, params: Vec<serde_json::Value>) -> Result<T, AppError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        self.send_rpc_request(method, params)
+            .await?
+            .ok_or(AppError::BitcoinRpcNoResult)
+    }
+
+    /// Execute an RPC command that returns `null` on success (e.g. `addnode`, `setban`)
+    async fn execute_rpc_command(&self, method: &str, params: Vec<serde_json::Value>) -> Result<(), AppError> {
+        self.send_rpc_request::<serde_json::Value>(method, params)
+            .await
+            .map(|_| ())
+    }
+
+    /// Send one JSON-RPC batch calling `method` once per params entry.
+    /// Results are returned in the same order as `params_list`.
+    async fn batch_rpc_request<T>(
+        &self,
+        method: &str,
+        params_list: Vec<Vec<serde_json::Value>>,
+    ) -> Result<Vec<T>, AppError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        if params_list.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let payload: Vec<serde_json::Value> = params_list
+            .into_iter()
+            .enumerate()
+            .map(|(id, params)| {
+                json!({
+                    "jsonrpc": "1.0",
+                    "id": id,
+                    "method": method,
+                    "params": params
+                })
+            })
+            .collect();
+        let expected = payload.len();
+
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| AppError::BitcoinRpcConnection(e.to_string()))?;
+
+        let mut responses: Vec<RpcBatchResponse<T>> = response
+            .json()
+            .await
+            .map_err(|e| AppError::BitcoinRpcParse(e.to_string()))?;
+
+        if responses.len() != expected {
+            return Err(AppError::BitcoinRpcParse(format!(
+                "Batch returned {} results for {} requests",
+                responses.len(),
+                expected
+            )));
+        }
+        responses.sort_by_key(|r| r.id);
+
+        responses
+            .into_iter()
+            .map(|r| {
+                if let Some(error) = r.error {
+                    return Err(AppError::BitcoinRpcError {
+                        code: error.code,
+                        message: error.message,
+                    });
+                }
+                r.result.ok_or(AppError::BitcoinRpcNoResult)
+            })
+            .collect()
+    }
+
+    async fn send_rpc_request<T>(&self, method: &str, params: Vec<serde_json::Value>) -> Result<Option<T>, AppError>
     where
         T: for<'de> Deserialize<'de>,
     {
@@ -132,6 +1168,6 @@ impl BitcoinRpc {
             });
         }
 
-        rpc_response.result.ok_or(AppError::BitcoinRpcNoResult)
+        Ok(rpc_response.result)
     }
 }
@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+
+use crate::db::{BlockStatsRepository, SqliteBlockStatsRepository};
+use crate::domain::block_stats::{BlockStats, BlockStatsSummary, ChainStats, MAX_BLOCK_WEIGHT};
+use crate::errors::AppError;
+use crate::services::bitcoin_rpc::{BitcoinRpc, BlockInputsRaw, BlockStatsRaw};
+use crate::utils::amount::btc_to_sats;
+
+/// Upper bound on blocks per stats request
+pub const MAX_STATS_RANGE: u64 = 500;
+
+/// Blocks per batched `getblockstats` request
+const STATS_BATCH_SIZE: usize = 50;
+
+/// Statistics for an inclusive height range of the node's active chain.
+/// Blocks are looked up by hash, so cached entries never outlive a reorg.
+/// Input types are classified from `getblock` verbosity 3, which is only
+/// fetched for blocks with segwit transactions: without one, no input can
+/// be segwit or taproot.
+pub async fn range_stats(
+    rpc: &BitcoinRpc,
+    repo: &SqliteBlockStatsRepository,
+    from: u64,
+    to: u64,
+) -> Result<ChainStats, AppError> {
+    if from > to {
+        return Err(AppError::BadRequest("from must not be greater than to".to_string()));
+    }
+    if to - from + 1 > MAX_STATS_RANGE {
+        return Err(AppError::BadRequest(format!(
+            "Range may span at most {} blocks",
+            MAX_STATS_RANGE
+        )));
+    }
+    let info = rpc.get_blockchain_info().await?;
+    if to > info.blocks {
+        return Err(AppError::BadRequest(format!(
+            "to ({}) is above the chain tip ({})",
+            to, info.blocks
+        )));
+    }
+    // getblockstats needs the undo data pruning removes
+    if let Some(prune_height) = info.pruneheight.filter(|_| info.pruned)
+        && from < prune_height
+    {
+        return Err(AppError::BadRequest(format!(
+            "Blocks below height {} are pruned on the active node",
+            prune_height
+        )));
+    }
+
+    let heights: Vec<u64> = (from..=to).collect();
+    let hashes = rpc.get_block_hashes(&heights).await?;
+
+    let mut by_hash: HashMap<String, BlockStats> = repo
+        .get_many(&hashes)
+        .await?
+        .into_iter()
+        .map(|stats| (stats.hash.clone(), stats))
+        .collect();
+
+    // The genesis block has no undo data, which getblockstats reads
+    if from == 0 && !by_hash.contains_key(&hashes[0]) {
+        let stats = genesis_stats(rpc, &hashes[0]).await?;
+        repo.store(&stats).await?;
+        by_hash.insert(stats.hash.clone(), stats);
+    }
+
+    let missing: Vec<String> = hashes
+        .iter()
+        .filter(|hash| !by_hash.contains_key(*hash))
+        .cloned()
+        .collect();
+    for chunk in missing.chunks(STATS_BATCH_SIZE) {
+        for raw in rpc.get_block_stats(chunk).await? {
+            let inputs = match raw.swtxs {
+                0 => None,
+                _ => Some(rpc.get_block_inputs(&raw.blockhash).await?),
+            };
+            let stats = block_stats_from_raw(raw, inputs.as_ref());
+            repo.store(&stats).await?;
+            by_hash.insert(stats.hash.clone(), stats);
+        }
+    }
+
+    let blocks: Vec<BlockStats> = hashes
+        .iter()
+        .filter_map(|hash| by_hash.remove(hash))
+        .collect();
+
+    Ok(ChainStats {
+        from,
+        to,
+        summary: summarize(&blocks),
+        blocks,
+    })
+}
+
+fn block_stats_from_raw(raw: BlockStatsRaw, block: Option<&BlockInputsRaw>) -> BlockStats {
+    let mut segwit_inputs = 0;
+    let mut taproot_inputs = Some(0);
+
+    for input in block.iter().flat_map(|b| &b.tx).flat_map(|tx| &tx.vin) {
+        if input.coinbase.is_some() {
+            continue;
+        }
+        if !input.txinwitness.is_empty() {
+            segwit_inputs += 1;
+        }
+        match &input.prevout {
+            Some(prevout) if prevout.script_pub_key.script_type == "witness_v1_taproot" => {
+                taproot_inputs = taproot_inputs.map(|n| n + 1);
+            }
+            Some(_) => {}
+            // Older nodes do not report prevouts
+            None => taproot_inputs = None,
+        }
+    }
+
+    BlockStats {
+        height: raw.height,
+        hash: raw.blockhash,
+        time: raw.time,
+        tx_count: raw.txs,
+        total_fee_sat: raw.totalfee,
+        subsidy_sat: raw.subsidy,
+        median_feerate: raw.feerate_percentiles.get(2).copied().unwrap_or(0.0),
+        avg_feerate: raw.avgfeerate,
+        total_size: raw.total_size,
+        total_weight: raw.total_weight,
+        fullness: raw.total_weight as f64 / MAX_BLOCK_WEIGHT as f64,
+        segwit_tx_count: raw.swtxs,
+        inputs: raw.ins,
+        segwit_inputs,
+        taproot_inputs,
+    }
+}
+
+/// Stats of the genesis block from its header. Its coinbase is its only
+/// transaction and getblockstats leaves coinbases out of the totals.
+async fn genesis_stats(rpc: &BitcoinRpc, hash: &str) -> Result<BlockStats, AppError> {
+    let header = rpc.get_block_header(hash).await?;
+
+    Ok(BlockStats {
+        height: header.height,
+        hash: header.hash,
+        time: header.time,
+        tx_count: header.n_tx,
+        total_fee_sat: 0,
+        subsidy_sat: btc_to_sats(50.0),
+        median_feerate: 0.0,
+        avg_feerate: 0.0,
+        total_size: 0,
+        total_weight: 0,
+        fullness: 0.0,
+        segwit_tx_count: 0,
+        inputs: 0,
+        segwit_inputs: 0,
+        taproot_inputs: Some(0),
+    })
+}
+
+fn summarize(blocks: &[BlockStats]) -> BlockStatsSummary {
+    let count = blocks.len() as u64;
+    let inputs: u64 = blocks.iter().map(|b| b.inputs).sum();
+    let segwit_inputs: u64 = blocks.iter().map(|b| b.segwit_inputs).sum();
+    let taproot_inputs: Option<u64> = blocks.iter().map(|b| b.taproot_inputs).sum();
+
+    let mut medians: Vec<f64> = blocks.iter().map(|b| b.median_feerate).collect();
+    medians.sort_by(f64::total_cmp);
+    let median_feerate = match medians.len() {
+        0 => 0.0,
+        n if n % 2 == 1 => medians[n / 2],
+        n => (medians[n / 2 - 1] + medians[n / 2]) / 2.0,
+    };
+
+    // Core's avgfeerate over the whole range: fees over virtual size, both of
+    // non-coinbase transactions (getblockstats leaves the coinbase out of total_weight)
+    let total_weight: u64 = blocks.iter().map(|b| b.total_weight).sum();
+    let avg_feerate = if total_weight > 0 {
+        blocks.iter().map(|b| b.total_fee_sat).sum::<u64>() as f64 * 4.0 / total_weight as f64
+    } else {
+        0.0
+    };
+
+    let share = |part: u64| (inputs > 0).then(|| part as f64 / inputs as f64);
+
+    BlockStatsSummary {
+        blocks: count,
+        tx_count: blocks.iter().map(|b| b.tx_count).sum(),
+        total_fee_sat: blocks.iter().map(|b| b.total_fee_sat).sum(),
+        total_subsidy_sat: blocks.iter().map(|b| b.subsidy_sat).sum(),
+        median_feerate,
+        avg_feerate,
+        avg_fullness: if count > 0 {
+            blocks.iter().map(|b| b.fullness).sum::<f64>() / count as f64
+        } else {
+            0.0
+        },
+        segwit_input_share: share(segwit_inputs),
+        taproot_input_share: taproot_inputs.and_then(share),
+    }
+}
@@ -0,0 +1,151 @@
+use chrono::Utc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::config::constants::env_keys;
+use crate::config::env::var_or;
+use crate::db::{
+    ChainTipRepository, NodeConfig, NodeConfigRepository, SqliteChainTipRepository,
+    SqliteNodeConfigRepository,
+};
+use crate::domain::chain::{ChainEvent, ChainTip, ReorgEvent, TipObservation};
+use crate::errors::AppError;
+use crate::services::bitcoin_rpc::BitcoinRpc;
+use crate::state::app_state::AppState;
+
+/// Number of best-block observations kept per node
+const TIP_HISTORY_LIMIT: i64 = 1000;
+
+/// Upper bound on how far back we walk when locating a fork point
+const MAX_REORG_WALK: usize = 1000;
+
+/// Spawn the background task that polls every configured node for its best
+/// block and chain tips, recording history and publishing reorg events.
+pub fn spawn(state: AppState) {
+    let interval_secs: u64 = var_or(env_keys::CHAIN_MONITOR_INTERVAL_SECS, 30);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs.max(1)));
+
+        loop {
+            interval.tick().await;
+
+            let nodes = match SqliteNodeConfigRepository::new(state.db_pool.clone()).list().await {
+                Ok(nodes) => nodes,
+                Err(e) => {
+                    eprintln!("⚠️  Chain monitor could not load node configurations: {}", e);
+                    continue;
+                }
+            };
+
+            for node in nodes {
+                if let Err(e) = poll_node(&state, &node).await {
+                    eprintln!("⚠️  Chain monitor failed for node '{}': {}", node.name, e);
+                }
+            }
+        }
+    });
+}
+
+async fn poll_node(state: &AppState, node: &NodeConfig) -> Result<(), AppError> {
+    let rpc = BitcoinRpc::from_node_config(node);
+    let repo = SqliteChainTipRepository::new(state.db_pool.clone());
+
+    let tips: Vec<ChainTip> = rpc
+        .get_chain_tips()
+        .await?
+        .into_iter()
+        .map(|t| ChainTip {
+            height: t.height,
+            hash: t.hash,
+            branch_len: t.branchlen,
+            status: t.status,
+        })
+        .collect();
+    repo.replace_chain_tips(&node.id, &tips).await?;
+
+    let best_hash = rpc.get_best_block_hash().await?;
+    let previous = repo.latest_best_tip(&node.id).await?;
+
+    if previous.as_ref().is_some_and(|p| p.hash == best_hash) {
+        return Ok(());
+    }
+
+    let best_header = rpc.get_block_header(&best_hash).await?;
+    let current = TipObservation {
+        node_id: node.id.clone(),
+        height: best_header.height,
+        hash: best_hash,
+        observed_at: Utc::now().timestamp(),
+    };
+
+    if let Some(previous) = previous
+        && let Some(reorg) = detect_reorg(&rpc, &previous, &current).await?
+    {
+        println!(
+            "🔀 Reorg on node '{}': depth {} at fork height {}",
+            node.name, reorg.depth, reorg.fork_height
+        );
+        repo.record_reorg(&reorg).await?;
+        let _ = state.chain_events.send(ChainEvent::Reorg(reorg));
+    }
+
+    repo.record_best_tip(&current).await?;
+    repo.prune_tip_history(&node.id, TIP_HISTORY_LIMIT).await?;
+    let _ = state.chain_events.send(ChainEvent::NewTip(current));
+
+    Ok(())
+}
+
+/// Walk back from the previously observed tip until we reach a block that is
+/// still on the active chain. Every block passed on the way was orphaned.
+async fn detect_reorg(
+    rpc: &BitcoinRpc,
+    previous: &TipObservation,
+    current: &TipObservation,
+) -> Result<Option<ReorgEvent>, AppError> {
+    let mut header = match rpc.get_block_header(&previous.hash).await {
+        Ok(header) => header,
+        // The node no longer knows the old tip (e.g. the node config now points
+        // at a different chain); there is nothing to compare against.
+        Err(AppError::BitcoinRpcError { code: -5, .. }) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    if header.confirmations >= 0 {
+        return Ok(None);
+    }
+
+    let mut orphaned_hashes = Vec::new();
+    while header.confirmations < 0 {
+        // Too deep to report; the new tip is still recorded so later polls
+        // don't repeat the walk
+        if orphaned_hashes.len() >= MAX_REORG_WALK {
+            eprintln!(
+                "⚠️  Reorg on node {} deeper than {} blocks from {}; not reported",
+                previous.node_id, MAX_REORG_WALK, previous.hash
+            );
+            return Ok(None);
+        }
+        orphaned_hashes.push(header.hash.clone());
+
+        let prev_hash = header.previousblockhash.clone().ok_or_else(|| {
+            AppError::Internal("Walked past genesis while locating fork point".to_string())
+        })?;
+        header = rpc.get_block_header(&prev_hash).await?;
+    }
+
+    Ok(Some(ReorgEvent {
+        id: Uuid::new_v4().to_string(),
+        node_id: previous.node_id.clone(),
+        fork_height: header.height,
+        fork_hash: header.hash,
+        depth: orphaned_hashes.len() as u64,
+        old_tip_height: previous.height,
+        old_tip_hash: previous.hash.clone(),
+        new_tip_height: current.height,
+        new_tip_hash: current.hash.clone(),
+        orphaned_hashes,
+        detected_at: current.observed_at,
+    }))
+}
@@ -0,0 +1,633 @@
+use bitcoin::secp256k1::rand::{Rng, seq::SliceRandom};
+use std::cmp::Reverse;
+
+use crate::domain::coin_selection::SelectionAlgorithm;
+use crate::domain::wallet::ScriptType;
+use crate::errors::AppError;
+
+/// Branch-and-Bound gives up after this many steps and keeps its best match
+const BNB_TOTAL_TRIES: usize = 100_000;
+
+/// Random passes of the knapsack solver's subset search
+const KNAPSACK_ITERATIONS: usize = 1_000;
+
+/// Change the knapsack solver and single random draw aim to leave, as in Core
+const CHANGE_TARGET_SAT: u64 = 50_000;
+
+/// Weight of version, locktime, input and output counts and the segwit
+/// marker. Counts are assumed below 253; an all-legacy spend overpays the
+/// marker by half a vbyte.
+pub const TX_OVERHEAD_WEIGHT: u64 = 42;
+
+/// Weight an input adds to a transaction, signature included. Signatures
+/// are assumed at their 72-byte maximum.
+pub fn input_weight(script_type: ScriptType) -> u64 {
+    match script_type {
+        // outpoint, sequence, scriptSig with signature and key
+        ScriptType::P2pkh => 148 * 4,
+        // scriptSig with the redeem script, witness with signature and key
+        ScriptType::P2shP2wpkh => 64 * 4 + 108,
+        ScriptType::P2wpkh => 41 * 4 + 108,
+        // key-path Schnorr signature
+        ScriptType::P2tr => 41 * 4 + 66,
+    }
+}
+
+/// Weight of an output paying to a scriptPubKey of the given length
+pub fn output_weight(script_pubkey_len: usize) -> u64 {
+    (8 + 1 + script_pubkey_len as u64) * 4
+}
+
+/// Length of the scriptPubKey of an address of the given type
+pub fn script_pubkey_len(script_type: ScriptType) -> usize {
+    match script_type {
+        ScriptType::P2pkh => 25,
+        ScriptType::P2shP2wpkh => 23,
+        ScriptType::P2wpkh => 22,
+        ScriptType::P2tr => 34,
+    }
+}
+
+/// Smallest output of the given type Core relays at its default dust feerate
+pub fn dust_limit(script_type: ScriptType) -> u64 {
+    match script_type {
+        ScriptType::P2pkh => 546,
+        ScriptType::P2shP2wpkh => 540,
+        ScriptType::P2wpkh => 294,
+        ScriptType::P2tr => 330,
+    }
+}
+
+/// Fee of `weight` at `feerate` sat/vB, rounded up
+pub fn fee_for_weight(weight: u64, feerate: f64) -> u64 {
+    (weight as f64 * feerate / 4.0).ceil() as u64
+}
+
+/// A spendable output as coin selection sees it
+#[derive(Debug, Clone)]
+pub struct Coin {
+    pub txid: String,
+    pub vout: u32,
+    pub value_sat: u64,
+    pub input_weight: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SelectionParams {
+    /// Sum of the recipient amounts
+    pub target_sat: u64,
+    /// sat/vB the transaction pays
+    pub feerate: f64,
+    /// sat/vB expected to spend coins at eventually; spending more inputs is
+    /// wasteful above it and consolidates cheaply below it
+    pub long_term_feerate: f64,
+    /// Transaction weight without inputs or change
+    pub base_weight: u64,
+    pub change_output_weight: u64,
+    /// Weight of spending the change output later
+    pub change_spend_weight: u64,
+    /// Smallest change worth creating; less is dropped to fees
+    pub min_change_sat: u64,
+}
+
+impl SelectionParams {
+    /// Value of a coin minus the fee of spending it at the target feerate
+    pub fn effective_value(&self, coin: &Coin) -> i64 {
+        coin.value_sat as i64 - fee_for_weight(coin.input_weight, self.feerate) as i64
+    }
+
+    /// Fee of creating a change output now and spending it later
+    pub fn cost_of_change(&self) -> u64 {
+        fee_for_weight(self.change_output_weight, self.feerate)
+            + fee_for_weight(self.change_spend_weight, self.long_term_feerate)
+    }
+
+    /// What spending a coin now costs over spending it at the long-term feerate
+    fn input_waste(&self, coin: &Coin) -> i64 {
+        fee_for_weight(coin.input_weight, self.feerate) as i64
+            - fee_for_weight(coin.input_weight, self.long_term_feerate) as i64
+    }
+}
+
+/// Inputs chosen by an algorithm with the resulting fee and change
+#[derive(Debug, Clone)]
+pub struct Selection {
+    pub algorithm: SelectionAlgorithm,
+    /// Must-spend coins first
+    pub coins: Vec<Coin>,
+    pub must_spend: usize,
+    pub input_sat: u64,
+    pub fee_sat: u64,
+    pub change_sat: Option<u64>,
+    /// Total weight, change output included
+    pub weight: u64,
+    pub waste_sat: i64,
+}
+
+impl Selection {
+    pub fn vsize(&self) -> u64 {
+        self.weight.div_ceil(4)
+    }
+}
+
+/// A coin from the pool with its effective value and waste
+struct Candidate<'a> {
+    coin: &'a Coin,
+    effective: i64,
+    waste: i64,
+}
+
+/// Run one algorithm over `pool`. Must-spend coins are always included and
+/// the algorithm only covers what they leave; coins that cost more to spend
+/// than they are worth are never picked.
+pub fn select<R: Rng + ?Sized>(
+    algorithm: SelectionAlgorithm,
+    pool: &[Coin],
+    must_spend: &[Coin],
+    params: &SelectionParams,
+    rng: &mut R,
+) -> Result<Selection, AppError> {
+    let covered: i64 = must_spend.iter().map(|c| params.effective_value(c)).sum();
+    let needed = i64::try_from(params.target_sat)
+        .ok()
+        .and_then(|target| target.checked_add(fee_for_weight(params.base_weight, params.feerate) as i64))
+        .and_then(|total| total.checked_sub(covered))
+        .ok_or_else(|| AppError::BadRequest("Target plus fee is out of range".to_string()))?;
+
+    let mut coins = must_spend.to_vec();
+    if needed > 0 {
+        let mut candidates: Vec<Candidate> = pool
+            .iter()
+            .map(|coin| Candidate {
+                coin,
+                effective: params.effective_value(coin),
+                waste: params.input_waste(coin),
+            })
+            .filter(|c| c.effective > 0)
+            .collect();
+
+        let available: i64 = candidates.iter().map(|c| c.effective).sum();
+        if available < needed {
+            return Err(AppError::BadRequest(format!(
+                "Insufficient funds: {} sat spendable after input fees, {} sat needed",
+                available.max(0),
+                needed
+            )));
+        }
+
+        let picked = match algorithm {
+            SelectionAlgorithm::Bnb => {
+                candidates.sort_by_key(|c| Reverse(c.effective));
+                branch_and_bound(&candidates, needed, params.cost_of_change() as i64)
+                    .ok_or("No input set matches the target closely enough to avoid change")
+            }
+            SelectionAlgorithm::Knapsack => {
+                candidates.shuffle(rng);
+                Ok(knapsack(&candidates, needed, CHANGE_TARGET_SAT as i64, rng))
+            }
+            SelectionAlgorithm::LargestFirst => {
+                candidates.sort_by_key(|c| Reverse(c.effective));
+                Ok(accumulate(&candidates, needed).expect("available covers needed"))
+            }
+            SelectionAlgorithm::SingleRandomDraw => {
+                candidates.shuffle(rng);
+                let change_fee = fee_for_weight(params.change_output_weight, params.feerate);
+                accumulate(&candidates, needed + (change_fee + CHANGE_TARGET_SAT) as i64)
+                    .ok_or("Funds do not cover the target plus a change output")
+            }
+        }
+        .map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+        coins.extend(picked.into_iter().map(|i| candidates[i].coin.clone()));
+    }
+
+    finish(algorithm, coins, must_spend.len(), params)
+}
+
+/// Work out fee, change and waste of an input set. Change is only added when
+/// the excess pays for it and leaves at least `min_change_sat`.
+fn finish(
+    algorithm: SelectionAlgorithm,
+    coins: Vec<Coin>,
+    must_spend: usize,
+    params: &SelectionParams,
+) -> Result<Selection, AppError> {
+    let input_sat: u64 = coins.iter().map(|c| c.value_sat).sum();
+    let weight = params.base_weight + coins.iter().map(|c| c.input_weight).sum::<u64>();
+    let fee = fee_for_weight(weight, params.feerate);
+    let input_waste: i64 = coins.iter().map(|c| params.input_waste(c)).sum();
+
+    let spent = params
+        .target_sat
+        .checked_add(fee)
+        .ok_or_else(|| AppError::BadRequest("Target plus fee is out of range".to_string()))?;
+    let excess = input_sat
+        .checked_sub(spent)
+        .ok_or_else(|| AppError::BadRequest("Selected inputs do not cover the target and fee".to_string()))?;
+
+    let with_change = weight + params.change_output_weight;
+    let change_fee = fee_for_weight(with_change, params.feerate) - fee;
+    let change = excess
+        .checked_sub(change_fee)
+        .filter(|change| excess > params.cost_of_change() && *change >= params.min_change_sat);
+
+    Ok(match change {
+        Some(change) => Selection {
+            algorithm,
+            coins,
+            must_spend,
+            input_sat,
+            fee_sat: fee + change_fee,
+            change_sat: Some(change),
+            weight: with_change,
+            waste_sat: input_waste + params.cost_of_change() as i64,
+        },
+        None => Selection {
+            algorithm,
+            coins,
+            must_spend,
+            input_sat,
+            fee_sat: fee + excess,
+            change_sat: None,
+            weight,
+            waste_sat: input_waste + excess as i64,
+        },
+    })
+}
+
+/// Depth-first search for the input set with the least waste whose effective
+/// value lands in [target, target + cost_of_change], as in Core. Candidates
+/// must be sorted by effective value, largest first.
+fn branch_and_bound(candidates: &[Candidate], target: i64, cost_of_change: i64) -> Option<Vec<usize>> {
+    let mut available: i64 = candidates.iter().map(|c| c.effective).sum();
+    let mut value = 0i64;
+    let mut waste = 0i64;
+    let mut selection: Vec<usize> = Vec::new();
+    let mut best: Option<(Vec<usize>, i64)> = None;
+    // Above the long-term feerate every extra input adds waste, so a branch
+    // already worse than the best cannot improve
+    let feerate_high = candidates.first().is_some_and(|c| c.waste > 0);
+
+    let mut index = 0;
+    for _ in 0..BNB_TOTAL_TRIES {
+        let best_waste = best.as_ref().map_or(i64::MAX, |(_, w)| *w);
+
+        let backtrack = if value + available < target
+            || value > target + cost_of_change
+            || (feerate_high && waste > best_waste)
+        {
+            true
+        } else if value >= target {
+            if waste + (value - target) <= best_waste {
+                best = Some((selection.clone(), waste + (value - target)));
+            }
+            true
+        } else {
+            false
+        };
+
+        if backtrack {
+            let Some(&last) = selection.last() else {
+                break;
+            };
+            // Put the coins skipped after the last inclusion back in reach,
+            // then try the branch without it
+            while index > last + 1 {
+                index -= 1;
+                available += candidates[index].effective;
+            }
+            index = last;
+            value -= candidates[last].effective;
+            waste -= candidates[last].waste;
+            selection.pop();
+        } else {
+            let candidate = &candidates[index];
+            available -= candidate.effective;
+            // Skip a coin equal to one just left out: that branch was explored
+            let duplicate = !selection.is_empty()
+                && selection.last() != Some(&(index - 1))
+                && candidate.effective == candidates[index - 1].effective
+                && candidate.waste == candidates[index - 1].waste;
+            if !duplicate {
+                selection.push(index);
+                value += candidate.effective;
+                waste += candidate.waste;
+            }
+        }
+        index += 1;
+    }
+
+    best.map(|(selection, _)| selection)
+}
+
+/// Core's knapsack solver: a single coin matching exactly, all smaller coins
+/// when they match exactly, otherwise the best of randomized subset sums and
+/// the smallest coin larger than the target plus `change_target`
+fn knapsack<R: Rng + ?Sized>(
+    candidates: &[Candidate],
+    target: i64,
+    change_target: i64,
+    rng: &mut R,
+) -> Vec<usize> {
+    let mut smaller: Vec<usize> = Vec::new();
+    let mut smaller_total = 0i64;
+    let mut lowest_larger: Option<usize> = None;
+
+    for (i, candidate) in candidates.iter().enumerate() {
+        if candidate.effective == target {
+            return vec![i];
+        } else if candidate.effective < target + change_target {
+            smaller.push(i);
+            smaller_total += candidate.effective;
+        } else if lowest_larger.is_none_or(|l| candidate.effective < candidates[l].effective) {
+            lowest_larger = Some(i);
+        }
+    }
+
+    if smaller_total == target {
+        return smaller;
+    }
+    if smaller_total < target {
+        return lowest_larger.into_iter().collect();
+    }
+
+    smaller.sort_by(|&a, &b| candidates[b].effective.cmp(&candidates[a].effective));
+    let values: Vec<i64> = smaller.iter().map(|&i| candidates[i].effective).collect();
+
+    let (mut best, mut best_value) = approximate_best_subset(&values, smaller_total, target, rng);
+    if best_value != target && smaller_total >= target + change_target {
+        (best, best_value) = approximate_best_subset(&values, smaller_total, target + change_target, rng);
+    }
+
+    // A single larger coin beats a subset that leaves too little change or
+    // is no smaller
+    if let Some(larger) = lowest_larger
+        && ((best_value != target && best_value < target + change_target)
+            || candidates[larger].effective <= best_value)
+    {
+        return vec![larger];
+    }
+
+    smaller
+        .iter()
+        .zip(best)
+        .filter(|(_, included)| *included)
+        .map(|(&i, _)| i)
+        .collect()
+}
+
+/// Randomized search for the subset of `values` (largest first) with the
+/// smallest sum not below `target`
+fn approximate_best_subset<R: Rng + ?Sized>(
+    values: &[i64],
+    total: i64,
+    target: i64,
+    rng: &mut R,
+) -> (Vec<bool>, i64) {
+    let mut best = vec![true; values.len()];
+    let mut best_value = total;
+
+    for _ in 0..KNAPSACK_ITERATIONS {
+        if best_value == target {
+            break;
+        }
+        let mut included = vec![false; values.len()];
+        let mut sum = 0i64;
+        let mut reached = false;
+
+        // First pass includes coins at random, the second tops up with the rest
+        for pass in 0..2 {
+            if reached {
+                break;
+            }
+            for i in 0..values.len() {
+                let include = if pass == 0 { rng.gen_bool(0.5) } else { !included[i] };
+                if !include {
+                    continue;
+                }
+                sum += values[i];
+                included[i] = true;
+                if sum >= target {
+                    reached = true;
+                    if sum < best_value {
+                        best_value = sum;
+                        best = included.clone();
+                    }
+                    sum -= values[i];
+                    included[i] = false;
+                }
+            }
+        }
+    }
+
+    (best, best_value)
+}
+
+/// Candidates in order until their effective value covers `target`
+fn accumulate(candidates: &[Candidate], target: i64) -> Option<Vec<usize>> {
+    let mut sum = 0i64;
+    for (i, candidate) in candidates.iter().enumerate() {
+        sum += candidate.effective;
+        if sum >= target {
+            return Some((0..=i).collect());
+        }
+    }
+    None
+}
+
+/// Coins and parameters shared by the selection tests here and in fee_bump
+#[cfg(test)]
+pub mod fixtures {
+    use super::*;
+
+    /// A P2WPKH coin worth `value_sat`, with a txid derived from `id`
+    pub fn coin(id: u8, value_sat: u64) -> Coin {
+        Coin {
+            txid: format!("{:02x}", id).repeat(32),
+            vout: 0,
+            value_sat,
+            input_weight: input_weight(ScriptType::P2wpkh),
+        }
+    }
+
+    /// One P2WPKH payment of `target_sat` with P2WPKH change
+    pub fn params(target_sat: u64, feerate: f64) -> SelectionParams {
+        SelectionParams {
+            target_sat,
+            feerate,
+            long_term_feerate: 1.0,
+            base_weight: TX_OVERHEAD_WEIGHT + output_weight(22),
+            change_output_weight: output_weight(22),
+            change_spend_weight: input_weight(ScriptType::P2wpkh),
+            min_change_sat: dust_limit(ScriptType::P2wpkh),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::secp256k1::rand::SeedableRng;
+    use bitcoin::secp256k1::rand::rngs::StdRng;
+
+    /// P2WPKH spends cost 68 sat at 1 sat/vB, so a coin of `effective + 68`
+    /// has exactly `effective` to contribute
+    fn coin(id: u8, effective: u64) -> Coin {
+        fixtures::coin(id, effective + 68)
+    }
+
+    /// One P2WPKH recipient at 1 sat/vB; the base fee is 42 sat, so a target
+    /// of `needed - 42` asks the algorithms for exactly `needed`
+    fn params(needed: u64) -> SelectionParams {
+        fixtures::params(needed - 42, 1.0)
+    }
+
+    fn run(
+        algorithm: SelectionAlgorithm,
+        pool: &[Coin],
+        must_spend: &[Coin],
+        params: &SelectionParams,
+    ) -> Result<Selection, AppError> {
+        select(algorithm, pool, must_spend, params, &mut StdRng::seed_from_u64(7))
+    }
+
+    fn values(selection: &Selection) -> Vec<u64> {
+        selection.coins.iter().map(|c| c.value_sat - 68).collect()
+    }
+
+    fn ladder() -> Vec<Coin> {
+        vec![coin(1, 10_000), coin(2, 20_000), coin(3, 30_000), coin(4, 40_000)]
+    }
+
+    #[test]
+    fn bnb_finds_exact_match_without_change() {
+        let pool = vec![coin(1, 100_000), coin(2, 50_000), coin(3, 30_000)];
+        let selection = run(SelectionAlgorithm::Bnb, &pool, &[], &params(80_000)).unwrap();
+
+        assert_eq!(values(&selection), vec![50_000, 30_000]);
+        assert_eq!(selection.change_sat, None);
+        assert_eq!(selection.input_sat, selection.fee_sat + 79_958);
+    }
+
+    #[test]
+    fn bnb_fails_without_a_match_in_the_window() {
+        let pool = vec![coin(1, 100_000)];
+        let err = run(SelectionAlgorithm::Bnb, &pool, &[], &params(50_000)).unwrap_err();
+
+        assert!(matches!(err, AppError::BadRequest(_)));
+    }
+
+    #[test]
+    fn knapsack_finds_exact_subset() {
+        let selection = run(SelectionAlgorithm::Knapsack, &ladder(), &[], &params(50_000)).unwrap();
+
+        assert_eq!(values(&selection).iter().sum::<u64>(), 50_000);
+        assert_eq!(selection.change_sat, None);
+    }
+
+    #[test]
+    fn knapsack_falls_back_to_lowest_larger_coin() {
+        let pool = vec![coin(1, 10_000), coin(2, 20_000), coin(3, 200_000), coin(4, 300_000)];
+        let selection = run(SelectionAlgorithm::Knapsack, &pool, &[], &params(50_000)).unwrap();
+
+        assert_eq!(values(&selection), vec![200_000]);
+        assert!(selection.change_sat.is_some());
+    }
+
+    #[test]
+    fn knapsack_subset_search_leaves_change_target() {
+        // No subset hits 65_000 exactly, so the solver searches again for the
+        // target plus the change target, which 30k + 40k + 45k matches
+        let pool = vec![coin(1, 30_000), coin(2, 40_000), coin(3, 45_000), coin(4, 60_000)];
+        let selection = run(SelectionAlgorithm::Knapsack, &pool, &[], &params(65_000)).unwrap();
+        let total: u64 = values(&selection).iter().sum();
+
+        assert_eq!(total, 65_000 + CHANGE_TARGET_SAT);
+    }
+
+    #[test]
+    fn largest_first_takes_largest_coins() {
+        let selection = run(SelectionAlgorithm::LargestFirst, &ladder(), &[], &params(50_000)).unwrap();
+
+        assert_eq!(values(&selection), vec![40_000, 30_000]);
+        assert_eq!(selection.change_sat, Some(19_969));
+        assert_eq!(selection.fee_sat, 209);
+    }
+
+    #[test]
+    fn single_random_draw_covers_change_target() {
+        let mut pool = ladder();
+        pool.push(coin(5, 100_000));
+        let selection = run(SelectionAlgorithm::SingleRandomDraw, &pool, &[], &params(50_000)).unwrap();
+
+        assert!(values(&selection).iter().sum::<u64>() >= 50_000 + 31 + CHANGE_TARGET_SAT);
+        assert!(selection.change_sat.is_some());
+    }
+
+    #[test]
+    fn single_random_draw_fails_without_room_for_change() {
+        let err = run(SelectionAlgorithm::SingleRandomDraw, &ladder(), &[], &params(60_000)).unwrap_err();
+
+        assert!(matches!(err, AppError::BadRequest(_)));
+    }
+
+    #[test]
+    fn must_spend_coins_come_first_and_reduce_the_target() {
+        let must = [coin(9, 40_000)];
+        let selection = run(SelectionAlgorithm::LargestFirst, &ladder(), &must, &params(50_000)).unwrap();
+
+        assert_eq!(selection.must_spend, 1);
+        assert_eq!(values(&selection), vec![40_000, 40_000]);
+    }
+
+    #[test]
+    fn must_spend_covering_the_target_adds_nothing() {
+        let must = [coin(9, 100_000)];
+        for algorithm in SelectionAlgorithm::ALL {
+            let selection = run(algorithm, &ladder(), &must, &params(50_000)).unwrap();
+            assert_eq!(values(&selection), vec![100_000], "{:?}", algorithm);
+        }
+    }
+
+    #[test]
+    fn insufficient_funds_are_reported() {
+        // The 50 sat coin costs more to spend than it is worth
+        let pool = vec![coin(1, 10_000), Coin { value_sat: 50, ..coin(2, 0) }];
+        for algorithm in SelectionAlgorithm::ALL {
+            match run(algorithm, &pool, &[], &params(50_000)) {
+                Err(AppError::BadRequest(message)) => {
+                    assert!(message.starts_with("Insufficient funds"), "{}", message)
+                }
+                other => panic!("{:?}: {:?}", algorithm, other.map(|s| s.coins)),
+            }
+        }
+    }
+
+    #[test]
+    fn change_below_min_change_goes_to_fees() {
+        // One input: 110 sat fee, 31 sat more with change. An excess of 200
+        // pays for change but would leave 169 sat, under the dust limit.
+        let pool = [Coin { value_sat: 49_958 + 110 + 200, ..coin(1, 0) }];
+        let selection = run(SelectionAlgorithm::LargestFirst, &pool, &[], &params(50_000)).unwrap();
+
+        assert_eq!(selection.change_sat, None);
+        assert_eq!(selection.fee_sat, 310);
+
+        let pool = [Coin { value_sat: 49_958 + 110 + 500, ..coin(1, 0) }];
+        let selection = run(SelectionAlgorithm::LargestFirst, &pool, &[], &params(50_000)).unwrap();
+
+        assert_eq!(selection.change_sat, Some(469));
+        assert_eq!(selection.fee_sat, 141);
+    }
+
+    #[test]
+    fn out_of_range_target_is_rejected() {
+        let params = SelectionParams {
+            target_sat: u64::MAX,
+            ..params(50_000)
+        };
+        let err = run(SelectionAlgorithm::LargestFirst, &ladder(), &[], &params).unwrap_err();
+
+        assert!(matches!(err, AppError::BadRequest(_)));
+    }
+}
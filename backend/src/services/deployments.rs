@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+
+use crate::domain::deployment::{Bip9Deployment, Deployment, DeploymentInfo, SignallingPeriod};
+use crate::errors::AppError;
+use crate::services::bitcoin_rpc::{Bip9StatisticsRaw, BitcoinRpc, DeploymentRaw};
+
+/// Upper bound on completed signalling periods looked up per request
+pub const MAX_PERIODS: u64 = 12;
+
+/// JSON-RPC "method not found"
+const RPC_METHOD_NOT_FOUND: i32 = -32601;
+
+/// Deployment state at the tip plus signalling statistics for up to
+/// `periods` completed periods of every BIP9 deployment still signalling.
+pub async fn deployment_info(rpc: &BitcoinRpc, periods: u64) -> Result<DeploymentInfo, AppError> {
+    let current = match rpc.get_deployment_info(None).await {
+        Ok(info) => info,
+        // Older nodes report deployments in getblockchaininfo, without history support
+        Err(AppError::BitcoinRpcError { code: RPC_METHOD_NOT_FOUND, .. }) => {
+            let info = rpc.get_blockchain_info().await?;
+            return Ok(DeploymentInfo {
+                source: "getblockchaininfo",
+                hash: info.bestblockhash,
+                height: info.blocks,
+                deployments: to_deployments(info.softforks.unwrap_or_default(), info.blocks),
+            });
+        }
+        Err(e) => return Err(e),
+    };
+
+    let height = current.height;
+    let mut deployments = to_deployments(current.deployments, height);
+
+    // Every deployment shares the period length, so one lookup per past period serves all
+    let period_length = deployments
+        .iter()
+        .filter_map(|d| d.bip9.as_ref()?.current_period.as_ref())
+        .map(|p| p.length)
+        .next();
+    if let Some(length) = period_length.filter(|length| *length > 0) {
+        // Periods are aligned to multiples of their length
+        let current_start = height - height % length;
+
+        for k in 0..periods.min(MAX_PERIODS) {
+            let Some(period_end) = current_start.checked_sub(1 + k * length) else {
+                break;
+            };
+            let hash = rpc.get_block_hash(period_end).await?;
+            let past = rpc.get_deployment_info(Some(&hash)).await?;
+
+            let mut any = false;
+            for deployment in deployments.iter_mut() {
+                let Some(bip9) = deployment.bip9.as_mut() else { continue };
+                if let Some(statistics) = past
+                    .deployments
+                    .get(&deployment.name)
+                    .and_then(|d| d.bip9.as_ref()?.statistics.clone())
+                {
+                    bip9.previous_periods.push(to_period(&statistics, period_end));
+                    any = true;
+                }
+            }
+            // Nothing was signalling yet that far back
+            if !any {
+                break;
+            }
+        }
+    }
+
+    Ok(DeploymentInfo {
+        source: "getdeploymentinfo",
+        hash: current.hash,
+        height,
+        deployments,
+    })
+}
+
+fn to_deployments(raw: BTreeMap<String, DeploymentRaw>, tip_height: u64) -> Vec<Deployment> {
+    raw.into_iter()
+        .map(|(name, d)| Deployment {
+            name,
+            deployment_type: d.deployment_type,
+            active: d.active,
+            height: d.height,
+            bip9: d.bip9.map(|b| Bip9Deployment {
+                bit: b.bit,
+                start_time: b.start_time,
+                timeout: b.timeout,
+                min_activation_height: b.min_activation_height,
+                status: b.status,
+                since: b.since,
+                status_next: b.status_next,
+                current_period: b.statistics.as_ref().map(|s| to_period(s, tip_height)),
+                signalling: b.signalling,
+                previous_periods: Vec::new(),
+            }),
+        })
+        .collect()
+}
+
+/// Statistics are reported for the period containing the queried block
+fn to_period(statistics: &Bip9StatisticsRaw, last_height: u64) -> SignallingPeriod {
+    SignallingPeriod {
+        start_height: (last_height + 1).saturating_sub(statistics.elapsed),
+        length: statistics.period,
+        threshold: statistics.threshold,
+        elapsed: statistics.elapsed,
+        count: statistics.count,
+        ratio: if statistics.elapsed > 0 {
+            statistics.count as f64 / statistics.elapsed as f64
+        } else {
+            0.0
+        },
+        possible: statistics.possible,
+    }
+}
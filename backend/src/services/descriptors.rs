@@ -0,0 +1,241 @@
+use bitcoin::NetworkKind;
+use bitcoin::bip32::{DerivationPath, Xpub};
+
+use crate::domain::wallet::{AddressChain, ScriptType, WalletAccount};
+use crate::errors::AppError;
+
+const INPUT_CHARSET: &str =
+    "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+const CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const GENERATOR: [u64; 5] = [0xf5dee51989, 0xa9fdca3312, 0x1bab10e32d, 0x3706b1677a, 0x644d626ffd];
+
+/// SLIP-132 version bytes: (prefix, network, script type; None when the prefix does not imply one)
+const SLIP132_VERSIONS: [([u8; 4], &str, NetworkKind, Option<ScriptType>); 6] = [
+    ([0x04, 0x88, 0xb2, 0x1e], "xpub", NetworkKind::Main, None),
+    ([0x04, 0x9d, 0x7c, 0xb2], "ypub", NetworkKind::Main, Some(ScriptType::P2shP2wpkh)),
+    ([0x04, 0xb2, 0x47, 0x46], "zpub", NetworkKind::Main, Some(ScriptType::P2wpkh)),
+    ([0x04, 0x35, 0x87, 0xcf], "tpub", NetworkKind::Test, None),
+    ([0x04, 0x4a, 0x52, 0x62], "upub", NetworkKind::Test, Some(ScriptType::P2shP2wpkh)),
+    ([0x04, 0x5f, 0x1c, 0xf6], "vpub", NetworkKind::Test, Some(ScriptType::P2wpkh)),
+];
+
+/// A single-key output descriptor, e.g. `wpkh([d34db33f/84h/0h/0h]xpub.../<0;1>/*)`
+#[derive(Debug, Clone)]
+pub struct KeyDescriptor {
+    pub script_type: ScriptType,
+    pub master_fingerprint: Option<String>,
+    /// Origin path in `m/84'/0'/0'` notation
+    pub origin_path: Option<String>,
+    pub xpub: Xpub,
+}
+
+/// BIP380 descriptor checksum
+pub fn checksum(descriptor: &str) -> Result<String, AppError> {
+    let mut symbols = Vec::with_capacity(descriptor.len() * 2);
+    let mut groups = Vec::with_capacity(3);
+    for c in descriptor.chars() {
+        let value = INPUT_CHARSET
+            .find(c)
+            .ok_or_else(|| AppError::BadRequest(format!("Invalid character '{}' in descriptor", c)))?
+            as u64;
+        symbols.push(value & 31);
+        groups.push(value >> 5);
+        if groups.len() == 3 {
+            symbols.push(groups[0] * 9 + groups[1] * 3 + groups[2]);
+            groups.clear();
+        }
+    }
+    match groups.len() {
+        1 => symbols.push(groups[0]),
+        2 => symbols.push(groups[0] * 3 + groups[1]),
+        _ => {}
+    }
+    symbols.extend([0; 8]);
+
+    let mut chk: u64 = 1;
+    for value in symbols {
+        let top = chk >> 35;
+        chk = ((chk & 0x7_ffff_ffff) << 5) ^ value;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+    chk ^= 1;
+
+    Ok((0..8)
+        .map(|i| CHECKSUM_CHARSET[((chk >> (5 * (7 - i))) & 31) as usize] as char)
+        .collect())
+}
+
+/// Append the checksum to a descriptor
+pub fn with_checksum(descriptor: &str) -> Result<String, AppError> {
+    Ok(format!("{}#{}", descriptor, checksum(descriptor)?))
+}
+
+/// Parse an extended public key in any SLIP-132 encoding into a standard
+/// xpub/tpub, returning the script type its prefix implies
+pub fn parse_extended_key(key: &str) -> Result<(Xpub, Option<ScriptType>), AppError> {
+    if key.get(1..4) == Some("prv") {
+        return Err(AppError::BadRequest(
+            "Extended private keys are not accepted; provide the public key".to_string(),
+        ));
+    }
+
+    let mut data = bitcoin::base58::decode_check(key)
+        .map_err(|e| AppError::BadRequest(format!("Invalid extended public key: {}", e)))?;
+    if data.len() != 78 {
+        return Err(AppError::BadRequest("Invalid extended public key length".to_string()));
+    }
+
+    let (_, _, network, script_type) = SLIP132_VERSIONS
+        .iter()
+        .find(|(version, ..)| data[..4] == version[..])
+        .ok_or_else(|| {
+            AppError::BadRequest(
+                "Unsupported extended key version; expected xpub, ypub, zpub, tpub, upub or vpub".to_string(),
+            )
+        })?;
+
+    // Re-encode with the standard version for the network
+    let standard = SLIP132_VERSIONS
+        .iter()
+        .find(|(_, _, n, t)| n == network && t.is_none())
+        .map(|(version, ..)| version)
+        .expect("every network has a standard version");
+    data[..4].copy_from_slice(standard);
+
+    let xpub = Xpub::decode(&data)
+        .map_err(|e| AppError::BadRequest(format!("Invalid extended public key: {}", e)))?;
+    Ok((xpub, *script_type))
+}
+
+/// Parse a single-key descriptor (`pkh`, `sh(wpkh)`, `wpkh` or key-path-only `tr`)
+/// over a ranged extended public key. A checksum, when present, must match.
+pub fn parse_key_descriptor(descriptor: &str) -> Result<KeyDescriptor, AppError> {
+    let descriptor = descriptor.trim();
+    let body = match descriptor.split_once('#') {
+        Some((body, given)) => {
+            let expected = checksum(body)?;
+            if given != expected {
+                return Err(AppError::BadRequest(format!(
+                    "Descriptor checksum mismatch: got {}, expected {}",
+                    given, expected
+                )));
+            }
+            body
+        }
+        None => descriptor,
+    };
+
+    let templates = [
+        ("sh(wpkh(", "))", ScriptType::P2shP2wpkh),
+        ("wpkh(", ")", ScriptType::P2wpkh),
+        ("pkh(", ")", ScriptType::P2pkh),
+        ("tr(", ")", ScriptType::P2tr),
+    ];
+    let (script_type, key) = templates
+        .iter()
+        .find_map(|(prefix, suffix, script_type)| {
+            body.strip_prefix(prefix)
+                .and_then(|rest| rest.strip_suffix(suffix))
+                .map(|key| (*script_type, key))
+        })
+        .filter(|(_, key)| !key.contains(['(', ')', ',']))
+        .ok_or_else(|| {
+            AppError::BadRequest(
+                "Only single-key pkh, sh(wpkh), wpkh and key-path tr descriptors are supported".to_string(),
+            )
+        })?;
+
+    // Optional key origin: [fingerprint/path]
+    let (master_fingerprint, origin_path, key) = match key.strip_prefix('[') {
+        Some(rest) => {
+            let (origin, key) = rest
+                .split_once(']')
+                .ok_or_else(|| AppError::BadRequest("Unterminated key origin".to_string()))?;
+            let (fingerprint, path) = origin.split_once('/').unwrap_or((origin, ""));
+            let fingerprint = parse_fingerprint(fingerprint)
+                .map_err(|_| AppError::BadRequest(format!("Invalid key origin fingerprint '{}'", fingerprint)))?;
+            let path = normalize_path(path)
+                .map_err(|_| AppError::BadRequest(format!("Invalid key origin path '{}'", path)))?;
+            (Some(fingerprint), Some(path), key)
+        }
+        None => (None, None, key),
+    };
+
+    // Receive and change chains always come from the same account key
+    let Some(key) = ["/<0;1>/*", "/0/*", "/1/*"]
+        .iter()
+        .find_map(|suffix| key.strip_suffix(suffix))
+    else {
+        return Err(AppError::BadRequest(
+            "Descriptor keys must be ranged as /0/*, /1/* or /<0;1>/*".to_string(),
+        ));
+    };
+
+    let (xpub, implied) = parse_extended_key(key)?;
+    if implied.is_some_and(|implied| implied != script_type) {
+        return Err(AppError::BadRequest(format!(
+            "Key prefix does not match the {} descriptor",
+            script_type.as_str()
+        )));
+    }
+
+    Ok(KeyDescriptor {
+        script_type,
+        master_fingerprint,
+        origin_path,
+        xpub,
+    })
+}
+
+/// Lowercase master fingerprint from 8 hex characters
+pub fn parse_fingerprint(fingerprint: &str) -> Result<String, AppError> {
+    let fingerprint = fingerprint.trim();
+    if fingerprint.len() != 8 || !fingerprint.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(AppError::BadRequest(format!(
+            "Invalid master fingerprint '{}'; expected 8 hex characters",
+            fingerprint
+        )));
+    }
+    Ok(fingerprint.to_ascii_lowercase())
+}
+
+/// `m/...` form of a derivation path written with or without `m/`, and with
+/// `'`, `h` or `H` for hardened steps
+pub fn normalize_path(path: &str) -> Result<String, AppError> {
+    let path: DerivationPath = path
+        .trim()
+        .replace('H', "h")
+        .parse()
+        .map_err(|e| AppError::BadRequest(format!("Invalid derivation path '{}': {}", path, e)))?;
+
+    Ok(if path.is_empty() {
+        "m".to_string()
+    } else {
+        format!("m/{}", path)
+    })
+}
+
+/// Descriptor with checksum for one chain of an account
+pub fn account_descriptor(
+    account: &WalletAccount,
+    master_fingerprint: Option<&str>,
+    chain: AddressChain,
+) -> Result<String, AppError> {
+    let origin = match (master_fingerprint, account.derivation_path.strip_prefix("m")) {
+        (Some(fingerprint), Some(path)) => format!("[{}{}]", fingerprint, path.replace('\'', "h")),
+        _ => String::new(),
+    };
+    let key = format!("{}{}/{}/*", origin, account.xpub, chain.index());
+
+    let descriptor = match account.script_type {
+        ScriptType::P2pkh => format!("pkh({})", key),
+        ScriptType::P2shP2wpkh => format!("sh(wpkh({}))", key),
+        ScriptType::P2wpkh => format!("wpkh({})", key),
+        ScriptType::P2tr => format!("tr({})", key),
+    };
+    with_checksum(&descriptor)
+}
@@ -0,0 +1,136 @@
+use chrono::Utc;
+
+use crate::domain::epoch::{EpochInfo, HalvingProjection, HashrateWindow, RetargetProjection};
+use crate::errors::AppError;
+use crate::services::bitcoin_rpc::BitcoinRpc;
+use crate::utils::amount::SATS_PER_BTC;
+
+/// Blocks per difficulty epoch
+const EPOCH_LENGTH: u64 = 2016;
+/// Target seconds between blocks
+const TARGET_SPACING_SECS: u64 = 600;
+/// A single retarget changes difficulty by at most this factor
+const MAX_ADJUSTMENT_FACTOR: f64 = 4.0;
+/// Windows passed to getnetworkhashps
+const HASHRATE_WINDOWS: [u64; 3] = [120, 1008, 2016];
+
+/// Consensus parameters that differ between networks
+struct ChainParams {
+    halving_interval: u64,
+    /// Regtest sets fPowNoRetargeting
+    retargeting: bool,
+}
+
+fn chain_params(chain: &str) -> ChainParams {
+    match chain {
+        "regtest" => ChainParams {
+            halving_interval: 150,
+            retargeting: false,
+        },
+        _ => ChainParams {
+            halving_interval: 210_000,
+            retargeting: true,
+        },
+    }
+}
+
+/// Block subsidy at a height: 50 BTC halved every `interval` blocks
+fn subsidy_at(height: u64, interval: u64) -> u64 {
+    let halvings = height / interval;
+    if halvings >= 64 {
+        0
+    } else {
+        (50 * SATS_PER_BTC) >> halvings
+    }
+}
+
+pub async fn epoch_info(rpc: &BitcoinRpc) -> Result<EpochInfo, AppError> {
+    let info = rpc.get_blockchain_info().await?;
+    let params = chain_params(&info.chain);
+    let height = info.blocks;
+    let now = Utc::now().timestamp();
+
+    let epoch_start_height = height - height % EPOCH_LENGTH;
+    let blocks_into_epoch = height - epoch_start_height;
+
+    let retarget = if params.retargeting {
+        let hashes = rpc.get_block_hashes(&[epoch_start_height, height]).await?;
+        let headers = rpc.get_block_headers(&hashes).await?;
+        let (start_time, tip_time) = (headers[0].time, headers[1].time);
+        // The epoch's own difficulty; on testnet the tip may be a 20-minute minimum-difficulty block
+        let epoch_difficulty = headers[0].difficulty;
+
+        let avg_block_interval_secs = (blocks_into_epoch > 0)
+            .then(|| (tip_time - start_time) as f64 / blocks_into_epoch as f64);
+
+        // Extrapolate the epoch's pace to a full epoch, then apply the
+        // retarget rule: new = old * target_timespan / actual_timespan.
+        // Core measures the epoch from its first to its last block, which
+        // spans one interval less than the epoch length.
+        let target_timespan = (EPOCH_LENGTH * TARGET_SPACING_SECS) as f64;
+        let projected_difficulty = avg_block_interval_secs.map(|interval| {
+            let timespan = (interval * (EPOCH_LENGTH - 1) as f64).clamp(
+                target_timespan / MAX_ADJUSTMENT_FACTOR,
+                target_timespan * MAX_ADJUSTMENT_FACTOR,
+            );
+            epoch_difficulty * target_timespan / timespan
+        });
+
+        let blocks_remaining = EPOCH_LENGTH - blocks_into_epoch;
+        let pace = avg_block_interval_secs
+            .filter(|interval| *interval > 0.0)
+            .unwrap_or(TARGET_SPACING_SECS as f64);
+        let estimated_secs_remaining = (blocks_remaining as f64 * pace) as u64;
+
+        Some(RetargetProjection {
+            next_retarget_height: epoch_start_height + EPOCH_LENGTH,
+            blocks_remaining,
+            avg_block_interval_secs,
+            projected_difficulty,
+            projected_adjustment_pct: projected_difficulty
+                .map(|projected| (projected / epoch_difficulty - 1.0) * 100.0),
+            estimated_secs_remaining,
+            estimated_at: now + estimated_secs_remaining as i64,
+        })
+    } else {
+        None
+    };
+
+    let interval = params.halving_interval;
+    let next_halving_height = (height / interval + 1) * interval;
+    let halving_blocks_remaining = next_halving_height - height;
+    let halving_secs = params
+        .retargeting
+        .then_some(halving_blocks_remaining * TARGET_SPACING_SECS);
+
+    let hashrates = rpc.get_network_hashps(&HASHRATE_WINDOWS).await?;
+
+    Ok(EpochInfo {
+        network: info.chain,
+        height,
+        difficulty: info.difficulty,
+        epoch_length: EPOCH_LENGTH,
+        epoch_start_height,
+        blocks_into_epoch,
+        progress: blocks_into_epoch as f64 / EPOCH_LENGTH as f64,
+        retargeting: params.retargeting,
+        retarget,
+        halving: HalvingProjection {
+            interval,
+            next_halving_height,
+            blocks_remaining: halving_blocks_remaining,
+            current_subsidy_sat: subsidy_at(height, interval),
+            next_subsidy_sat: subsidy_at(next_halving_height, interval),
+            estimated_secs_remaining: halving_secs,
+            estimated_at: halving_secs.map(|secs| now + secs as i64),
+        },
+        hashrate: HASHRATE_WINDOWS
+            .iter()
+            .zip(hashrates)
+            .map(|(blocks, hashes_per_second)| HashrateWindow {
+                blocks: *blocks,
+                hashes_per_second,
+            })
+            .collect(),
+    })
+}
@@ -0,0 +1,389 @@
+use bitcoin::consensus::encode::deserialize_hex;
+use bitcoin::secp256k1::rand::thread_rng;
+use bitcoin::{Address, Transaction, TxOut};
+use std::collections::{HashMap, HashSet};
+
+use crate::config::constants::env_keys;
+use crate::config::env::var_or;
+use crate::db::{
+    PendingSendRepository, SqlitePendingSendRepository, SqliteWalletAddressRepository, WalletAddressRepository,
+};
+use crate::domain::coin_selection::SelectionAlgorithm;
+use crate::domain::send::{BumpMode, FeeBump, SendRecipient};
+use crate::domain::wallet::{AddressChain, AddressFilter, Wallet, WalletAddress};
+use crate::errors::AppError;
+use crate::services::bitcoin_rpc::BitcoinRpc;
+use crate::services::coin_selection::{self, Coin, Selection, SelectionParams};
+use crate::services::psbt::{self, PsbtInput};
+use crate::services::{balance, node_wallets, spend, wallet, wallet_addresses};
+use crate::state::app_state::AppState;
+use crate::utils::amount::btc_to_sats;
+
+/// Times the feerate is raised to meet the absolute fee rule before giving up
+const MAX_FEE_ATTEMPTS: usize = 5;
+
+/// Build a BIP125 replacement of an unconfirmed wallet transaction and store
+/// it as a new send, to be signed and broadcast like any other.
+///
+/// The replacement spends every input of the original, so the original can
+/// never confirm alongside it. Its fee covers the target feerate, and at
+/// least the fees of everything it evicts plus the incremental relay fee for
+/// its own size. A bump keeps the payments and takes the difference from
+/// change, adding confirmed inputs when change runs out. A cancel drops the
+/// payments and sends everything back to a change address.
+pub async fn bump(
+    state: &AppState,
+    wallet: &Wallet,
+    txid: &str,
+    feerate: f64,
+    mode: BumpMode,
+) -> Result<FeeBump, AppError> {
+    if !(feerate > 0.0 && feerate <= spend::MAX_FEERATE) {
+        return Err(AppError::BadRequest(format!(
+            "feerate must be above 0 and at most {} sat/vB",
+            spend::MAX_FEERATE
+        )));
+    }
+    let txid = txid.trim().to_ascii_lowercase();
+    let rpc = state.active_rpc().await?;
+    let node = rpc.for_wallet(&node_wallets::node_wallet_name(&wallet.id));
+
+    let raw = node.get_wallet_transaction(&txid).await.map_err(|e| match e {
+        // -5: unknown to the wallet
+        AppError::BitcoinRpcError { code: -5, .. } => {
+            AppError::NotFound(format!("Transaction {} not found in wallet '{}'", txid, wallet.name))
+        }
+        e => e,
+    })?;
+    if raw.confirmations > 0 {
+        return Err(AppError::Conflict(format!("Transaction {} is already confirmed", txid)));
+    }
+    if raw.confirmations < 0 {
+        return Err(AppError::Conflict(format!(
+            "Transaction {} conflicts with the active chain; it was already replaced or double-spent",
+            txid
+        )));
+    }
+    let original: Transaction = deserialize_hex(&raw.hex)
+        .map_err(|e| AppError::Internal(format!("Invalid transaction {}: {}", txid, e)))?;
+    if !original.is_explicitly_rbf() {
+        return Err(AppError::Conflict(format!(
+            "Transaction {} does not signal BIP125 replaceability",
+            txid
+        )));
+    }
+
+    let reserved: HashSet<String> = SqlitePendingSendRepository::new(state.db_pool.clone())
+        .reserved_outpoints(&wallet.id)
+        .await?
+        .into_iter()
+        .collect();
+    if original
+        .input
+        .iter()
+        .any(|input| reserved.contains(&input.previous_output.to_string()))
+    {
+        return Err(AppError::Conflict(format!(
+            "A replacement of {} is already in progress; broadcast or abandon it first",
+            txid
+        )));
+    }
+
+    let entry = rpc.get_mempool_entry(&txid).await.map_err(|e| match e {
+        // -5: not in the mempool
+        AppError::BitcoinRpcError { code: -5, .. } => {
+            AppError::Conflict(format!("Transaction {} is not in the mempool of the active node", txid))
+        }
+        e => e,
+    })?;
+    // BTC/kvB to sat/vB
+    let incremental_feerate = btc_to_sats(rpc.get_network_info().await?.incrementalfee) as f64 / 1000.0;
+
+    let addresses: HashMap<String, WalletAddress> = SqliteWalletAddressRepository::new(state.db_pool.clone())
+        .list_addresses(&wallet.id, &AddressFilter::default())
+        .await?
+        .into_iter()
+        .map(|a| (a.script_pub_key.clone(), a))
+        .collect();
+    let inputs = original_inputs(&node, wallet, &original, &addresses).await?;
+
+    let original_fee_sat = btc_to_sats(entry.fees.base);
+    let original_feerate = original_fee_sat as f64 / entry.vsize as f64;
+    let min_feerate = original_feerate + incremental_feerate;
+    if feerate < min_feerate {
+        return Err(AppError::BadRequest(format!(
+            "feerate must be at least {:.2} sat/vB: the original {:.2} plus the incremental relay fee of {:.2}",
+            min_feerate, original_feerate, incremental_feerate
+        )));
+    }
+
+    // The first change output is kept as the replacement's change; any
+    // other output, ours or not, is a payment
+    let mut change_address: Option<WalletAddress> = None;
+    let mut payments: Vec<TxOut> = Vec::new();
+    for output in &original.output {
+        match addresses.get(&output.script_pubkey.to_hex_string()) {
+            Some(address) if address.chain == AddressChain::Internal && change_address.is_none() => {
+                change_address = Some(address.clone())
+            }
+            _ => payments.push(output.clone()),
+        }
+    }
+    if mode == BumpMode::Cancel {
+        payments.clear();
+    }
+    let change_script_type = match &change_address {
+        Some(address) => address.script_type,
+        None => spend::change_script_type(wallet, None)?,
+    };
+
+    // Extra inputs must be confirmed: BIP125 allows no new unconfirmed ones
+    let utxos = match mode {
+        BumpMode::Bump => balance::list_utxos(state, wallet).await?,
+        BumpMode::Cancel => vec![],
+    };
+    let spent: HashSet<String> = inputs.iter().map(|i| format!("{}:{}", i.txid, i.vout)).collect();
+    let pool: Vec<Coin> = utxos
+        .iter()
+        .filter(|u| u.confirmations > 0 && u.safe && !u.frozen)
+        .filter(|u| !reserved.contains(&u.outpoint) && !spent.contains(&u.outpoint))
+        .filter_map(PsbtInput::from_utxo)
+        .map(|input| coin(&input))
+        .collect();
+    let must_spend: Vec<Coin> = inputs.iter().map(coin).collect();
+
+    let mut params = SelectionParams {
+        target_sat: payments.iter().map(|o| o.value.to_sat()).sum(),
+        feerate,
+        long_term_feerate: var_or(env_keys::LONG_TERM_FEERATE, 10.0),
+        base_weight: coin_selection::TX_OVERHEAD_WEIGHT
+            + payments
+                .iter()
+                .map(|o| coin_selection::output_weight(o.script_pubkey.len()))
+                .sum::<u64>(),
+        change_output_weight: coin_selection::output_weight(coin_selection::script_pubkey_len(change_script_type)),
+        change_spend_weight: coin_selection::input_weight(change_script_type),
+        min_change_sat: coin_selection::dust_limit(change_script_type),
+    };
+    let descendant_fee_sat = btc_to_sats(entry.fees.descendant);
+    let (selection, min_fee_sat) = replacement_selection(&pool, &must_spend, &mut params, |weight| {
+        descendant_fee_sat + coin_selection::fee_for_weight(weight, incremental_feerate)
+    })?;
+    if payments.is_empty() && selection.change_sat.is_none() {
+        return Err(AppError::BadRequest(format!(
+            "The inputs of {} are worth too little to send back to the wallet at {} sat/vB",
+            txid, params.feerate
+        )));
+    }
+
+    let mut psbt_inputs = inputs;
+    psbt_inputs.extend(
+        selection.coins[selection.must_spend..]
+            .iter()
+            .map(|coin| {
+                utxos
+                    .iter()
+                    .find(|u| u.txid == coin.txid && u.vout == coin.vout)
+                    .and_then(PsbtInput::from_utxo)
+                    .ok_or_else(|| AppError::Internal(format!("Selected coin {}:{} is unknown", coin.txid, coin.vout)))
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+    );
+
+    let change = match (selection.change_sat, change_address) {
+        (Some(change_sat), Some(address)) => Some((change_sat, address)),
+        (Some(change_sat), None) => Some((
+            change_sat,
+            wallet_addresses::change_address(state, wallet, change_script_type).await?,
+        )),
+        (None, _) => None,
+    };
+
+    let psbt = psbt::build(
+        state,
+        wallet,
+        &psbt_inputs,
+        payments.clone(),
+        change.as_ref().map(|(sat, address)| (*sat, address)),
+        true,
+    )
+    .await?;
+
+    let network = wallet::parse_network(&wallet.network)?;
+    let recipients = payments
+        .iter()
+        .map(|output| SendRecipient {
+            address: Address::from_script(&output.script_pubkey, network)
+                .map(|a| a.to_string())
+                .unwrap_or_else(|_| output.script_pubkey.to_hex_string()),
+            amount_sat: output.value.to_sat(),
+        })
+        .collect();
+    let mut send = psbt::new_send(
+        wallet,
+        &psbt,
+        &psbt_inputs,
+        recipients,
+        change.map(|(_, address)| address.address),
+        &selection,
+        params.feerate,
+    );
+    send.replaces = Some(txid.clone());
+    SqlitePendingSendRepository::new(state.db_pool.clone()).create(&send).await?;
+
+    println!(
+        "⏫ Built replacement {} of {} for wallet '{}' (fee {} -> {} sat)",
+        send.unsigned_txid, txid, wallet.name, original_fee_sat, send.fee_sat
+    );
+
+    Ok(FeeBump {
+        mode,
+        original_txid: txid,
+        original_fee_sat,
+        original_vsize: entry.vsize,
+        original_feerate,
+        min_fee_sat,
+        send,
+    })
+}
+
+/// The outputs the original spends, which must all be the wallet's. Outputs
+/// to addresses we have not derived, e.g. past our lookahead, take their
+/// derivation path from the node wallet's descriptors.
+async fn original_inputs(
+    node: &BitcoinRpc,
+    wallet: &Wallet,
+    original: &Transaction,
+    addresses: &HashMap<String, WalletAddress>,
+) -> Result<Vec<PsbtInput>, AppError> {
+    let foreign = |outpoint: String| {
+        AppError::BadRequest(format!(
+            "Input {} does not belong to wallet '{}'; only transactions it funded alone can be replaced",
+            outpoint, wallet.name
+        ))
+    };
+    let network = wallet::parse_network(&wallet.network)?;
+
+    let mut inputs = Vec::new();
+    for input in &original.input {
+        let outpoint = input.previous_output;
+        let prev = match node.get_wallet_transaction(&outpoint.txid.to_string()).await {
+            Ok(prev) => prev,
+            Err(AppError::BitcoinRpcError { code: -5, .. }) => return Err(foreign(outpoint.to_string())),
+            Err(e) => return Err(e),
+        };
+        let prev: Transaction = deserialize_hex(&prev.hex)
+            .map_err(|e| AppError::Internal(format!("Invalid transaction {}: {}", outpoint.txid, e)))?;
+        let spent = prev
+            .output
+            .get(outpoint.vout as usize)
+            .ok_or_else(|| AppError::Internal(format!("Transaction {} has no output {}", outpoint.txid, outpoint.vout)))?;
+
+        let (script_type, derivation_path) = match addresses.get(&spent.script_pubkey.to_hex_string()) {
+            Some(address) => (address.script_type, address.derivation_path.clone()),
+            None => {
+                let address = Address::from_script(&spent.script_pubkey, network)
+                    .map_err(|_| foreign(outpoint.to_string()))?;
+                let info = node.get_address_info(&address.to_string()).await?;
+                let derivation_path = info
+                    .desc
+                    .as_deref()
+                    .filter(|_| info.ismine)
+                    .and_then(balance::origin_path)
+                    .ok_or_else(|| foreign(outpoint.to_string()))?;
+                let script_type =
+                    spend::script_type_of(&spent.script_pubkey).ok_or_else(|| foreign(outpoint.to_string()))?;
+                (script_type, derivation_path)
+            }
+        };
+
+        inputs.push(PsbtInput {
+            txid: outpoint.txid.to_string(),
+            vout: outpoint.vout,
+            value_sat: spent.value.to_sat(),
+            script_pub_key: spent.script_pubkey.clone(),
+            script_type,
+            derivation_path,
+        });
+    }
+    Ok(inputs)
+}
+
+/// Select at the target feerate, raising it while the absolute fee rule
+/// asks for more. Largest-first keeps the number of extra inputs down.
+fn replacement_selection(
+    pool: &[Coin],
+    must_spend: &[Coin],
+    params: &mut SelectionParams,
+    min_fee: impl Fn(u64) -> u64,
+) -> Result<(Selection, u64), AppError> {
+    for _ in 0..MAX_FEE_ATTEMPTS {
+        let selection = coin_selection::select(
+            SelectionAlgorithm::LargestFirst,
+            pool,
+            must_spend,
+            params,
+            &mut thread_rng(),
+        )?;
+        let min_fee_sat = min_fee(selection.weight);
+        if selection.fee_sat >= min_fee_sat {
+            return Ok((selection, min_fee_sat));
+        }
+        params.feerate = min_fee_sat as f64 * 4.0 / selection.weight as f64;
+    }
+    Err(AppError::BadRequest(
+        "Could not find inputs paying the fee the replacement rules require".to_string(),
+    ))
+}
+
+fn coin(input: &PsbtInput) -> Coin {
+    Coin {
+        txid: input.txid.clone(),
+        vout: input.vout,
+        value_sat: input.value_sat,
+        input_weight: coin_selection::input_weight(input.script_type),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::coin_selection::fixtures::{coin, params};
+
+    /// Evicted fees of 3_000 sat plus 1 sat/vB for the replacement's own size
+    fn min_fee(weight: u64) -> u64 {
+        3_000 + coin_selection::fee_for_weight(weight, 1.0)
+    }
+
+    #[test]
+    fn keeps_target_feerate_when_it_pays_enough() {
+        let mut params = params(40_000, 30.0);
+        let (selection, min_fee_sat) = replacement_selection(&[], &[coin(1, 50_000)], &mut params, min_fee).unwrap();
+
+        assert_eq!(params.feerate, 30.0);
+        assert_eq!(min_fee_sat, min_fee(selection.weight));
+        assert!(selection.fee_sat >= min_fee_sat);
+    }
+
+    #[test]
+    fn raises_feerate_to_meet_absolute_fee() {
+        // At 2 sat/vB the original's input covers the payment, but not the evicted fees
+        let mut params = params(4_000, 2.0);
+        let (selection, min_fee_sat) =
+            replacement_selection(&[coin(2, 20_000)], &[coin(1, 6_000)], &mut params, min_fee).unwrap();
+
+        assert!(params.feerate > 2.0);
+        assert_eq!(selection.coins[0].txid, coin(1, 0).txid);
+        assert_eq!(selection.coins.len(), 2);
+        assert_eq!(min_fee_sat, min_fee(selection.weight));
+        assert!(selection.fee_sat >= min_fee_sat);
+    }
+
+    #[test]
+    fn gives_up_without_inputs_for_absolute_fee() {
+        let mut params = params(4_000, 2.0);
+        let err = replacement_selection(&[], &[coin(1, 6_000)], &mut params, min_fee).unwrap_err();
+
+        assert!(matches!(err, AppError::BadRequest(_)));
+    }
+}
@@ -0,0 +1,168 @@
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::config::constants::env_keys;
+use crate::config::env::var_or;
+use crate::db::{HeaderRepository, SqliteHeaderRepository};
+use crate::domain::header::{BlockHeader, HeaderIndexStatus};
+use crate::errors::AppError;
+use crate::services::bitcoin_rpc::{BitcoinRpc, BlockHeaderRaw};
+use crate::state::app_state::AppState;
+
+/// Number of headers requested per JSON-RPC batch
+const BATCH_SIZE: u64 = 500;
+
+/// Spawn the background task that keeps the local header index in sync with
+/// the active node. It polls on an interval and also wakes up on chain events.
+pub fn spawn(state: AppState) {
+    let poll_secs: u64 = var_or(env_keys::HEADER_INDEX_POLL_SECS, 10);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(poll_secs.max(1)));
+        let mut events = state.chain_events.subscribe();
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                event = events.recv() => {
+                    if let Err(RecvError::Closed) = event {
+                        return;
+                    }
+                }
+            }
+
+            match sync_once(&state).await {
+                Ok(0) => {}
+                Ok(added) => println!("📚 Header index: {} new headers", added),
+                Err(e) => eprintln!("⚠️  Header index sync failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Roll back any headers that diverge from the active node, then append new
+/// headers up to the node's tip. Returns the number of headers added.
+pub async fn sync_once(state: &AppState) -> Result<u64, AppError> {
+    let rpc = state.active_rpc().await?;
+    let repo = SqliteHeaderRepository::new(state.db_pool.clone());
+    let node_height = rpc.get_block_count().await?;
+
+    if let Some(tip) = repo.tip().await?
+        && let Some(fork) = find_divergence(&rpc, &repo, tip.height, node_height).await?
+    {
+        let removed = repo.delete_from_height(fork).await?;
+        println!("🔀 Header index: rolled back {} headers from height {}", removed, fork);
+    }
+
+    let tip = repo.tip().await?;
+    let mut next = tip.as_ref().map_or(0, |t| t.height + 1);
+    let mut previous_hash = tip.map(|t| t.hash);
+    let mut added = 0;
+
+    while next <= node_height {
+        let end = (next + BATCH_SIZE - 1).min(node_height);
+        let heights: Vec<u64> = (next..=end).collect();
+        let hashes = rpc.get_block_hashes(&heights).await?;
+        let raw_headers = rpc.get_block_headers(&hashes).await?;
+
+        // Only keep the prefix that links onto what we already have; a reorg
+        // racing this batch will be rolled back on the next round.
+        let mut batch = Vec::with_capacity(raw_headers.len());
+        for raw in raw_headers {
+            if raw.previousblockhash != previous_hash {
+                break;
+            }
+            previous_hash = Some(raw.hash.clone());
+            batch.push(header_from_raw(raw));
+        }
+
+        let complete = batch.len() as u64 == end - next + 1;
+        repo.insert_batch(&batch).await?;
+        added += batch.len() as u64;
+
+        if !complete {
+            break;
+        }
+        next = end + 1;
+    }
+
+    Ok(added)
+}
+
+pub async fn status(state: &AppState) -> Result<HeaderIndexStatus, AppError> {
+    let rpc = state.active_rpc().await?;
+    let repo = SqliteHeaderRepository::new(state.db_pool.clone());
+
+    let node_height = rpc.get_block_count().await?;
+    let tip = repo.tip().await?;
+
+    Ok(HeaderIndexStatus {
+        synced: tip.as_ref().is_some_and(|t| t.height == node_height),
+        indexed_height: tip.as_ref().map(|t| t.height),
+        indexed_tip_hash: tip.map(|t| t.hash),
+        node_height,
+    })
+}
+
+/// Find the lowest indexed height whose hash no longer matches the node.
+/// A mismatch at some height implies a mismatch at every height above it,
+/// so a binary search keeps this to O(log n) RPC calls.
+async fn find_divergence(
+    rpc: &BitcoinRpc,
+    repo: &SqliteHeaderRepository,
+    tip_height: u64,
+    node_height: u64,
+) -> Result<Option<u64>, AppError> {
+    if matches_node(rpc, repo, tip_height, node_height).await? {
+        return Ok(None);
+    }
+    if !matches_node(rpc, repo, 0, node_height).await? {
+        // Different genesis: the active node is on another network
+        return Ok(Some(0));
+    }
+
+    let (mut good, mut bad) = (0, tip_height);
+    while bad - good > 1 {
+        let mid = good + (bad - good) / 2;
+        if matches_node(rpc, repo, mid, node_height).await? {
+            good = mid;
+        } else {
+            bad = mid;
+        }
+    }
+
+    Ok(Some(bad))
+}
+
+async fn matches_node(
+    rpc: &BitcoinRpc,
+    repo: &SqliteHeaderRepository,
+    height: u64,
+    node_height: u64,
+) -> Result<bool, AppError> {
+    if height > node_height {
+        return Ok(false);
+    }
+
+    let stored = repo.get_by_height(height).await?;
+    let node_hash = rpc.get_block_hash(height).await?;
+
+    Ok(stored.is_some_and(|h| h.hash == node_hash))
+}
+
+fn header_from_raw(raw: BlockHeaderRaw) -> BlockHeader {
+    BlockHeader {
+        height: raw.height,
+        hash: raw.hash,
+        previous_hash: raw.previousblockhash,
+        version: raw.version,
+        merkle_root: raw.merkleroot,
+        time: raw.time,
+        median_time: raw.mediantime,
+        nonce: raw.nonce,
+        bits: raw.bits,
+        difficulty: raw.difficulty,
+        chainwork: raw.chainwork,
+        tx_count: raw.n_tx,
+    }
+}
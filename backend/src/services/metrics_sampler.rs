@@ -0,0 +1,77 @@
+use chrono::Utc;
+use std::time::Duration;
+
+use crate::config::constants::env_keys;
+use crate::config::env::var_or;
+use crate::db::{
+    MetricsRepository, NodeConfig, NodeConfigRepository, SqliteMetricsRepository,
+    SqliteNodeConfigRepository,
+};
+use crate::domain::metrics::MetricSample;
+use crate::errors::AppError;
+use crate::services::bitcoin_rpc::BitcoinRpc;
+use crate::state::app_state::AppState;
+
+/// Spawn the background task that samples every configured node and prunes
+/// samples older than the retention window.
+pub fn spawn(state: AppState) {
+    let interval_secs: u64 = var_or(env_keys::METRICS_SAMPLE_INTERVAL_SECS, 60);
+    let retention_days: i64 = var_or(env_keys::METRICS_RETENTION_DAYS, 30);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs.max(1)));
+        let repo = SqliteMetricsRepository::new(state.db_pool.clone());
+
+        loop {
+            interval.tick().await;
+
+            let nodes = match SqliteNodeConfigRepository::new(state.db_pool.clone()).list().await {
+                Ok(nodes) => nodes,
+                Err(e) => {
+                    eprintln!("⚠️  Metrics sampler could not load node configurations: {}", e);
+                    continue;
+                }
+            };
+
+            for node in nodes {
+                let sample = match sample_node(&node).await {
+                    Ok(sample) => sample,
+                    // Unreachable nodes simply leave a gap in the series
+                    Err(_) => continue,
+                };
+                if let Err(e) = repo.record_sample(&sample).await {
+                    eprintln!("⚠️  Metrics sampler failed to store sample for '{}': {}", node.name, e);
+                }
+            }
+
+            let cutoff = Utc::now().timestamp() - retention_days * 86_400;
+            if let Err(e) = repo.prune_before(cutoff).await {
+                eprintln!("⚠️  Metrics sampler failed to prune old samples: {}", e);
+            }
+        }
+    });
+}
+
+async fn sample_node(node: &NodeConfig) -> Result<MetricSample, AppError> {
+    let rpc = BitcoinRpc::from_node_config(node);
+
+    let (chain, mempool, totals, peer_count) = tokio::try_join!(
+        rpc.get_blockchain_info(),
+        rpc.get_mempool_info(),
+        rpc.get_net_totals(),
+        rpc.get_connection_count(),
+    )?;
+
+    Ok(MetricSample {
+        node_id: node.id.clone(),
+        sampled_at: Utc::now().timestamp(),
+        block_height: chain.blocks,
+        headers: chain.headers,
+        mempool_size: mempool.size,
+        mempool_bytes: mempool.bytes,
+        peer_count,
+        bytes_recv: totals.totalbytesrecv,
+        bytes_sent: totals.totalbytessent,
+        verification_progress: chain.verificationprogress,
+    })
+}
@@ -1 +1,26 @@
+pub mod address_indexer;
+pub mod balance;
+pub mod bitcoin_conf;
 pub mod bitcoin_rpc;
+pub mod block_stats;
+pub mod chain_monitor;
+pub mod coin_selection;
+pub mod deployments;
+pub mod descriptors;
+pub mod epoch;
+pub mod fee_bump;
+pub mod header_indexer;
+pub mod metrics_sampler;
+pub mod node_process;
+pub mod node_wallets;
+pub mod psbt;
+pub mod regtest;
+pub mod scenario;
+pub mod search;
+pub mod spend;
+pub mod transaction_history;
+pub mod utxo_scan;
+pub mod utxoset;
+pub mod wallet;
+pub mod wallet_addresses;
+pub mod wallet_keyring;
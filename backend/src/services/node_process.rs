@@ -0,0 +1,456 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio::process::{Child, Command};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::config::managed_node::ManagedNodeConfig;
+use crate::db::{NewNodeConfig, NodeConfigRepository, SqliteNodeConfigRepository};
+use crate::domain::managed_node::{
+    LogSource, ManagedNetwork, ManagedNodeLogs, ManagedNodeState, ManagedNodeStatus,
+};
+use crate::errors::AppError;
+use crate::services::bitcoin_rpc::BitcoinRpc;
+
+/// Written next to bitcoin.conf so restarts reuse the same credentials and ports
+const CREDENTIALS_FILE: &str = "ohla-managed.json";
+const STDOUT_LOG_FILE: &str = "stdout.log";
+/// Seconds to wait for bitcoind to exit after the `stop` RPC before killing it
+const STOP_TIMEOUT_SECS: u64 = 30;
+/// Only the end of a log file is read when tailing
+const MAX_TAIL_BYTES: u64 = 512 * 1024;
+
+#[derive(Serialize, Deserialize)]
+struct ManagedCredentials {
+    rpc_user: String,
+    rpc_password: String,
+    rpc_port: u16,
+    p2p_port: u16,
+}
+
+struct Inner {
+    status: ManagedNodeStatus,
+    child: Option<Child>,
+    rpc: Option<Arc<BitcoinRpc>>,
+}
+
+impl Inner {
+    /// Notice a process that exited on its own
+    fn refresh(&mut self) {
+        if let Some(child) = self.child.as_mut()
+            && let Ok(Some(exit)) = child.try_wait()
+        {
+            self.child = None;
+            self.rpc = None;
+            self.status.state = ManagedNodeState::Exited;
+            self.status.pid = None;
+            self.status.exit_code = exit.code();
+        }
+    }
+}
+
+/// Spawns and supervises a local bitcoind for regtest/signet development.
+/// One managed node runs at a time; its datadir lives under `MANAGED_NODE_DATA_DIR/<network>`.
+pub struct NodeProcessManager {
+    config: ManagedNodeConfig,
+    inner: Mutex<Inner>,
+}
+
+impl NodeProcessManager {
+    pub fn new(config: ManagedNodeConfig) -> Self {
+        Self {
+            config,
+            inner: Mutex::new(Inner {
+                status: ManagedNodeStatus {
+                    state: ManagedNodeState::Stopped,
+                    network: None,
+                    pid: None,
+                    data_dir: None,
+                    rpc_url: None,
+                    node_config_id: None,
+                    started_at: None,
+                    exit_code: None,
+                    error: None,
+                },
+                child: None,
+                rpc: None,
+            }),
+        }
+    }
+
+    pub async fn status(&self) -> ManagedNodeStatus {
+        let mut inner = self.inner.lock().await;
+        inner.refresh();
+        inner.status.clone()
+    }
+
+    /// Start bitcoind, wait for RPC warmup and register a matching node configuration.
+    /// The configuration is activated when `activate` is set or no node is active yet.
+    pub async fn start(
+        self: &Arc<Self>,
+        db_pool: SqlitePool,
+        network: ManagedNetwork,
+        activate: bool,
+    ) -> Result<ManagedNodeStatus, AppError> {
+        let binary = self.config.bitcoind_path.clone().ok_or_else(|| {
+            AppError::ServiceUnavailable(
+                "The managed node is disabled; set BITCOIND_PATH to enable it".to_string(),
+            )
+        })?;
+
+        let data_dir = self.data_dir(network);
+        let (rpc, rpc_url, credentials) = {
+            let mut inner = self.inner.lock().await;
+            inner.refresh();
+            if inner.child.is_some() {
+                return Err(AppError::Conflict(
+                    "The managed node is already running; stop it first".to_string(),
+                ));
+            }
+
+            let credentials = prepare_data_dir(&data_dir, network).await?;
+            let stdout_log = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(data_dir.join(STDOUT_LOG_FILE))
+                .map_err(|e| AppError::Internal(format!("Failed to open stdout log: {}", e)))?;
+            let stderr_log = stdout_log
+                .try_clone()
+                .map_err(|e| AppError::Internal(format!("Failed to open stdout log: {}", e)))?;
+
+            let child = Command::new(&binary)
+                .arg(format!("-datadir={}", data_dir.display()))
+                .stdin(Stdio::null())
+                .stdout(stdout_log)
+                .stderr(stderr_log)
+                .kill_on_drop(true)
+                .spawn()
+                .map_err(|e| {
+                    AppError::Internal(format!("Failed to start {}: {}", binary.display(), e))
+                })?;
+
+            let rpc_url = format!("http://127.0.0.1:{}", credentials.rpc_port);
+            let rpc = Arc::new(BitcoinRpc::new(
+                rpc_url.clone(),
+                credentials.rpc_user.clone(),
+                credentials.rpc_password.clone(),
+            ));
+
+            println!("🟢 Started managed bitcoind ({}), pid {:?}", network.as_str(), child.id());
+            inner.status = ManagedNodeStatus {
+                state: ManagedNodeState::Starting,
+                network: Some(network),
+                pid: child.id(),
+                data_dir: Some(data_dir.display().to_string()),
+                rpc_url: Some(rpc_url.clone()),
+                node_config_id: None,
+                started_at: Some(Utc::now().timestamp()),
+                exit_code: None,
+                error: None,
+            };
+            inner.child = Some(child);
+            inner.rpc = Some(rpc.clone());
+
+            (rpc, rpc_url, credentials)
+        };
+
+        if let Err(e) = self.wait_for_warmup(&rpc).await {
+            self.abort_start(&e).await;
+            return Err(e);
+        }
+
+        let node_config_id = match register_node_config(db_pool, network, &rpc_url, &credentials, activate).await {
+            Ok(id) => id,
+            Err(e) => {
+                self.abort_start(&e).await;
+                return Err(e);
+            }
+        };
+
+        let status = {
+            let mut inner = self.inner.lock().await;
+            inner.status.state = ManagedNodeState::Running;
+            inner.status.node_config_id = Some(node_config_id);
+            inner.status.clone()
+        };
+        println!("✅ Managed bitcoind ready at {}", rpc_url);
+
+        let manager = Arc::clone(self);
+        tokio::spawn(async move { manager.supervise().await });
+
+        Ok(status)
+    }
+
+    /// Stop the node with the `stop` RPC, killing it if it does not exit in time
+    pub async fn stop(&self) -> Result<ManagedNodeStatus, AppError> {
+        let (mut child, rpc) = {
+            let mut inner = self.inner.lock().await;
+            inner.refresh();
+            match inner.status.state {
+                ManagedNodeState::Running => {}
+                ManagedNodeState::Starting => {
+                    return Err(AppError::Conflict(
+                        "The managed node is still starting".to_string(),
+                    ));
+                }
+                _ => {
+                    return Err(AppError::Conflict("The managed node is not running".to_string()));
+                }
+            }
+            inner.status.state = ManagedNodeState::Stopping;
+            match (inner.child.take(), inner.rpc.take()) {
+                (Some(child), Some(rpc)) => (child, rpc),
+                _ => return Err(AppError::Internal("Managed node state is inconsistent".to_string())),
+            }
+        };
+
+        if let Err(e) = rpc.stop().await {
+            eprintln!("⚠️ stop RPC to managed bitcoind failed: {}", e);
+        }
+
+        let exit_code = match tokio::time::timeout(Duration::from_secs(STOP_TIMEOUT_SECS), child.wait()).await {
+            Ok(Ok(exit)) => exit.code(),
+            _ => {
+                eprintln!("⚠️ Managed bitcoind did not exit in time, killing it");
+                let _ = child.kill().await;
+                None
+            }
+        };
+        println!("🔴 Managed bitcoind stopped");
+
+        let mut inner = self.inner.lock().await;
+        inner.status.state = ManagedNodeState::Stopped;
+        inner.status.pid = None;
+        inner.status.exit_code = exit_code;
+        Ok(inner.status.clone())
+    }
+
+    /// Stop the node if it is running; used on server shutdown
+    pub async fn shutdown(&self) {
+        if self.status().await.state == ManagedNodeState::Running
+            && let Err(e) = self.stop().await
+        {
+            eprintln!("⚠️ Failed to stop managed bitcoind: {}", e);
+        }
+    }
+
+    /// Last `lines` lines of the captured stdout or of debug.log.
+    /// Defaults to the network of the current (or last) run.
+    pub async fn logs(
+        &self,
+        source: LogSource,
+        network: Option<ManagedNetwork>,
+        lines: usize,
+    ) -> Result<ManagedNodeLogs, AppError> {
+        let network = match network {
+            Some(network) => network,
+            None => self.status().await.network.unwrap_or(ManagedNetwork::Regtest),
+        };
+        let data_dir = self.data_dir(network);
+        let path = match source {
+            LogSource::Stdout => data_dir.join(STDOUT_LOG_FILE),
+            LogSource::Debug => data_dir.join(network.as_str()).join("debug.log"),
+        };
+
+        let lines = tail_lines(&path, lines).await.map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => {
+                AppError::NotFound(format!("Log file {} does not exist yet", path.display()))
+            }
+            _ => AppError::Internal(format!("Failed to read {}: {}", path.display(), e)),
+        })?;
+
+        Ok(ManagedNodeLogs {
+            source,
+            path: path.display().to_string(),
+            lines,
+        })
+    }
+
+    /// Kill and reap a node that failed to start, leaving it stopped with the error
+    async fn abort_start(&self, error: &AppError) {
+        let mut inner = self.inner.lock().await;
+        if let Some(mut child) = inner.child.take() {
+            let _ = child.kill().await;
+        }
+        inner.rpc = None;
+        inner.status.pid = None;
+        inner.status.error = Some(error.to_string());
+        if inner.status.state == ManagedNodeState::Starting {
+            inner.status.state = ManagedNodeState::Stopped;
+        }
+    }
+
+    fn data_dir(&self, network: ManagedNetwork) -> PathBuf {
+        self.config.data_dir.join(network.as_str())
+    }
+
+    /// Poll RPC until bitcoind answers (it replies -28 while warming up)
+    async fn wait_for_warmup(&self, rpc: &BitcoinRpc) -> Result<(), AppError> {
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(self.config.startup_timeout_secs);
+
+        loop {
+            {
+                let mut inner = self.inner.lock().await;
+                inner.refresh();
+                if inner.status.state == ManagedNodeState::Exited {
+                    return Err(AppError::Internal(format!(
+                        "bitcoind exited during startup (exit code {:?}); see the stdout log",
+                        inner.status.exit_code
+                    )));
+                }
+            }
+
+            if rpc.get_blockchain_info().await.is_ok() {
+                return Ok(());
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(AppError::ServiceUnavailable(format!(
+                    "bitcoind did not finish warmup within {} seconds",
+                    self.config.startup_timeout_secs
+                )));
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+    }
+
+    /// Report the process exiting on its own while it is supposed to be running
+    async fn supervise(&self) {
+        let mut interval = tokio::time::interval(Duration::from_secs(5));
+        loop {
+            interval.tick().await;
+
+            let mut inner = self.inner.lock().await;
+            if inner.status.state != ManagedNodeState::Running {
+                return;
+            }
+            inner.refresh();
+            if inner.status.state == ManagedNodeState::Exited {
+                eprintln!(
+                    "❌ Managed bitcoind exited unexpectedly (exit code {:?})",
+                    inner.status.exit_code
+                );
+                return;
+            }
+        }
+    }
+}
+
+/// Create the datadir and (re)write bitcoin.conf. Credentials and ports are
+/// generated on first use and reused afterwards so the node config stays valid.
+async fn prepare_data_dir(data_dir: &Path, network: ManagedNetwork) -> Result<ManagedCredentials, AppError> {
+    let io_error = |e: std::io::Error| AppError::Internal(format!("Failed to prepare {}: {}", data_dir.display(), e));
+
+    tokio::fs::create_dir_all(data_dir).await.map_err(io_error)?;
+
+    let credentials_path = data_dir.join(CREDENTIALS_FILE);
+    let credentials = match tokio::fs::read_to_string(&credentials_path).await {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| AppError::Internal(format!("Invalid {}: {}", credentials_path.display(), e)))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            let credentials = ManagedCredentials {
+                rpc_user: "ohla".to_string(),
+                rpc_password: format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple()),
+                rpc_port: free_port().map_err(io_error)?,
+                p2p_port: free_port().map_err(io_error)?,
+            };
+            let content = serde_json::to_string_pretty(&credentials)
+                .map_err(|e| AppError::Internal(e.to_string()))?;
+            tokio::fs::write(&credentials_path, content).await.map_err(io_error)?;
+            credentials
+        }
+        Err(e) => return Err(io_error(e)),
+    };
+
+    let name = network.as_str();
+    let mut conf = format!(
+        "# Generated for the managed {name} node; rewritten on every start\n\
+         {name}=1\n\
+         server=1\n\
+         \n\
+         [{name}]\n\
+         rpcuser={}\n\
+         rpcpassword={}\n\
+         rpcbind=127.0.0.1\n\
+         rpcallowip=127.0.0.1\n\
+         rpcport={}\n\
+         port={}\n",
+        credentials.rpc_user, credentials.rpc_password, credentials.rpc_port, credentials.p2p_port,
+    );
+    if network == ManagedNetwork::Regtest {
+        // Regtest has no fee estimates; without a fallback fee wallet sends fail
+        conf.push_str("fallbackfee=0.0001\n");
+    }
+    tokio::fs::write(data_dir.join("bitcoin.conf"), conf)
+        .await
+        .map_err(io_error)?;
+
+    Ok(credentials)
+}
+
+/// Ask the OS for a currently unused local port
+fn free_port() -> std::io::Result<u16> {
+    Ok(std::net::TcpListener::bind("127.0.0.1:0")?.local_addr()?.port())
+}
+
+/// Reuse the node configuration pointing at the managed node, or create one
+async fn register_node_config(
+    db_pool: SqlitePool,
+    network: ManagedNetwork,
+    rpc_url: &str,
+    credentials: &ManagedCredentials,
+    activate: bool,
+) -> Result<String, AppError> {
+    let repo = SqliteNodeConfigRepository::new(db_pool);
+
+    let existing = repo.list().await?.into_iter().find(|config| {
+        config.rpc_url == rpc_url
+            && config.rpc_user == credentials.rpc_user
+            && config.rpc_password == credentials.rpc_password
+    });
+    let config = match existing {
+        Some(config) => config,
+        None => {
+            repo.create(NewNodeConfig {
+                name: format!("Managed {} node", network.as_str()),
+                rpc_url: rpc_url.to_string(),
+                rpc_user: credentials.rpc_user.clone(),
+                rpc_password: credentials.rpc_password.clone(),
+                network: network.as_str().to_string(),
+            })
+            .await?
+        }
+    };
+
+    if activate || repo.get_active().await?.is_none() {
+        repo.set_active(&config.id).await?;
+    }
+
+    Ok(config.id)
+}
+
+/// Read the last `lines` lines of a file, looking at most at its final `MAX_TAIL_BYTES`
+async fn tail_lines(path: &Path, lines: usize) -> std::io::Result<Vec<String>> {
+    let mut file = tokio::fs::File::open(path).await?;
+    let len = file.metadata().await?.len();
+    let start = len.saturating_sub(MAX_TAIL_BYTES);
+    file.seek(std::io::SeekFrom::Start(start)).await?;
+
+    let mut buffer = Vec::new();
+    file.read_to_end(&mut buffer).await?;
+    let text = String::from_utf8_lossy(&buffer);
+
+    let mut all: Vec<&str> = text.lines().collect();
+    // The first line is likely cut in half when reading from the middle
+    if start > 0 && !all.is_empty() {
+        all.remove(0);
+    }
+    let skip = all.len().saturating_sub(lines);
+
+    Ok(all[skip..].iter().map(|line| line.to_string()).collect())
+}
@@ -0,0 +1,331 @@
+use chrono::Utc;
+use serde_json::json;
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, Notify};
+
+use crate::config::constants::env_keys;
+use crate::config::env::var_or;
+use crate::db::{
+    NodeConfigRepository, NodeWalletRepository, SqliteNodeConfigRepository, SqliteNodeWalletRepository,
+    SqliteWalletRepository, WalletRepository,
+};
+use crate::domain::wallet::{AddressChain, NodeWalletStatus, NodeWalletSync, RescanProgress, Wallet};
+use crate::errors::AppError;
+use crate::services::bitcoin_rpc::{BitcoinRpc, WalletScanningRaw};
+use crate::services::wallet;
+use crate::state::app_state::AppState;
+
+/// Node id recorded for the node configured through environment variables
+const ENV_NODE_ID: &str = "env";
+
+/// Addresses Core keeps derived ahead on each descriptor; it tops the range
+/// up by itself as addresses get used
+const DESCRIPTOR_RANGE_END: u32 = 999;
+
+/// Name of the Core wallet mirroring an ohla wallet
+pub fn node_wallet_name(wallet_id: &str) -> String {
+    format!("ohla-{}", wallet_id)
+}
+
+/// Tracks `importdescriptors` calls in flight. Each blocks until its rescan
+/// finishes, which takes hours on mainnet from an early timestamp.
+#[derive(Default)]
+pub struct NodeWalletManager {
+    importing: Mutex<HashSet<String>>,
+    wake: Notify,
+}
+
+impl NodeWalletManager {
+    /// Run a sync pass now instead of waiting for the next interval
+    pub fn wake(&self) {
+        self.wake.notify_one();
+    }
+
+    pub async fn is_importing(&self, wallet_id: &str) -> bool {
+        self.importing.lock().await.contains(wallet_id)
+    }
+
+    /// Import a wallet's descriptors into its node wallet in the background
+    async fn start_import(
+        self: &Arc<Self>,
+        rpc: &BitcoinRpc,
+        db_pool: SqlitePool,
+        wallet: &Wallet,
+        node_id: &str,
+        rescan_from: i64,
+    ) -> Result<(), AppError> {
+        let requests = wallet::wallet_descriptors(wallet)?
+            .into_iter()
+            .map(|d| {
+                json!({
+                    "desc": d.descriptor,
+                    "timestamp": rescan_from,
+                    "active": true,
+                    "internal": d.chain == AddressChain::Internal,
+                    "range": [0, DESCRIPTOR_RANGE_END],
+                })
+            })
+            .collect();
+
+        if !self.importing.lock().await.insert(wallet.id.clone()) {
+            return Err(AppError::Conflict(format!(
+                "Descriptors of wallet '{}' are already being imported",
+                wallet.name
+            )));
+        }
+
+        let node_wallet = node_wallet_name(&wallet.id);
+        println!(
+            "🔁 Importing descriptors of wallet '{}' into node wallet '{}' (rescan from {})",
+            wallet.name, node_wallet, rescan_from
+        );
+
+        let manager = Arc::clone(self);
+        let rpc = rpc.for_wallet(&node_wallet);
+        let wallet_id = wallet.id.clone();
+        let node_id = node_id.to_string();
+        tokio::spawn(async move {
+            let (node_id, error) = match rpc.import_descriptors(requests).await {
+                Ok(results) => {
+                    for warning in results.iter().flat_map(|r| &r.warnings) {
+                        eprintln!("⚠️  Node wallet '{}': {}", node_wallet, warning);
+                    }
+                    let error = results.into_iter().find(|r| !r.success).map(|r| {
+                        r.error
+                            .map_or_else(|| "Descriptor import failed".to_string(), |e| e.message)
+                    });
+                    (Some(node_id), error)
+                }
+                // Includes an aborted rescan: the descriptors stay imported
+                Err(AppError::BitcoinRpcError { message, .. }) => (Some(node_id), Some(message)),
+                // Never reached the node; the next pass retries
+                Err(e) => (None, Some(e.to_string())),
+            };
+
+            match &error {
+                None => println!("✅ Node wallet '{}' synced", node_wallet),
+                Some(e) => eprintln!("⚠️  Node wallet '{}' import failed: {}", node_wallet, e),
+            }
+
+            let repo = SqliteNodeWalletRepository::new(db_pool);
+            if let Err(e) = repo
+                .record_import(&wallet_id, node_id.as_deref(), Utc::now().timestamp(), error.as_deref())
+                .await
+            {
+                eprintln!("⚠️  Failed to record node wallet import: {}", e);
+            }
+            manager.importing.lock().await.remove(&wallet_id);
+        });
+
+        Ok(())
+    }
+}
+
+/// Spawn the background task that keeps every wallet imported into the
+/// active node. A pass runs on an interval and whenever a wallet is added.
+pub fn spawn(state: AppState) {
+    let interval_secs: u64 = var_or(env_keys::NODE_WALLET_SYNC_SECS, 60);
+    if interval_secs == 0 {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = state.node_wallets.wake.notified() => {}
+            }
+
+            if let Err(e) = sync_all(&state).await {
+                eprintln!("⚠️  Node wallet sync failed: {}", e);
+            }
+        }
+    });
+}
+
+/// Load every wallet's node wallet on the active node. Descriptors are
+/// imported when the node wallet is new or the active node changed since
+/// the last import.
+pub async fn sync_all(state: &AppState) -> Result<(), AppError> {
+    let (rpc, node_id) = active_node(state).await?;
+    let chain = rpc.get_blockchain_info().await?.chain;
+    let loaded = rpc.list_wallets().await?;
+    let repo = SqliteNodeWalletRepository::new(state.db_pool.clone());
+
+    let wallets = SqliteWalletRepository::new(state.db_pool.clone()).list().await?;
+    for wallet in wallets.iter().filter(|w| w.network == chain) {
+        if state.node_wallets.is_importing(&wallet.id).await {
+            continue;
+        }
+
+        let sync = enrolled(&repo, &wallet.id).await?;
+        let created = match ensure_loaded(&rpc, &sync.node_wallet, &loaded).await {
+            Ok(created) => created,
+            Err(e) => {
+                eprintln!("⚠️  Node wallet '{}' unavailable: {}", sync.node_wallet, e);
+                repo.record_import(&wallet.id, None, Utc::now().timestamp(), Some(&e.to_string()))
+                    .await?;
+                continue;
+            }
+        };
+
+        if created || sync.node_id.as_deref() != Some(node_id.as_str()) {
+            match state
+                .node_wallets
+                .start_import(&rpc, state.db_pool.clone(), wallet, &node_id, sync.rescan_from)
+                .await
+            {
+                Ok(()) | Err(AppError::Conflict(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Register a new wallet for node sync and trigger a pass
+pub async fn enrol(state: &AppState, wallet_id: &str, rescan_from: i64) -> Result<(), AppError> {
+    SqliteNodeWalletRepository::new(state.db_pool.clone())
+        .enrol(wallet_id, &node_wallet_name(wallet_id), rescan_from)
+        .await?;
+    state.node_wallets.wake();
+    Ok(())
+}
+
+/// Sync state of a wallet on the active node, with rescan progress
+pub async fn status(state: &AppState, wallet: &Wallet) -> Result<NodeWalletStatus, AppError> {
+    let (rpc, node_id) = active_node(state).await?;
+    let repo = SqliteNodeWalletRepository::new(state.db_pool.clone());
+    let sync = enrolled(&repo, &wallet.id).await?;
+
+    let scanning = match rpc.for_wallet(&sync.node_wallet).get_wallet_info().await {
+        Ok(info) => match info.scanning {
+            Some(WalletScanningRaw::Active { duration, progress }) => Some(RescanProgress {
+                duration_secs: duration,
+                progress,
+            }),
+            _ => None,
+        },
+        // -18: wallet not loaded
+        Err(AppError::BitcoinRpcError { code: -18, .. }) => None,
+        Err(e) => return Err(e),
+    };
+
+    Ok(NodeWalletStatus {
+        synced: sync.node_id.as_deref() == Some(node_id.as_str()) && sync.last_error.is_none(),
+        importing: state.node_wallets.is_importing(&wallet.id).await,
+        wallet_id: sync.wallet_id,
+        node_wallet: sync.node_wallet,
+        active_node_id: node_id,
+        scanning,
+        rescan_from: sync.rescan_from,
+        synced_at: sync.synced_at,
+        last_error: sync.last_error,
+    })
+}
+
+/// Import the wallet's descriptors into the active node again, optionally
+/// from a different timestamp. Descriptors already imported are only rescanned.
+pub async fn resync(
+    state: &AppState,
+    wallet: &Wallet,
+    rescan_from: Option<i64>,
+) -> Result<NodeWalletStatus, AppError> {
+    let (rpc, node_id) = active_node(state).await?;
+    let chain = rpc.get_blockchain_info().await?.chain;
+    if wallet.network != chain {
+        return Err(AppError::BadRequest(format!(
+            "Wallet '{}' is on '{}' but the active node is on '{}'",
+            wallet.name, wallet.network, chain
+        )));
+    }
+
+    let repo = SqliteNodeWalletRepository::new(state.db_pool.clone());
+    let mut sync = enrolled(&repo, &wallet.id).await?;
+    if let Some(rescan_from) = rescan_from {
+        if rescan_from < 0 {
+            return Err(AppError::BadRequest("rescan_from must not be negative".to_string()));
+        }
+        repo.set_rescan_from(&wallet.id, rescan_from).await?;
+        sync.rescan_from = rescan_from;
+    }
+
+    let loaded = rpc.list_wallets().await?;
+    ensure_loaded(&rpc, &sync.node_wallet, &loaded).await?;
+    state
+        .node_wallets
+        .start_import(&rpc, state.db_pool.clone(), wallet, &node_id, sync.rescan_from)
+        .await?;
+
+    status(state, wallet).await
+}
+
+/// Abort the node wallet's running rescan. The descriptors stay imported;
+/// blocks not rescanned yet are picked up by a later resync.
+pub async fn abort_rescan(state: &AppState, wallet: &Wallet) -> Result<NodeWalletStatus, AppError> {
+    let (rpc, _) = active_node(state).await?;
+    let node_wallet = node_wallet_name(&wallet.id);
+
+    match rpc.for_wallet(&node_wallet).abort_rescan().await {
+        Ok(true) => println!("⏹️  Aborted rescan of node wallet '{}'", node_wallet),
+        Ok(false) => {
+            return Err(AppError::Conflict(format!(
+                "Node wallet '{}' is not rescanning",
+                node_wallet
+            )));
+        }
+        Err(AppError::BitcoinRpcError { code: -18, .. }) => {
+            return Err(AppError::NotFound(format!(
+                "Node wallet '{}' is not loaded on the active node",
+                node_wallet
+            )));
+        }
+        Err(e) => return Err(e),
+    }
+
+    status(state, wallet).await
+}
+
+/// RPC client and id of the active node configuration
+async fn active_node(state: &AppState) -> Result<(Arc<BitcoinRpc>, String), AppError> {
+    let repo = SqliteNodeConfigRepository::new(state.db_pool.clone());
+
+    Ok(match repo.get_active().await? {
+        Some(config) => (Arc::new(BitcoinRpc::from_node_config(&config)), config.id),
+        None => (state.bitcoin.clone(), ENV_NODE_ID.to_string()),
+    })
+}
+
+/// Sync record of a wallet; wallets created before node sync rescan from genesis
+async fn enrolled(repo: &SqliteNodeWalletRepository, wallet_id: &str) -> Result<NodeWalletSync, AppError> {
+    repo.enrol(wallet_id, &node_wallet_name(wallet_id), 0).await?;
+    repo.get(wallet_id)
+        .await?
+        .ok_or_else(|| AppError::Internal(format!("Node sync record of wallet {} is missing", wallet_id)))
+}
+
+/// Load a node wallet, creating it blank and watch-only when it does not
+/// exist yet. Returns whether it was created.
+async fn ensure_loaded(rpc: &BitcoinRpc, name: &str, loaded: &[String]) -> Result<bool, AppError> {
+    if loaded.iter().any(|w| w == name) {
+        return Ok(false);
+    }
+
+    match rpc.load_wallet(name).await {
+        // -35: loaded meanwhile
+        Ok(()) | Err(AppError::BitcoinRpcError { code: -35, .. }) => Ok(false),
+        // -18: wallet file not found
+        Err(AppError::BitcoinRpcError { code: -18, .. }) => {
+            rpc.create_wallet(name, true, true).await?;
+            println!("👛 Created watch-only node wallet '{}'", name);
+            Ok(true)
+        }
+        Err(e) => Err(e),
+    }
+}
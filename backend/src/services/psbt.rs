@@ -0,0 +1,702 @@
+use base64::Engine;
+use bitcoin::absolute::LockTime;
+use bitcoin::bip32::{ChildNumber, DerivationPath, Fingerprint, KeySource, Xpriv, Xpub};
+use bitcoin::consensus::encode::{deserialize_hex, serialize_hex};
+use bitcoin::psbt::{Psbt, SigningKeys};
+use bitcoin::script::{Builder, PushBytes};
+use bitcoin::secp256k1::rand::{Rng, thread_rng};
+use bitcoin::secp256k1::{PublicKey, Secp256k1, Verification};
+use bitcoin::transaction::Version;
+use bitcoin::{CompressedPublicKey, OutPoint, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Txid, Witness};
+use chrono::Utc;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::db::{PendingSendRepository, SqlitePendingSendRepository};
+use crate::domain::coin_selection::SelectionAlgorithm;
+use crate::domain::send::{BroadcastResult, FinalizedTransaction, PendingSend, SendRecipient, SendStage};
+use crate::domain::utxo::WalletUtxo;
+use crate::domain::wallet::{AddressChain, ScriptType, Wallet, WalletAddress};
+use crate::errors::AppError;
+use crate::services::bitcoin_rpc::BitcoinRpc;
+use crate::services::coin_selection::Selection;
+use crate::services::spend::{self, Recipient, SpendOptions};
+use crate::services::{node_wallets, wallet, wallet_addresses};
+use crate::state::app_state::AppState;
+
+/// A wallet output to spend, with what a PSBT needs to have it signed
+#[derive(Debug, Clone)]
+pub struct PsbtInput {
+    pub txid: String,
+    pub vout: u32,
+    pub value_sat: u64,
+    pub script_pub_key: ScriptBuf,
+    pub script_type: ScriptType,
+    /// e.g. m/84'/0'/0'/0/5
+    pub derivation_path: String,
+}
+
+impl PsbtInput {
+    /// None for outputs whose type or derivation path is unknown
+    pub fn from_utxo(utxo: &WalletUtxo) -> Option<Self> {
+        Some(Self {
+            txid: utxo.txid.clone(),
+            vout: utxo.vout,
+            value_sat: utxo.value.sat,
+            script_pub_key: ScriptBuf::from_hex(&utxo.script_pub_key).ok()?,
+            script_type: spend::utxo_script_type(utxo)?,
+            derivation_path: utxo.derivation_path.clone()?,
+        })
+    }
+}
+
+pub fn encode_psbt(psbt: &Psbt) -> String {
+    base64::engine::general_purpose::STANDARD.encode(psbt.serialize())
+}
+
+pub fn decode_psbt(psbt: &str) -> Result<Psbt, AppError> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(psbt.trim())
+        .map_err(|e| AppError::BadRequest(format!("PSBT is not valid base64: {}", e)))?;
+    Psbt::deserialize(&bytes).map_err(|e| AppError::BadRequest(format!("Invalid PSBT: {}", e)))
+}
+
+/// Txid of a transaction with its signatures stripped. It matches the
+/// unsigned transaction of the PSBT the transaction was finalized from.
+pub fn unsigned_txid(tx: &Transaction) -> Txid {
+    let mut tx = tx.clone();
+    for input in &mut tx.input {
+        input.script_sig = ScriptBuf::new();
+        input.witness = Witness::new();
+    }
+    tx.compute_txid()
+}
+
+/// Select coins for a payment, build the unsigned PSBT and store it as a new
+/// send. Without an algorithm the selection with the lowest waste is used.
+pub async fn create(
+    state: &AppState,
+    wallet: &Wallet,
+    recipients: &[Recipient],
+    options: &SpendOptions,
+    algorithm: Option<SelectionAlgorithm>,
+    rbf: bool,
+) -> Result<PendingSend, AppError> {
+    let plan = spend::plan(state, wallet, recipients, options).await?;
+    let selection = match algorithm {
+        Some(algorithm) => plan.select(algorithm)?,
+        None => plan.select_best()?,
+    };
+
+    let inputs = selection
+        .coins
+        .iter()
+        .map(|coin| {
+            plan.utxo(coin)
+                .and_then(PsbtInput::from_utxo)
+                .ok_or_else(|| AppError::Internal(format!("Selected coin {}:{} is unknown", coin.txid, coin.vout)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let change = match selection.change_sat {
+        Some(change_sat) => {
+            let address = wallet_addresses::change_address(state, wallet, plan.change_script_type).await?;
+            Some((change_sat, address))
+        }
+        None => None,
+    };
+
+    let psbt = build(
+        state,
+        wallet,
+        &inputs,
+        plan.outputs.clone(),
+        change.as_ref().map(|(sat, address)| (*sat, address)),
+        rbf,
+    )
+    .await?;
+
+    let recipients = recipients
+        .iter()
+        .map(|r| SendRecipient {
+            address: r.address.trim().to_string(),
+            amount_sat: r.amount_sat,
+        })
+        .collect();
+    let change_address = change.map(|(_, address)| address.address);
+    let send = new_send(wallet, &psbt, &inputs, recipients, change_address, &selection, options.feerate);
+    SqlitePendingSendRepository::new(state.db_pool.clone()).create(&send).await?;
+
+    println!(
+        "📝 Built PSBT {} for wallet '{}' ({} inputs, fee {} sat)",
+        send.unsigned_txid,
+        wallet.name,
+        send.inputs.len(),
+        send.fee_sat
+    );
+    Ok(send)
+}
+
+/// A send in stage `created` for a freshly built PSBT; not yet stored
+pub fn new_send(
+    wallet: &Wallet,
+    psbt: &Psbt,
+    inputs: &[PsbtInput],
+    recipients: Vec<SendRecipient>,
+    change_address: Option<String>,
+    selection: &Selection,
+    feerate: f64,
+) -> PendingSend {
+    let now = Utc::now().timestamp();
+    PendingSend {
+        id: Uuid::new_v4().to_string(),
+        wallet_id: wallet.id.clone(),
+        stage: SendStage::Created,
+        unsigned_txid: psbt.unsigned_tx.compute_txid().to_string(),
+        psbt: encode_psbt(psbt),
+        tx_hex: None,
+        txid: None,
+        inputs: inputs.iter().map(|i| format!("{}:{}", i.txid, i.vout)).collect(),
+        recipients,
+        change_address,
+        change_sat: selection.change_sat,
+        fee_sat: selection.fee_sat,
+        feerate,
+        vsize: selection.vsize(),
+        algorithm: Some(selection.algorithm),
+        replaces: None,
+        last_error: None,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+/// Build an unsigned PSBT with the key origins a signer needs. The change
+/// output, if any, goes at a random position. The locktime is the current
+/// height to discourage fee sniping.
+pub async fn build(
+    state: &AppState,
+    wallet: &Wallet,
+    inputs: &[PsbtInput],
+    mut outputs: Vec<TxOut>,
+    change: Option<(u64, &WalletAddress)>,
+    rbf: bool,
+) -> Result<Psbt, AppError> {
+    let rpc = state.active_rpc().await?;
+    let node = rpc.for_wallet(&node_wallets::node_wallet_name(&wallet.id));
+    let height = rpc.get_block_count().await?;
+
+    let change_index = match change {
+        Some((change_sat, address)) => {
+            let index = thread_rng().gen_range(0..=outputs.len());
+            outputs.insert(
+                index,
+                TxOut {
+                    value: bitcoin::Amount::from_sat(change_sat),
+                    script_pubkey: ScriptBuf::from_hex(&address.script_pub_key)
+                        .map_err(|e| AppError::Internal(format!("Invalid change script: {}", e)))?,
+                },
+            );
+            Some(index)
+        }
+        None => None,
+    };
+
+    let sequence = if rbf {
+        Sequence::ENABLE_RBF_NO_LOCKTIME
+    } else {
+        Sequence::ENABLE_LOCKTIME_NO_RBF
+    };
+    let tx = Transaction {
+        version: Version::TWO,
+        lock_time: LockTime::from_height(height as u32).unwrap_or(LockTime::ZERO),
+        input: inputs
+            .iter()
+            .map(|input| {
+                Ok(TxIn {
+                    previous_output: OutPoint {
+                        txid: input
+                            .txid
+                            .parse()
+                            .map_err(|e| AppError::Internal(format!("Invalid txid {}: {}", input.txid, e)))?,
+                        vout: input.vout,
+                    },
+                    script_sig: ScriptBuf::new(),
+                    sequence,
+                    witness: Witness::new(),
+                })
+            })
+            .collect::<Result<Vec<_>, AppError>>()?,
+        output: outputs,
+    };
+    let mut psbt =
+        Psbt::from_unsigned_tx(tx).map_err(|e| AppError::Internal(format!("Failed to create PSBT: {}", e)))?;
+
+    let secp = Secp256k1::verification_only();
+    for (psbt_input, input) in psbt.inputs.iter_mut().zip(inputs) {
+        let (public_key, source) =
+            key_origin(&secp, wallet, input.script_type, &input.derivation_path, &input.script_pub_key)?;
+
+        // Legacy signers need the whole previous transaction to check the amount
+        if input.script_type == ScriptType::P2pkh {
+            let prev = node.get_wallet_transaction(&input.txid).await?;
+            psbt_input.non_witness_utxo = Some(
+                deserialize_hex(&prev.hex)
+                    .map_err(|e| AppError::Internal(format!("Invalid transaction {}: {}", input.txid, e)))?,
+            );
+        } else {
+            psbt_input.witness_utxo = Some(TxOut {
+                value: bitcoin::Amount::from_sat(input.value_sat),
+                script_pubkey: input.script_pub_key.clone(),
+            });
+        }
+
+        match input.script_type {
+            ScriptType::P2tr => {
+                let (x_only, _) = public_key.x_only_public_key();
+                psbt_input.tap_internal_key = Some(x_only);
+                psbt_input.tap_key_origins.insert(x_only, (vec![], source));
+            }
+            ScriptType::P2shP2wpkh => {
+                psbt_input.redeem_script = Some(ScriptBuf::new_p2wpkh(&CompressedPublicKey(public_key).wpubkey_hash()));
+                psbt_input.bip32_derivation.insert(public_key, source);
+            }
+            ScriptType::P2wpkh | ScriptType::P2pkh => {
+                psbt_input.bip32_derivation.insert(public_key, source);
+            }
+        }
+    }
+
+    // Key origins on the change output let hardware signers recognise it
+    if let (Some(index), Some((_, address))) = (change_index, change) {
+        let script_pub_key = ScriptBuf::from_hex(&address.script_pub_key)
+            .map_err(|e| AppError::Internal(format!("Invalid change script: {}", e)))?;
+        let (public_key, source) =
+            key_origin(&secp, wallet, address.script_type, &address.derivation_path, &script_pub_key)?;
+        let output = &mut psbt.outputs[index];
+
+        match address.script_type {
+            ScriptType::P2tr => {
+                let (x_only, _) = public_key.x_only_public_key();
+                output.tap_internal_key = Some(x_only);
+                output.tap_key_origins.insert(x_only, (vec![], source));
+            }
+            ScriptType::P2shP2wpkh => {
+                output.redeem_script = Some(ScriptBuf::new_p2wpkh(&CompressedPublicKey(public_key).wpubkey_hash()));
+                output.bip32_derivation.insert(public_key, source);
+            }
+            ScriptType::P2wpkh | ScriptType::P2pkh => {
+                output.bip32_derivation.insert(public_key, source);
+            }
+        }
+    }
+
+    Ok(psbt)
+}
+
+/// Sign a send's PSBT with the unlocked wallet seed. Either the send id or
+/// a PSBT of the send (e.g. one updated by a co-signer) identifies it.
+pub async fn sign(
+    state: &AppState,
+    wallet: &Wallet,
+    send_id: Option<&str>,
+    psbt: Option<&str>,
+) -> Result<PendingSend, AppError> {
+    wallet::ensure_can_sign(wallet)?;
+    let seed = state.wallet_keys.seed(&wallet.id).await?;
+
+    let repo = SqlitePendingSendRepository::new(state.db_pool.clone());
+    let (send, mut psbt) = resolve_psbt(&repo, send_id, psbt).await?;
+    let mut send = send.filter(|s| s.wallet_id == wallet.id).ok_or_else(|| {
+        AppError::NotFound(format!(
+            "PSBT is not a send of wallet '{}'; build it with POST /wallet/{{id}}/psbt",
+            wallet.name
+        ))
+    })?;
+    ensure_stage(&send, &[SendStage::Created, SendStage::Signed])?;
+
+    let master = Xpriv::new_master(wallet::parse_network(&wallet.network)?, &seed)
+        .map_err(|e| AppError::Internal(format!("Failed to derive master key: {}", e)))?;
+    let signed = match psbt.sign(&master, &Secp256k1::new()) {
+        Ok(signed) => signed,
+        Err((_, errors)) => {
+            let message = errors
+                .iter()
+                .map(|(index, e)| format!("input {}: {}", index, e))
+                .collect::<Vec<_>>()
+                .join("; ");
+            return Err(record_failure(&repo, &mut send, format!("Signing failed: {}", message)).await);
+        }
+    };
+
+    let signed_inputs = signed
+        .values()
+        .filter(|keys| match keys {
+            SigningKeys::Ecdsa(keys) => !keys.is_empty(),
+            SigningKeys::Schnorr(keys) => !keys.is_empty(),
+        })
+        .count();
+    if signed_inputs == 0 {
+        return Err(record_failure(
+            &repo,
+            &mut send,
+            format!("No input of the PSBT belongs to wallet '{}'", wallet.name),
+        )
+        .await);
+    }
+
+    send.psbt = encode_psbt(&psbt);
+    send.stage = SendStage::Signed;
+    send.last_error = None;
+    send.updated_at = Utc::now().timestamp();
+    repo.update(&send).await?;
+
+    println!("✍️  Signed {} inputs of PSBT {}", signed_inputs, send.unsigned_txid);
+    Ok(send)
+}
+
+/// Finalize every input of a signed PSBT and extract the transaction. The
+/// PSBT may come from anywhere; a matching send is moved to `finalized`.
+pub async fn finalize(
+    state: &AppState,
+    send_id: Option<&str>,
+    psbt: Option<&str>,
+) -> Result<FinalizedTransaction, AppError> {
+    let repo = SqlitePendingSendRepository::new(state.db_pool.clone());
+    let (mut send, mut psbt) = resolve_psbt(&repo, send_id, psbt).await?;
+    if let Some(send) = &send {
+        ensure_stage(send, &[SendStage::Created, SendStage::Signed, SendStage::Finalized])?;
+    }
+
+    let tx = match finalize_inputs(&mut psbt).and_then(|()| {
+        psbt.clone()
+            .extract_tx()
+            .map_err(|e| AppError::BadRequest(format!("Failed to extract transaction: {}", e)))
+    }) {
+        Ok(tx) => tx,
+        Err(AppError::BadRequest(message)) if send.is_some() => {
+            return Err(record_failure(&repo, send.as_mut().expect("checked above"), message).await);
+        }
+        Err(e) => return Err(e),
+    };
+
+    let finalized = FinalizedTransaction {
+        send_id: send.as_ref().map(|s| s.id.clone()),
+        txid: tx.compute_txid().to_string(),
+        tx_hex: serialize_hex(&tx),
+    };
+
+    if let Some(mut send) = send {
+        send.psbt = encode_psbt(&psbt);
+        send.stage = SendStage::Finalized;
+        send.tx_hex = Some(finalized.tx_hex.clone());
+        send.txid = Some(finalized.txid.clone());
+        send.last_error = None;
+        send.updated_at = Utc::now().timestamp();
+        repo.update(&send).await?;
+    }
+
+    Ok(finalized)
+}
+
+/// Check a final transaction with `testmempoolaccept`, then broadcast it. A
+/// rejection is reported with the mempool's reason and recorded on the send.
+pub async fn broadcast(
+    state: &AppState,
+    send_id: Option<&str>,
+    tx_hex: Option<&str>,
+) -> Result<BroadcastResult, AppError> {
+    let repo = SqlitePendingSendRepository::new(state.db_pool.clone());
+    let (mut send, tx_hex) = match (send_id, tx_hex) {
+        (Some(id), None) => {
+            let send = load(&repo, id).await?;
+            let tx_hex = send.tx_hex.clone().ok_or_else(|| {
+                AppError::Conflict(format!("Send {} has not been finalized yet", send.id))
+            })?;
+            (Some(send), tx_hex)
+        }
+        (None, Some(tx_hex)) => {
+            let tx: Transaction = deserialize_hex(tx_hex.trim())
+                .map_err(|e| AppError::BadRequest(format!("Invalid transaction: {}", e)))?;
+            let send = repo.find_by_unsigned_txid(&unsigned_txid(&tx).to_string()).await?;
+            (send, tx_hex.trim().to_string())
+        }
+        _ => return Err(AppError::BadRequest("Provide either send_id or tx_hex".to_string())),
+    };
+    if let Some(send) = &send {
+        ensure_stage(send, &[SendStage::Created, SendStage::Signed, SendStage::Finalized])?;
+    }
+
+    let rpc = state.active_rpc().await?;
+    let accept = rpc.test_mempool_accept(&tx_hex).await?;
+    let txid = if !accept.allowed {
+        // e.g. a restart between broadcasting a send and recording it
+        match already_broadcast(&rpc, send.as_ref(), &tx_hex, accept.reject_reason.as_deref()).await {
+            Some(txid) => txid,
+            None => {
+                let message = format!(
+                    "Mempool rejected the transaction: {}",
+                    accept.reject_reason.as_deref().unwrap_or("no reason given")
+                );
+                return Err(match send.as_mut() {
+                    Some(send) => record_failure(&repo, send, message).await,
+                    None => AppError::BadRequest(message),
+                });
+            }
+        }
+    } else {
+        match rpc.send_raw_transaction(&tx_hex).await {
+            Ok(txid) => {
+                println!("📡 Broadcast transaction {}", txid);
+                txid
+            }
+            Err(e) => {
+                if let Some(send) = send.as_mut() {
+                    record_failure(&repo, send, e.to_string()).await;
+                }
+                return Err(e);
+            }
+        }
+    };
+
+    if let Some(send) = send.as_mut() {
+        send.stage = SendStage::Broadcast;
+        send.tx_hex = Some(tx_hex);
+        send.txid = Some(txid.clone());
+        send.last_error = None;
+        send.updated_at = Utc::now().timestamp();
+        repo.update(send).await?;
+    }
+
+    Ok(BroadcastResult {
+        send_id: send.map(|s| s.id),
+        txid,
+    })
+}
+
+/// Txid of a transaction the node already has, in its mempool or in a block.
+/// Checked after `testmempoolaccept` rejects it: by the reason, then by looking
+/// the txid up, which finds confirmed transactions in the send's node wallet
+/// even without -txindex.
+async fn already_broadcast(
+    rpc: &BitcoinRpc,
+    send: Option<&PendingSend>,
+    tx_hex: &str,
+    reject_reason: Option<&str>,
+) -> Option<String> {
+    let txid = deserialize_hex::<Transaction>(tx_hex).ok()?.compute_txid().to_string();
+
+    if matches!(reject_reason, Some("txn-already-in-mempool" | "txn-already-known")) {
+        return Some(txid);
+    }
+    if rpc.get_raw_transaction(&txid).await.is_ok() {
+        return Some(txid);
+    }
+    let send = send?;
+    rpc.for_wallet(&node_wallets::node_wallet_name(&send.wallet_id))
+        .get_wallet_transaction(&txid)
+        .await
+        .ok()
+        .map(|_| txid)
+}
+
+/// A wallet's sends, newest first
+pub async fn list(state: &AppState, wallet: &Wallet) -> Result<Vec<PendingSend>, AppError> {
+    Ok(SqlitePendingSendRepository::new(state.db_pool.clone())
+        .list(&wallet.id)
+        .await?)
+}
+
+pub async fn get(state: &AppState, wallet: &Wallet, send_id: &str) -> Result<PendingSend, AppError> {
+    let send = load(&SqlitePendingSendRepository::new(state.db_pool.clone()), send_id).await?;
+    if send.wallet_id != wallet.id {
+        return Err(AppError::NotFound(format!("Send {} not found", send_id)));
+    }
+    Ok(send)
+}
+
+/// Give up on a send before broadcast, releasing its inputs
+pub async fn abandon(state: &AppState, wallet: &Wallet, send_id: &str) -> Result<PendingSend, AppError> {
+    let mut send = get(state, wallet, send_id).await?;
+    ensure_stage(&send, &[SendStage::Created, SendStage::Signed, SendStage::Finalized])?;
+
+    send.stage = SendStage::Abandoned;
+    send.updated_at = Utc::now().timestamp();
+    SqlitePendingSendRepository::new(state.db_pool.clone())
+        .update(&send)
+        .await?;
+    Ok(send)
+}
+
+async fn load(repo: &SqlitePendingSendRepository, send_id: &str) -> Result<PendingSend, AppError> {
+    repo.get(send_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Send {} not found", send_id)))
+}
+
+/// The PSBT to work on: the stored one of `send_id`, or the given one with
+/// the send it belongs to, if any
+async fn resolve_psbt(
+    repo: &SqlitePendingSendRepository,
+    send_id: Option<&str>,
+    psbt: Option<&str>,
+) -> Result<(Option<PendingSend>, Psbt), AppError> {
+    match (send_id, psbt) {
+        (Some(id), None) => {
+            let send = load(repo, id).await?;
+            let psbt = decode_psbt(&send.psbt)?;
+            Ok((Some(send), psbt))
+        }
+        (None, Some(psbt)) => {
+            let psbt = decode_psbt(psbt)?;
+            let send = repo
+                .find_by_unsigned_txid(&psbt.unsigned_tx.compute_txid().to_string())
+                .await?;
+            Ok((send, psbt))
+        }
+        _ => Err(AppError::BadRequest("Provide either send_id or psbt".to_string())),
+    }
+}
+
+fn ensure_stage(send: &PendingSend, allowed: &[SendStage]) -> Result<(), AppError> {
+    if allowed.contains(&send.stage) {
+        return Ok(());
+    }
+    Err(AppError::Conflict(match send.stage {
+        SendStage::Broadcast => format!(
+            "Send {} was already broadcast as {}",
+            send.id,
+            send.txid.as_deref().unwrap_or("?")
+        ),
+        stage => format!("Send {} is {}", send.id, stage.as_str()),
+    }))
+}
+
+/// Remember why a stage failed on the send and return the error to report
+async fn record_failure(repo: &SqlitePendingSendRepository, send: &mut PendingSend, message: String) -> AppError {
+    send.last_error = Some(message.clone());
+    send.updated_at = Utc::now().timestamp();
+    if let Err(e) = repo.update(send).await {
+        eprintln!("⚠️  Failed to record error of send {}: {}", send.id, e);
+    }
+    AppError::BadRequest(message)
+}
+
+/// Turn the signatures of every input into its final scriptSig and witness,
+/// then drop the signing data as BIP174 finalizers do. Supports the
+/// single-key script types the wallet derives.
+fn finalize_inputs(psbt: &mut Psbt) -> Result<(), AppError> {
+    for index in 0..psbt.inputs.len() {
+        let script_pubkey = psbt
+            .spend_utxo(index)
+            .map_err(|e| AppError::BadRequest(format!("Input {}: {}", index, e)))?
+            .script_pubkey
+            .clone();
+        let input = &mut psbt.inputs[index];
+        if input.final_script_sig.is_some() || input.final_script_witness.is_some() {
+            continue;
+        }
+        let unsigned = || AppError::BadRequest(format!("Input {} is not signed", index));
+
+        if script_pubkey.is_p2tr() {
+            let signature = input.tap_key_sig.ok_or_else(unsigned)?;
+            input.final_script_witness = Some(Witness::p2tr_key_spend(&signature));
+        } else {
+            let (public_key, signature) = input.partial_sigs.iter().next().ok_or_else(unsigned)?;
+
+            if script_pubkey.is_p2wpkh() {
+                input.final_script_witness = Some(Witness::p2wpkh(signature, &public_key.inner));
+            } else if script_pubkey.is_p2sh()
+                && let Some(redeem_script) = input.redeem_script.as_ref().filter(|s| s.is_p2wpkh())
+            {
+                let redeem_script = <&PushBytes>::try_from(redeem_script.as_bytes())
+                    .map_err(|e| AppError::BadRequest(format!("Input {}: {}", index, e)))?;
+                input.final_script_sig = Some(Builder::new().push_slice(redeem_script).into_script());
+                input.final_script_witness = Some(Witness::p2wpkh(signature, &public_key.inner));
+            } else if script_pubkey.is_p2pkh() {
+                input.final_script_sig = Some(
+                    Builder::new()
+                        .push_slice(signature.serialize())
+                        .push_key(public_key)
+                        .into_script(),
+                );
+            } else {
+                return Err(AppError::BadRequest(format!(
+                    "Input {} has a script type that cannot be finalized here",
+                    index
+                )));
+            }
+        }
+
+        input.partial_sigs = BTreeMap::new();
+        input.sighash_type = None;
+        input.redeem_script = None;
+        input.witness_script = None;
+        input.bip32_derivation = BTreeMap::new();
+        input.tap_key_sig = None;
+        input.tap_script_sigs = BTreeMap::new();
+        input.tap_scripts = BTreeMap::new();
+        input.tap_key_origins = BTreeMap::new();
+        input.tap_internal_key = None;
+        input.tap_merkle_root = None;
+    }
+    Ok(())
+}
+
+/// Public key and BIP32 origin of a wallet key, checked against the script
+/// it is expected to control. Keys of wallets without a known master
+/// fingerprint are given relative to the account key.
+fn key_origin<C: Verification>(
+    secp: &Secp256k1<C>,
+    wallet: &Wallet,
+    script_type: ScriptType,
+    derivation_path: &str,
+    script_pub_key: &ScriptBuf,
+) -> Result<(PublicKey, KeySource), AppError> {
+    let account = wallet_addresses::account(wallet, script_type)?;
+    let path = DerivationPath::from_str(derivation_path)
+        .map_err(|e| AppError::Internal(format!("Invalid derivation path {}: {}", derivation_path, e)))?;
+    let (chain, index) = match path.as_ref() {
+        [.., ChildNumber::Normal { index: chain }, ChildNumber::Normal { index }]
+            if derivation_path.starts_with(&account.derivation_path) =>
+        {
+            (*chain, *index)
+        }
+        _ => {
+            return Err(AppError::Internal(format!(
+                "{} is not below the {} account",
+                derivation_path,
+                script_type.as_str()
+            )));
+        }
+    };
+
+    let chain = AddressChain::from_index(chain)
+        .ok_or_else(|| AppError::Internal(format!("Unknown chain in {}", derivation_path)))?;
+    let network = wallet::parse_network(&wallet.network)?;
+    let derived = wallet_addresses::derive_address(secp, account, network, chain, index)?;
+    if derived.script_pub_key != script_pub_key.to_hex_string() {
+        return Err(AppError::Internal(format!(
+            "Key at {} does not control script {}",
+            derivation_path,
+            script_pub_key.to_hex_string()
+        )));
+    }
+
+    let xpub: Xpub = account
+        .xpub
+        .parse()
+        .map_err(|e| AppError::Internal(format!("Invalid account key: {}", e)))?;
+    let key = xpub
+        .derive_pub(secp, &[ChildNumber::Normal { index: chain.index() }, ChildNumber::Normal { index }])
+        .map_err(|e| AppError::Internal(format!("Failed to derive key: {}", e)))?;
+    let fingerprint = match &wallet.master_fingerprint {
+        Some(fingerprint) => Fingerprint::from_str(fingerprint)
+            .map_err(|e| AppError::Internal(format!("Invalid master fingerprint: {}", e)))?,
+        None => xpub.fingerprint(),
+    };
+
+    Ok((key.public_key, (fingerprint, path)))
+}
+
@@ -0,0 +1,97 @@
+use crate::config::constants::env_keys;
+use crate::config::env::var_or;
+use crate::domain::regtest::SimulatedReorg;
+use crate::errors::AppError;
+use crate::services::bitcoin_rpc::BitcoinRpc;
+
+/// Default name of the node wallet mining and the faucet use
+const DEFAULT_MINER_WALLET: &str = "ohla-miner";
+
+/// Refuse to continue unless the node reports `chain = regtest`.
+/// Mining, reorg simulation and clock manipulation must never reach a real network.
+pub async fn ensure_regtest(rpc: &BitcoinRpc) -> Result<(), AppError> {
+    let info = rpc.get_blockchain_info().await?;
+
+    if info.chain != "regtest" {
+        return Err(AppError::Forbidden(format!(
+            "This action only runs against a regtest node, but the node is on '{}'",
+            info.chain
+        )));
+    }
+
+    Ok(())
+}
+
+/// Load a node wallet with private keys, creating it when it does not exist yet
+pub async fn ensure_wallet(rpc: &BitcoinRpc, name: &str) -> Result<(), AppError> {
+    if rpc.list_wallets().await?.iter().any(|w| w == name) {
+        return Ok(());
+    }
+
+    match rpc.load_wallet(name).await {
+        // -35: loaded meanwhile
+        Ok(()) | Err(AppError::BitcoinRpcError { code: -35, .. }) => Ok(()),
+        // -18: wallet file not found
+        Err(AppError::BitcoinRpcError { code: -18, .. }) => rpc.create_wallet(name, false, false).await,
+        Err(e) => Err(e),
+    }
+}
+
+/// Client scoped to the miner wallet (`REGTEST_MINER_WALLET`), loaded or
+/// created on demand. Every wallet call goes through it, since other node
+/// wallets (such as the watch-only mirrors of ohla wallets) may be loaded too.
+pub async fn miner_wallet(rpc: &BitcoinRpc) -> Result<BitcoinRpc, AppError> {
+    let name: String = var_or(env_keys::REGTEST_MINER_WALLET, DEFAULT_MINER_WALLET.to_string());
+    ensure_wallet(rpc, &name).await?;
+    Ok(rpc.for_wallet(&name))
+}
+
+/// Mine blocks, paying the coinbase to `address` or to a fresh miner wallet address
+pub async fn mine_blocks(
+    rpc: &BitcoinRpc,
+    blocks: u64,
+    address: Option<String>,
+) -> Result<Vec<String>, AppError> {
+    let address = match address {
+        Some(address) => address,
+        None => miner_wallet(rpc).await?.get_new_address().await?,
+    };
+
+    rpc.generate_to_address(blocks, &address).await
+}
+
+/// Disconnect the last `depth` blocks by invalidating the block at `tip - depth + 1`.
+/// Returns the hash of the invalidated block so it can be reconsidered later.
+pub async fn rewind_blocks(rpc: &BitcoinRpc, depth: u64) -> Result<String, AppError> {
+    let height = rpc.get_block_count().await?;
+    if depth == 0 || depth > height {
+        return Err(AppError::BadRequest(format!(
+            "Reorg depth must be between 1 and the chain height ({})",
+            height
+        )));
+    }
+
+    let hash = rpc.get_block_hash(height - depth + 1).await?;
+    rpc.invalidate_block(&hash).await?;
+
+    Ok(hash)
+}
+
+/// Replace the last `depth` blocks with a longer competing branch: invalidate
+/// the old branch, mine `depth + 1` blocks, then reconsider the old branch so
+/// it shows up as a valid fork in `getchaintips`.
+pub async fn simulate_reorg(
+    rpc: &BitcoinRpc,
+    depth: u64,
+    address: Option<String>,
+) -> Result<SimulatedReorg, AppError> {
+    let invalidated_hash = rewind_blocks(rpc, depth).await?;
+    let block_hashes = mine_blocks(rpc, depth + 1, address).await?;
+    rpc.reconsider_block(&invalidated_hash).await?;
+
+    Ok(SimulatedReorg {
+        invalidated_hash,
+        depth,
+        block_hashes,
+    })
+}
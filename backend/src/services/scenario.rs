@@ -0,0 +1,315 @@
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::mpsc;
+
+use crate::domain::scenario::{Scenario, ScenarioStep, ScenarioSummary, StepResult, StepStatus};
+use crate::errors::AppError;
+use crate::services::bitcoin_rpc::BitcoinRpc;
+use crate::services::regtest;
+use crate::utils::amount::btc_to_sats;
+
+/// Upper bound on blocks mined by a single step
+const MAX_BLOCKS_PER_STEP: u64 = 1000;
+
+/// Progress reported while a scenario runs
+pub enum ScenarioEvent {
+    Step(StepResult),
+    Finished(ScenarioSummary),
+}
+
+/// Parse a YAML or JSON scenario (YAML is a superset of JSON) and check it
+/// is well formed before anything touches the node.
+pub fn parse_scenario(content: &str) -> Result<Scenario, AppError> {
+    let scenario: Scenario = serde_yaml::from_str(content)
+        .map_err(|e| AppError::BadRequest(format!("Invalid scenario: {}", e)))?;
+
+    if scenario.steps.is_empty() {
+        return Err(AppError::BadRequest("Scenario has no steps".to_string()));
+    }
+
+    for (index, step) in scenario.steps.iter().enumerate() {
+        validate_step(step).map_err(|e| {
+            AppError::BadRequest(format!("Step {} ({}): {}", index, step.action(), e))
+        })?;
+    }
+
+    Ok(scenario)
+}
+
+/// Execute a scenario step by step, stopping at the first failure.
+/// Stops early as well if the receiver goes away (client disconnected).
+pub async fn run(rpc: Arc<BitcoinRpc>, scenario: Scenario, events: mpsc::Sender<ScenarioEvent>) {
+    let started = Instant::now();
+    let mut runner = Runner {
+        rpc,
+        saved_txids: HashMap::new(),
+    };
+    let steps_total = scenario.steps.len();
+    let mut steps_run = 0;
+    let mut passed = true;
+
+    for (index, step) in scenario.steps.iter().enumerate() {
+        let step_started = Instant::now();
+        let outcome = runner.run_step(step).await;
+        steps_run += 1;
+
+        let (status, output, error) = match outcome {
+            Ok(output) => (StepStatus::Passed, output, None),
+            Err(e) => (StepStatus::Failed, serde_json::Value::Null, Some(e)),
+        };
+        let failed = error.is_some();
+
+        let result = StepResult {
+            index,
+            action: step.action(),
+            status,
+            output,
+            error,
+            duration_ms: step_started.elapsed().as_millis() as u64,
+        };
+        if events.send(ScenarioEvent::Step(result)).await.is_err() {
+            return;
+        }
+
+        if failed {
+            passed = false;
+            break;
+        }
+    }
+
+    let _ = events
+        .send(ScenarioEvent::Finished(ScenarioSummary {
+            name: scenario.name,
+            passed,
+            steps_total,
+            steps_run,
+            duration_ms: started.elapsed().as_millis() as u64,
+        }))
+        .await;
+}
+
+fn validate_step(step: &ScenarioStep) -> Result<(), String> {
+    let wallet_names: Vec<&String> = match step {
+        ScenarioStep::CreateWallet { wallet } => vec![wallet],
+        ScenarioStep::Mine { wallet, .. } | ScenarioStep::Reorg { wallet, .. } => {
+            wallet.iter().collect()
+        }
+        ScenarioStep::Send { from, to_wallet, .. } => {
+            std::iter::once(from).chain(to_wallet.iter()).collect()
+        }
+        ScenarioStep::BumpFee { wallet, .. }
+        | ScenarioStep::AssertBalance { wallet, .. }
+        | ScenarioStep::AssertConfirmations { wallet, .. } => vec![wallet],
+        ScenarioStep::SetMockTime { .. } => vec![],
+    };
+    for name in wallet_names {
+        if name.is_empty()
+            || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(format!(
+                "wallet name '{}' may only contain letters, digits, '-' and '_'",
+                name
+            ));
+        }
+    }
+
+    match step {
+        ScenarioStep::Mine { blocks, wallet, address } => {
+            if *blocks == 0 || *blocks > MAX_BLOCKS_PER_STEP {
+                return Err(format!("blocks must be between 1 and {}", MAX_BLOCKS_PER_STEP));
+            }
+            exactly_one(wallet, address, "wallet", "address")
+        }
+        ScenarioStep::Reorg { depth, wallet, address } => {
+            if *depth == 0 || *depth >= MAX_BLOCKS_PER_STEP {
+                return Err(format!("depth must be between 1 and {}", MAX_BLOCKS_PER_STEP - 1));
+            }
+            exactly_one(wallet, address, "wallet", "address")
+        }
+        ScenarioStep::Send { to_wallet, to_address, amount_sat, .. } => {
+            if *amount_sat == 0 {
+                return Err("amount_sat must be positive".to_string());
+            }
+            exactly_one(to_wallet, to_address, "to_wallet", "to_address")
+        }
+        ScenarioStep::AssertBalance { equals_sat, min_sat, max_sat, .. } => {
+            if equals_sat.is_none() && min_sat.is_none() && max_sat.is_none() {
+                return Err("provide equals_sat, min_sat or max_sat".to_string());
+            }
+            Ok(())
+        }
+        ScenarioStep::AssertConfirmations { min, max, .. } => {
+            if min.is_none() && max.is_none() {
+                return Err("provide min or max".to_string());
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn exactly_one(a: &Option<String>, b: &Option<String>, a_name: &str, b_name: &str) -> Result<(), String> {
+    match (a, b) {
+        (Some(_), None) | (None, Some(_)) => Ok(()),
+        _ => Err(format!("provide exactly one of {} or {}", a_name, b_name)),
+    }
+}
+
+struct Runner {
+    rpc: Arc<BitcoinRpc>,
+    /// Txids saved with `save_as`
+    saved_txids: HashMap<String, String>,
+}
+
+impl Runner {
+    async fn run_step(&mut self, step: &ScenarioStep) -> Result<serde_json::Value, String> {
+        match step {
+            ScenarioStep::CreateWallet { wallet } => {
+                self.ensure_wallet(wallet).await?;
+                Ok(json!({ "wallet": wallet }))
+            }
+            ScenarioStep::Mine { blocks, wallet, address } => {
+                let address = self.resolve_address(wallet, address).await?;
+                let hashes = self
+                    .rpc
+                    .generate_to_address(*blocks, &address)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                Ok(json!({ "address": address, "block_hashes": hashes }))
+            }
+            ScenarioStep::Send { from, to_wallet, to_address, amount_sat, replaceable, save_as } => {
+                self.ensure_wallet(from).await?;
+                let address = self.resolve_address(to_wallet, to_address).await?;
+                let txid = self
+                    .rpc
+                    .for_wallet(from)
+                    .send_to_address(&address, *amount_sat, *replaceable)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                self.save(save_as, &txid);
+                Ok(json!({ "txid": txid, "address": address, "amount_sat": amount_sat }))
+            }
+            ScenarioStep::BumpFee { wallet, tx, save_as } => {
+                let original = self.txid(tx);
+                let bumped = self
+                    .rpc
+                    .for_wallet(wallet)
+                    .bump_fee(&original)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                self.save(save_as, &bumped.txid);
+                Ok(json!({
+                    "original_txid": original,
+                    "replacement_txid": bumped.txid,
+                    "original_fee_sat": btc_to_sats(bumped.origfee),
+                    "fee_sat": btc_to_sats(bumped.fee),
+                }))
+            }
+            ScenarioStep::Reorg { depth, wallet, address } => {
+                let address = self.resolve_address(wallet, address).await?;
+                let reorg = regtest::simulate_reorg(&self.rpc, *depth, Some(address))
+                    .await
+                    .map_err(|e| e.to_string())?;
+                serde_json::to_value(reorg).map_err(|e| e.to_string())
+            }
+            ScenarioStep::SetMockTime { timestamp } => {
+                self.rpc
+                    .set_mock_time(*timestamp)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                Ok(json!({ "timestamp": timestamp }))
+            }
+            ScenarioStep::AssertBalance { wallet, equals_sat, min_sat, max_sat, include_pending } => {
+                let balances = self
+                    .rpc
+                    .for_wallet(wallet)
+                    .get_balances()
+                    .await
+                    .map_err(|e| e.to_string())?;
+                let mut actual = btc_to_sats(balances.mine.trusted);
+                if *include_pending {
+                    actual += btc_to_sats(balances.mine.untrusted_pending);
+                }
+
+                check_bounds("balance", actual as i64, equals_sat.map(|v| v as i64), min_sat.map(|v| v as i64), max_sat.map(|v| v as i64))?;
+                Ok(json!({ "wallet": wallet, "balance_sat": actual }))
+            }
+            ScenarioStep::AssertConfirmations { wallet, tx, min, max } => {
+                let txid = self.txid(tx);
+                let transaction = self
+                    .rpc
+                    .for_wallet(wallet)
+                    .get_wallet_transaction(&txid)
+                    .await
+                    .map_err(|e| e.to_string())?;
+
+                check_bounds("confirmations", transaction.confirmations, None, *min, *max)?;
+                Ok(json!({ "txid": transaction.txid, "confirmations": transaction.confirmations }))
+            }
+        }
+    }
+
+    /// Load a node wallet, creating it when it does not exist yet
+    async fn ensure_wallet(&self, name: &str) -> Result<(), String> {
+        regtest::ensure_wallet(&self.rpc, name)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    async fn resolve_address(&self, wallet: &Option<String>, address: &Option<String>) -> Result<String, String> {
+        match (wallet, address) {
+            (_, Some(address)) => Ok(address.clone()),
+            (Some(wallet), None) => {
+                self.ensure_wallet(wallet).await?;
+                self.rpc
+                    .for_wallet(wallet)
+                    .get_new_address()
+                    .await
+                    .map_err(|e| e.to_string())
+            }
+            (None, None) => Err("no wallet or address given".to_string()),
+        }
+    }
+
+    /// Resolve a saved name to its txid; anything else is taken as a literal txid
+    fn txid(&self, reference: &str) -> String {
+        self.saved_txids
+            .get(reference)
+            .cloned()
+            .unwrap_or_else(|| reference.to_string())
+    }
+
+    fn save(&mut self, save_as: &Option<String>, txid: &str) {
+        if let Some(name) = save_as {
+            self.saved_txids.insert(name.clone(), txid.to_string());
+        }
+    }
+}
+
+fn check_bounds(
+    what: &str,
+    actual: i64,
+    equals: Option<i64>,
+    min: Option<i64>,
+    max: Option<i64>,
+) -> Result<(), String> {
+    if let Some(expected) = equals
+        && actual != expected
+    {
+        return Err(format!("expected {} {}, got {}", what, expected, actual));
+    }
+    if let Some(min) = min
+        && actual < min
+    {
+        return Err(format!("expected {} >= {}, got {}", what, min, actual));
+    }
+    if let Some(max) = max
+        && actual > max
+    {
+        return Err(format!("expected {} <= {}, got {}", what, max, actual));
+    }
+
+    Ok(())
+}
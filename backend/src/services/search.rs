@@ -0,0 +1,200 @@
+use crate::db::{NodeConfigRepository, SqliteNodeConfigRepository};
+use crate::domain::search::{SearchResponse, SearchResult};
+use crate::errors::AppError;
+use crate::services::bitcoin_rpc::BitcoinRpc;
+use crate::state::app_state::AppState;
+
+/// Extended public key prefixes: (prefix, network, SLIP-132 script type)
+const EXTENDED_KEY_PREFIXES: [(&str, &str, Option<&str>); 10] = [
+    ("xpub", "main", None),
+    ("ypub", "main", Some("p2sh-p2wpkh")),
+    ("zpub", "main", Some("p2wpkh")),
+    ("Ypub", "main", Some("p2sh-p2wsh")),
+    ("Zpub", "main", Some("p2wsh")),
+    ("tpub", "test", None),
+    ("upub", "test", Some("p2sh-p2wpkh")),
+    ("vpub", "test", Some("p2wpkh")),
+    ("Upub", "test", Some("p2sh-p2wsh")),
+    ("Vpub", "test", Some("p2wsh")),
+];
+
+const EXTENDED_PRIVATE_KEY_PREFIXES: [&str; 10] =
+    ["xprv", "yprv", "zprv", "Yprv", "Zprv", "tprv", "uprv", "vprv", "Uprv", "Vprv"];
+
+/// Base58-encoded length of a serialized BIP32 key
+const EXTENDED_KEY_LENGTH: usize = 111;
+
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Classify a query and look it up. Node names and extended keys are
+/// resolved without the active node, so they work while it is unreachable.
+pub async fn search(state: &AppState, query: &str) -> Result<SearchResponse, AppError> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Err(AppError::BadRequest("q must not be empty".to_string()));
+    }
+
+    if let Some(node) = SqliteNodeConfigRepository::new(state.db_pool.clone())
+        .list()
+        .await?
+        .into_iter()
+        .find(|n| n.name.eq_ignore_ascii_case(query))
+    {
+        return Ok(SearchResponse {
+            query: query.to_string(),
+            path: format!("/node/{}", node.id),
+            result: SearchResult::Node {
+                id: node.id,
+                name: node.name,
+                network: node.network,
+                is_active: node.is_active,
+            },
+        });
+    }
+
+    let result = match extended_key(query)? {
+        Some(result) => result,
+        None => {
+            let rpc = state.active_rpc().await?;
+            if query.bytes().all(|b| b.is_ascii_digit()) {
+                search_height(&rpc, query).await?
+            } else if query.len() == 64 && query.bytes().all(|b| b.is_ascii_hexdigit()) {
+                search_hash(&rpc, &query.to_ascii_lowercase()).await?
+            } else if query.contains('(') {
+                search_descriptor(&rpc, query).await?
+            } else {
+                search_address(&rpc, query).await?
+            }
+        }
+    };
+
+    Ok(SearchResponse {
+        query: query.to_string(),
+        path: canonical_path(&result),
+        result,
+    })
+}
+
+async fn search_height(rpc: &BitcoinRpc, query: &str) -> Result<SearchResult, AppError> {
+    let height: u64 = query
+        .parse()
+        .map_err(|_| AppError::BadRequest(format!("'{}' is not a valid block height", query)))?;
+
+    let hash = match rpc.get_block_hash(height).await {
+        Ok(hash) => hash,
+        Err(AppError::BitcoinRpcError { code: -8, .. }) => {
+            return Err(AppError::NotFound(format!("No block at height {}", height)));
+        }
+        Err(e) => return Err(e),
+    };
+    block_result(rpc, &hash).await
+}
+
+/// 64 hex characters are either a block hash or a txid; ask the node which
+async fn search_hash(rpc: &BitcoinRpc, hash: &str) -> Result<SearchResult, AppError> {
+    match block_result(rpc, hash).await {
+        Err(AppError::BitcoinRpcError { code: -5, .. }) => {}
+        other => return other,
+    }
+
+    match rpc.get_raw_transaction(hash).await {
+        Ok(tx) => Ok(SearchResult::Transaction {
+            txid: tx.txid,
+            block_hash: tx.blockhash,
+            confirmations: tx.confirmations.unwrap_or(0),
+        }),
+        Err(AppError::BitcoinRpcError { code: -5, .. }) => Err(AppError::NotFound(format!(
+            "No block or transaction {} found (confirmed transactions require -txindex)",
+            hash
+        ))),
+        Err(e) => Err(e),
+    }
+}
+
+async fn block_result(rpc: &BitcoinRpc, hash: &str) -> Result<SearchResult, AppError> {
+    let header = rpc.get_block_header(hash).await?;
+    Ok(SearchResult::Block {
+        hash: header.hash,
+        height: header.height,
+        confirmations: header.confirmations,
+        time: header.time,
+        n_tx: header.n_tx,
+    })
+}
+
+/// Recognize serialized BIP32 keys by their prefix, length and alphabet.
+/// Private keys are rejected rather than echoed back.
+fn extended_key(query: &str) -> Result<Option<SearchResult>, AppError> {
+    if query.len() != EXTENDED_KEY_LENGTH || !query.chars().all(|c| BASE58_ALPHABET.contains(c)) {
+        return Ok(None);
+    }
+
+    if EXTENDED_PRIVATE_KEY_PREFIXES.iter().any(|p| query.starts_with(p)) {
+        return Err(AppError::BadRequest(
+            "That is an extended private key; never paste private keys into search".to_string(),
+        ));
+    }
+
+    Ok(EXTENDED_KEY_PREFIXES
+        .iter()
+        .find(|(prefix, _, _)| query.starts_with(prefix))
+        .map(|(prefix, network, script_type)| SearchResult::ExtendedKey {
+            key: query.to_string(),
+            prefix: prefix.to_string(),
+            network: network.to_string(),
+            script_type: script_type.map(str::to_string),
+        }))
+}
+
+async fn search_descriptor(rpc: &BitcoinRpc, query: &str) -> Result<SearchResult, AppError> {
+    match rpc.get_descriptor_info(query).await {
+        Ok(info) => Ok(SearchResult::Descriptor {
+            descriptor: info.descriptor,
+            checksum: info.checksum,
+            is_range: info.isrange,
+            is_solvable: info.issolvable,
+        }),
+        Err(AppError::BitcoinRpcError { message, .. }) => {
+            Err(AppError::BadRequest(format!("Invalid descriptor: {}", message)))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+async fn search_address(rpc: &BitcoinRpc, query: &str) -> Result<SearchResult, AppError> {
+    let validation = rpc.validate_address(query).await?;
+    if !validation.isvalid {
+        return Err(AppError::NotFound(format!("Nothing matches '{}'", query)));
+    }
+
+    Ok(SearchResult::Address {
+        address: query.to_string(),
+        script_pub_key: validation.script_pub_key,
+        is_script: validation.isscript,
+        witness_version: validation.witness_version,
+    })
+}
+
+fn canonical_path(result: &SearchResult) -> String {
+    match result {
+        SearchResult::Block { hash, .. } => format!("/block/{}", hash),
+        SearchResult::Transaction { txid, .. } => format!("/tx/{}", txid),
+        SearchResult::Address { address, .. } => format!("/address/{}", address),
+        SearchResult::ExtendedKey { key, .. } => format!("/xpub/{}", key),
+        SearchResult::Descriptor { descriptor, .. } => {
+            format!("/descriptor/{}", percent_encode(descriptor))
+        }
+        SearchResult::Node { id, .. } => format!("/node/{}", id),
+    }
+}
+
+/// Percent-encode everything outside the RFC 3986 unreserved set
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
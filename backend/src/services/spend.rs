@@ -0,0 +1,362 @@
+use bitcoin::secp256k1::rand::thread_rng;
+use bitcoin::{Address, Script, ScriptBuf, TxOut};
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use crate::config::constants::env_keys;
+use crate::config::env::var_or;
+use crate::db::{PendingSendRepository, SqlitePendingSendRepository};
+use crate::domain::coin_selection::{
+    CoinSelection, CoinSelectionPreview, SelectedInput, SelectionAlgorithm, SelectionOutcome,
+};
+use crate::domain::utxo::{Amount, WalletUtxo};
+use crate::domain::wallet::{ScriptType, Wallet};
+use crate::errors::AppError;
+use crate::services::coin_selection::{self, Coin, Selection, SelectionParams};
+use crate::services::{balance, wallet};
+use crate::state::app_state::AppState;
+
+/// Highest feerate accepted, Core's default -maxfeerate in sat/vB
+pub const MAX_FEERATE: f64 = 10_000.0;
+
+/// A payment to one address
+#[derive(Debug, Clone)]
+pub struct Recipient {
+    pub address: String,
+    pub amount_sat: u64,
+}
+
+/// How inputs are chosen for a spend
+#[derive(Debug, Clone, Default)]
+pub struct SpendOptions {
+    /// sat/vB
+    pub feerate: f64,
+    /// sat/vB; defaults to LONG_TERM_FEERATE
+    pub long_term_feerate: Option<f64>,
+    /// txid:vout outpoints spent whatever the algorithm picks
+    pub must_spend: Vec<String>,
+    /// Defaults to p2wpkh, or the wallet's first account without one
+    pub change_script_type: Option<ScriptType>,
+}
+
+/// A validated spend ready for coin selection
+#[derive(Debug)]
+pub struct SpendPlan {
+    /// Every unspent output of the wallet
+    pub utxos: Vec<WalletUtxo>,
+    pub pool: Vec<Coin>,
+    pub must_spend: Vec<Coin>,
+    /// Recipient outputs in request order
+    pub outputs: Vec<TxOut>,
+    pub change_script_type: ScriptType,
+    pub params: SelectionParams,
+    pub excluded_frozen: usize,
+    pub excluded_unsafe: usize,
+    pub excluded_reserved: usize,
+}
+
+impl SpendPlan {
+    /// Run one algorithm over the plan's coins
+    pub fn select(&self, algorithm: SelectionAlgorithm) -> Result<Selection, AppError> {
+        coin_selection::select(algorithm, &self.pool, &self.must_spend, &self.params, &mut thread_rng())
+    }
+
+    /// The successful selection with the lowest waste, as Core picks it
+    pub fn select_best(&self) -> Result<Selection, AppError> {
+        let mut last_error = None;
+        let mut best: Option<Selection> = None;
+        for algorithm in SelectionAlgorithm::ALL {
+            match self.select(algorithm) {
+                Ok(selection) if best.as_ref().is_none_or(|b| selection.waste_sat < b.waste_sat) => {
+                    best = Some(selection)
+                }
+                Ok(_) => {}
+                Err(e) => last_error = Some(e),
+            }
+        }
+        best.ok_or_else(|| last_error.expect("every algorithm failed"))
+    }
+
+    /// The wallet output a selected coin spends
+    pub fn utxo(&self, coin: &Coin) -> Option<&WalletUtxo> {
+        self.utxos.iter().find(|u| u.txid == coin.txid && u.vout == coin.vout)
+    }
+
+    /// A selection with amounts and effective values for display
+    pub fn describe(&self, selection: &Selection) -> CoinSelection {
+        CoinSelection {
+            algorithm: selection.algorithm,
+            inputs: selection
+                .coins
+                .iter()
+                .enumerate()
+                .map(|(i, coin)| SelectedInput {
+                    outpoint: format!("{}:{}", coin.txid, coin.vout),
+                    value: Amount::from_sat(coin.value_sat),
+                    effective_value_sat: self.params.effective_value(coin),
+                    must_spend: i < selection.must_spend,
+                })
+                .collect(),
+            input_total: Amount::from_sat(selection.input_sat),
+            fee: Amount::from_sat(selection.fee_sat),
+            change: selection.change_sat.map(Amount::from_sat),
+            vsize: selection.vsize(),
+            waste_sat: selection.waste_sat,
+        }
+    }
+}
+
+/// Validate recipients and options against the wallet and gather its coins.
+/// Frozen outputs, unconfirmed outputs from third parties and outputs spent
+/// by unfinished sends are left out of the pool; must-spend outpoints may be
+/// none of these.
+pub async fn plan(
+    state: &AppState,
+    wallet: &Wallet,
+    recipients: &[Recipient],
+    options: &SpendOptions,
+) -> Result<SpendPlan, AppError> {
+    if !(options.feerate > 0.0 && options.feerate <= MAX_FEERATE) {
+        return Err(AppError::BadRequest(format!(
+            "feerate must be above 0 and at most {} sat/vB",
+            MAX_FEERATE
+        )));
+    }
+    let long_term_feerate = options
+        .long_term_feerate
+        .unwrap_or_else(|| var_or(env_keys::LONG_TERM_FEERATE, 10.0));
+    if !(long_term_feerate > 0.0 && long_term_feerate <= MAX_FEERATE) {
+        return Err(AppError::BadRequest(format!(
+            "long_term_feerate must be above 0 and at most {} sat/vB",
+            MAX_FEERATE
+        )));
+    }
+
+    let outputs = parse_recipients(wallet, recipients)?;
+    let change_script_type = change_script_type(wallet, options.change_script_type)?;
+
+    let utxos = balance::list_utxos(state, wallet).await?;
+    let reserved: HashSet<String> = SqlitePendingSendRepository::new(state.db_pool.clone())
+        .reserved_outpoints(&wallet.id)
+        .await?
+        .into_iter()
+        .collect();
+
+    let mut wanted: HashSet<String> = HashSet::new();
+    for outpoint in &options.must_spend {
+        let outpoint = outpoint.trim().to_ascii_lowercase();
+        let utxo = utxos.iter().find(|u| u.outpoint == outpoint).ok_or_else(|| {
+            AppError::BadRequest(format!("{} is not an unspent output of wallet '{}'", outpoint, wallet.name))
+        })?;
+        if utxo.frozen {
+            return Err(AppError::BadRequest(format!("{} is frozen; unfreeze it first", outpoint)));
+        }
+        if !utxo.safe {
+            return Err(AppError::BadRequest(format!(
+                "{} is an unconfirmed output from a third party",
+                outpoint
+            )));
+        }
+        if reserved.contains(&outpoint) {
+            return Err(AppError::Conflict(format!(
+                "{} is already spent by an unfinished send; broadcast or abandon it first",
+                outpoint
+            )));
+        }
+        wanted.insert(outpoint);
+    }
+
+    let mut pool = Vec::new();
+    let mut must_spend = Vec::new();
+    let (mut excluded_frozen, mut excluded_unsafe, mut excluded_reserved) = (0, 0, 0);
+    for utxo in &utxos {
+        // Outputs we cannot size or derive a key for are not ours to spend
+        let Some(script_type) = utxo_script_type(utxo).filter(|_| utxo.derivation_path.is_some()) else {
+            continue;
+        };
+        let coin = Coin {
+            txid: utxo.txid.clone(),
+            vout: utxo.vout,
+            value_sat: utxo.value.sat,
+            input_weight: coin_selection::input_weight(script_type),
+        };
+
+        if wanted.contains(&utxo.outpoint) {
+            must_spend.push(coin);
+        } else if utxo.frozen {
+            excluded_frozen += 1;
+        } else if !utxo.safe {
+            excluded_unsafe += 1;
+        } else if reserved.contains(&utxo.outpoint) {
+            excluded_reserved += 1;
+        } else {
+            pool.push(coin);
+        }
+    }
+
+    let base_weight = coin_selection::TX_OVERHEAD_WEIGHT
+        + outputs
+            .iter()
+            .map(|output| coin_selection::output_weight(output.script_pubkey.len()))
+            .sum::<u64>();
+    let target_sat = outputs
+        .iter()
+        .try_fold(bitcoin::Amount::ZERO, |total, output| total.checked_add(output.value))
+        .filter(|total| *total <= bitcoin::Amount::MAX_MONEY)
+        .ok_or_else(|| AppError::BadRequest("Recipient amounts add up to more than 21 million BTC".to_string()))?;
+    let params = SelectionParams {
+        target_sat: target_sat.to_sat(),
+        feerate: options.feerate,
+        long_term_feerate,
+        base_weight,
+        change_output_weight: coin_selection::output_weight(coin_selection::script_pubkey_len(change_script_type)),
+        change_spend_weight: coin_selection::input_weight(change_script_type),
+        min_change_sat: coin_selection::dust_limit(change_script_type),
+    };
+
+    Ok(SpendPlan {
+        utxos,
+        pool,
+        must_spend,
+        outputs,
+        change_script_type,
+        params,
+        excluded_frozen,
+        excluded_unsafe,
+        excluded_reserved,
+    })
+}
+
+/// What every algorithm would choose for a spend, without building it
+pub async fn preview(
+    state: &AppState,
+    wallet: &Wallet,
+    recipients: &[Recipient],
+    options: &SpendOptions,
+) -> Result<CoinSelectionPreview, AppError> {
+    let plan = plan(state, wallet, recipients, options).await?;
+    let outcomes: Vec<_> = SelectionAlgorithm::ALL
+        .into_iter()
+        .map(|algorithm| (algorithm, plan.select(algorithm)))
+        .collect();
+    let recommended = outcomes
+        .iter()
+        .filter_map(|(_, result)| result.as_ref().ok())
+        .min_by_key(|s| s.waste_sat)
+        .map(|s| s.algorithm);
+
+    Ok(CoinSelectionPreview {
+        wallet_id: wallet.id.clone(),
+        feerate: plan.params.feerate,
+        long_term_feerate: plan.params.long_term_feerate,
+        target: Amount::from_sat(plan.params.target_sat),
+        available: Amount::from_sat(plan.pool.iter().chain(&plan.must_spend).map(|c| c.value_sat).sum()),
+        excluded_frozen: plan.excluded_frozen,
+        excluded_unsafe: plan.excluded_unsafe,
+        excluded_reserved: plan.excluded_reserved,
+        outcomes: outcomes
+            .into_iter()
+            .map(|(algorithm, result)| match result {
+                Ok(selection) => SelectionOutcome {
+                    algorithm,
+                    selection: Some(plan.describe(&selection)),
+                    error: None,
+                },
+                Err(e) => SelectionOutcome {
+                    algorithm,
+                    selection: None,
+                    error: Some(e.to_string()),
+                },
+            })
+            .collect(),
+        recommended,
+    })
+}
+
+/// Recipient outputs, checked against the wallet's network and the dust limit
+fn parse_recipients(wallet: &Wallet, recipients: &[Recipient]) -> Result<Vec<TxOut>, AppError> {
+    if recipients.is_empty() {
+        return Err(AppError::BadRequest("At least one recipient is required".to_string()));
+    }
+    let network = wallet::parse_network(&wallet.network)?;
+
+    recipients
+        .iter()
+        .map(|recipient| {
+            let address = Address::from_str(recipient.address.trim())
+                .map_err(|e| AppError::BadRequest(format!("Invalid address '{}': {}", recipient.address, e)))?
+                .require_network(network)
+                .map_err(|_| {
+                    AppError::BadRequest(format!(
+                        "Address '{}' is not valid on {}",
+                        recipient.address, wallet.network
+                    ))
+                })?;
+            let script = address.script_pubkey();
+
+            let amount = bitcoin::Amount::from_sat(recipient.amount_sat);
+            if amount == bitcoin::Amount::ZERO || amount > bitcoin::Amount::MAX_MONEY {
+                return Err(AppError::BadRequest(format!(
+                    "Amount to {} must be above 0 and at most 21 million BTC",
+                    recipient.address
+                )));
+            }
+            let dust = script.minimal_non_dust().to_sat();
+            if recipient.amount_sat < dust {
+                return Err(AppError::BadRequest(format!(
+                    "Amount to {} is below the dust limit of {} sat",
+                    recipient.address, dust
+                )));
+            }
+            Ok(TxOut {
+                value: amount,
+                script_pubkey: script,
+            })
+        })
+        .collect()
+}
+
+/// The requested change type, which the wallet must have an account for
+pub fn change_script_type(wallet: &Wallet, requested: Option<ScriptType>) -> Result<ScriptType, AppError> {
+    let has = |script_type: ScriptType| wallet.accounts.iter().any(|a| a.script_type == script_type);
+
+    match requested {
+        Some(script_type) if has(script_type) => Ok(script_type),
+        Some(script_type) => Err(AppError::BadRequest(format!(
+            "Wallet '{}' has no {} account for change",
+            wallet.name,
+            script_type.as_str()
+        ))),
+        None if has(ScriptType::P2wpkh) => Ok(ScriptType::P2wpkh),
+        None => wallet
+            .accounts
+            .first()
+            .map(|a| a.script_type)
+            .ok_or_else(|| AppError::BadRequest(format!("Wallet '{}' has no accounts", wallet.name))),
+    }
+}
+
+/// Script type of an output: from the derived address, or else from the
+/// shape of its scriptPubKey
+pub fn utxo_script_type(utxo: &WalletUtxo) -> Option<ScriptType> {
+    if utxo.script_type.is_some() {
+        return utxo.script_type;
+    }
+    script_type_of(&ScriptBuf::from_hex(&utxo.script_pub_key).ok()?)
+}
+
+/// Script type from the shape of a scriptPubKey. P2SH is assumed to wrap
+/// P2WPKH, the only kind this wallet derives.
+pub fn script_type_of(script: &Script) -> Option<ScriptType> {
+    if script.is_p2wpkh() {
+        Some(ScriptType::P2wpkh)
+    } else if script.is_p2tr() {
+        Some(ScriptType::P2tr)
+    } else if script.is_p2sh() {
+        Some(ScriptType::P2shP2wpkh)
+    } else if script.is_p2pkh() {
+        Some(ScriptType::P2pkh)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,223 @@
+use chrono::Utc;
+use std::collections::{BTreeSet, HashSet};
+
+use crate::db::{
+    DbError, NodeWalletRepository, SqliteNodeWalletRepository, SqliteWalletAddressRepository,
+    SqliteWalletTransactionRepository, WalletAddressRepository, WalletTransactionRepository,
+};
+use crate::domain::transaction::{TransactionFilter, TransactionPage, TxDirection, TxSyncCursor, WalletTransaction};
+use crate::domain::wallet::{AddressFilter, Wallet};
+use crate::errors::AppError;
+use crate::services::bitcoin_rpc::{BitcoinRpc, WalletTransactionDetailRaw};
+use crate::services::node_wallets;
+use crate::state::app_state::AppState;
+use crate::utils::amount::{btc_to_sats, btc_to_signed_sats};
+
+/// Bring the stored history up to date with the wallet's node wallet.
+///
+/// `listsinceblock` reports transactions after the stored cursor, including
+/// those of blocks disconnected since, so a reorg re-fetches whatever it
+/// touched. Unconfirmed transactions are re-fetched every time to pick up
+/// confirmations and replacements. After a descriptor import the whole
+/// history is reloaded, since the rescan may have found older transactions.
+pub async fn refresh(state: &AppState, wallet: &Wallet) -> Result<TxSyncCursor, AppError> {
+    let rpc = state.active_rpc().await?;
+    let node_wallet = node_wallets::node_wallet_name(&wallet.id);
+    let node = rpc.for_wallet(&node_wallet);
+    let repo = SqliteWalletTransactionRepository::new(state.db_pool.clone());
+
+    let imported_at = SqliteNodeWalletRepository::new(state.db_pool.clone())
+        .get(&wallet.id)
+        .await?
+        .and_then(|sync| sync.synced_at);
+    let since = repo
+        .cursor(&wallet.id)
+        .await?
+        .filter(|cursor| imported_at.is_none_or(|t| t < cursor.refreshed_at))
+        .map(|cursor| cursor.last_block);
+
+    let listed = match node.list_since_block(since.as_deref()).await {
+        // -5: block unknown to this node, e.g. after switching nodes
+        Err(AppError::BitcoinRpcError { code: -5, .. }) if since.is_some() => node.list_since_block(None).await,
+        result => result,
+    }
+    .map_err(|e| match e {
+        // -18: wallet not loaded
+        AppError::BitcoinRpcError { code: -18, .. } => AppError::ServiceUnavailable(format!(
+            "Node wallet '{}' is not loaded on the active node yet",
+            node_wallet
+        )),
+        e => e,
+    })?;
+
+    let mut txids: BTreeSet<String> = listed
+        .transactions
+        .into_iter()
+        .chain(listed.removed)
+        .map(|entry| entry.txid)
+        .collect();
+    txids.extend(repo.unconfirmed_txids(&wallet.id).await?);
+
+    let ours: HashSet<String> = SqliteWalletAddressRepository::new(state.db_pool.clone())
+        .list_addresses(&wallet.id, &AddressFilter::default())
+        .await?
+        .into_iter()
+        .map(|a| a.address)
+        .collect();
+
+    for txid in &txids {
+        let raw = match node.get_wallet_transaction_detail(txid).await {
+            Ok(raw) => raw,
+            // -5: no longer known to the wallet
+            Err(AppError::BitcoinRpcError { code: -5, .. }) => continue,
+            Err(e) => return Err(e),
+        };
+        let tx = transaction_from_raw(&rpc, raw, &ours).await;
+        repo.upsert(&wallet.id, &tx).await?;
+    }
+
+    let cursor = TxSyncCursor {
+        tip_height: rpc.get_block_header(&listed.lastblock).await?.height,
+        last_block: listed.lastblock,
+        refreshed_at: Utc::now().timestamp(),
+    };
+    repo.save_cursor(&wallet.id, &cursor).await?;
+
+    Ok(cursor)
+}
+
+/// A page of the wallet's history, refreshed first. When the node cannot be
+/// reached the stored history is served as of its last refresh.
+pub async fn list(
+    state: &AppState,
+    wallet: &Wallet,
+    filter: &TransactionFilter,
+    page: u32,
+    page_size: u32,
+) -> Result<TransactionPage, AppError> {
+    let repo = SqliteWalletTransactionRepository::new(state.db_pool.clone());
+
+    let cursor = match refresh(state, wallet).await {
+        Ok(cursor) => Some(cursor),
+        Err(e) => match repo.cursor(&wallet.id).await? {
+            Some(cursor) => {
+                eprintln!("⚠️  History refresh of wallet '{}' failed: {}", wallet.name, e);
+                Some(cursor)
+            }
+            None => return Err(e),
+        },
+    };
+
+    let offset = (page.saturating_sub(1) as i64) * page_size as i64;
+    let transactions = repo.list(&wallet.id, filter, offset, page_size as i64).await?;
+    let total = repo.count(&wallet.id, filter).await?;
+
+    Ok(TransactionPage {
+        transactions,
+        page,
+        page_size,
+        total,
+        synced_block: cursor.map(|c| c.last_block),
+    })
+}
+
+/// Label a stored transaction; an empty label removes it. A transaction
+/// not stored yet is looked for once more after refreshing the history.
+pub async fn set_label(state: &AppState, wallet: &Wallet, txid: &str, label: &str) -> Result<(), AppError> {
+    let label = Some(label.trim()).filter(|l| !l.is_empty());
+    let txid = txid.trim().to_ascii_lowercase();
+    let repo = SqliteWalletTransactionRepository::new(state.db_pool.clone());
+
+    let refreshed = match repo.set_label(&wallet.id, &txid, label).await {
+        Err(DbError::NotFound) => refresh(state, wallet).await,
+        result => return Ok(result?),
+    };
+    match repo.set_label(&wallet.id, &txid, label).await {
+        Err(DbError::NotFound) => Err(AppError::NotFound(match refreshed {
+            Ok(_) => format!("Transaction {} is not in the history of wallet '{}'", txid, wallet.name),
+            Err(e) => format!(
+                "Transaction {} is not in the stored history of wallet '{}', which could not be refreshed: {}",
+                txid, wallet.name, e
+            ),
+        })),
+        result => Ok(result?),
+    }
+}
+
+async fn transaction_from_raw(
+    rpc: &BitcoinRpc,
+    raw: WalletTransactionDetailRaw,
+    ours: &HashSet<String>,
+) -> WalletTransaction {
+    let fee = raw.fee.unwrap_or(0.0);
+    let fee_sat = raw.fee.map(|f| btc_to_sats(-f));
+
+    // Core leaves change out of `details`, so our derived addresses fill the gap
+    let received: HashSet<u32> = raw
+        .details
+        .iter()
+        .filter(|d| d.category != "send")
+        .map(|d| d.vout)
+        .collect();
+    let foreign: Vec<String> = raw
+        .decoded
+        .vout
+        .iter()
+        .filter(|o| !received.contains(&o.n))
+        .filter_map(|o| o.script_pub_key.address.clone())
+        .filter(|address| !ours.contains(address))
+        .collect();
+
+    let direction = match (fee_sat, foreign.is_empty()) {
+        (Some(_), true) => TxDirection::SelfTransfer,
+        (Some(_), false) => TxDirection::Outgoing,
+        (None, _) => TxDirection::Incoming,
+    };
+    let counterparties = match direction {
+        TxDirection::Outgoing => dedup(foreign),
+        TxDirection::Incoming => input_addresses(rpc, &raw.txid, raw.blockhash.as_deref()).await,
+        TxDirection::SelfTransfer => vec![],
+    };
+
+    WalletTransaction {
+        direction,
+        net_sat: btc_to_signed_sats(raw.amount + fee),
+        fee_sat,
+        confirmations: raw.confirmations.max(0) as u64,
+        block_height: raw.blockheight,
+        block_hash: raw.blockhash,
+        block_time: raw.blocktime,
+        first_seen: raw.timereceived,
+        counterparties,
+        replaceable: match raw.bip125_replaceable.as_deref() {
+            Some("yes") => Some(true),
+            Some("no") => Some(false),
+            _ => None,
+        },
+        replaced_by: raw.replaced_by_txid,
+        replaces: raw.replaces_txid,
+        conflicted: raw.confirmations < 0,
+        label: None,
+        txid: raw.txid,
+    }
+}
+
+/// Addresses the inputs of a transaction spent from. Needs Core 25+ and
+/// the block (or mempool) still holding the transaction; empty otherwise.
+async fn input_addresses(rpc: &BitcoinRpc, txid: &str, blockhash: Option<&str>) -> Vec<String> {
+    match rpc.get_transaction_inputs(txid, blockhash).await {
+        Ok(inputs) => dedup(
+            inputs
+                .vin
+                .into_iter()
+                .filter_map(|vin| vin.prevout.and_then(|p| p.script_pub_key.address))
+                .collect(),
+        ),
+        Err(_) => vec![],
+    }
+}
+
+fn dedup(addresses: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    addresses.into_iter().filter(|a| seen.insert(a.clone())).collect()
+}
@@ -0,0 +1,203 @@
+use chrono::Utc;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::domain::scan::{ScanDescriptor, ScanJob, ScanJobStatus, ScanResult, ScannedUtxo};
+use crate::errors::AppError;
+use crate::services::bitcoin_rpc::{BitcoinRpc, ScanTxOutSetResultRaw};
+use crate::utils::amount::btc_to_sats;
+
+/// Finished jobs kept in memory for polling
+const MAX_FINISHED_JOBS: usize = 50;
+
+struct TrackedScan {
+    job: ScanJob,
+    /// Node the scan runs on, used for status polling and abort
+    rpc: Arc<BitcoinRpc>,
+}
+
+/// Tracks long-running `scantxoutset` jobs.
+/// Bitcoin Core runs one scan at a time, so at most one job is running.
+#[derive(Default)]
+pub struct ScanJobManager {
+    jobs: RwLock<HashMap<String, TrackedScan>>,
+}
+
+impl ScanJobManager {
+    /// Start a scan in the background and return the tracked job immediately
+    pub async fn start(
+        self: &Arc<Self>,
+        rpc: Arc<BitcoinRpc>,
+        descriptors: Vec<ScanDescriptor>,
+    ) -> Result<ScanJob, AppError> {
+        if descriptors.is_empty() {
+            return Err(AppError::BadRequest(
+                "Provide at least one address or descriptor to scan".to_string(),
+            ));
+        }
+
+        let mut jobs = self.jobs.write().await;
+        if jobs.values().any(|t| t.job.status == ScanJobStatus::Running) {
+            return Err(AppError::Conflict(
+                "A UTXO set scan is already running; abort it or wait for it to finish".to_string(),
+            ));
+        }
+
+        let job = ScanJob {
+            id: Uuid::new_v4().to_string(),
+            status: ScanJobStatus::Running,
+            progress: Some(0.0),
+            descriptors: descriptors.clone(),
+            started_at: Utc::now().timestamp(),
+            finished_at: None,
+            result: None,
+            error: None,
+        };
+        jobs.insert(
+            job.id.clone(),
+            TrackedScan {
+                job: job.clone(),
+                rpc: rpc.clone(),
+            },
+        );
+        drop(jobs);
+
+        let scan_objects = descriptors
+            .into_iter()
+            .map(|d| match d.range {
+                Some(range) => json!({ "desc": d.desc, "range": range }),
+                None => json!(d.desc),
+            })
+            .collect();
+
+        let manager = Arc::clone(self);
+        let job_id = job.id.clone();
+        tokio::spawn(async move {
+            let outcome = rpc.scan_tx_out_set_start(scan_objects).await;
+            manager.finish(&job_id, outcome).await;
+        });
+
+        Ok(job)
+    }
+
+    /// Get a job, refreshing progress from the node while it is running
+    pub async fn get(&self, id: &str) -> Result<ScanJob, AppError> {
+        let (mut job, rpc) = {
+            let jobs = self.jobs.read().await;
+            let tracked = jobs
+                .get(id)
+                .ok_or_else(|| AppError::NotFound(format!("Scan job {} not found", id)))?;
+            (tracked.job.clone(), tracked.rpc.clone())
+        };
+
+        if job.status == ScanJobStatus::Running
+            && let Some(status) = rpc.scan_tx_out_set_status().await?
+        {
+            job.progress = Some(status.progress);
+            if let Some(tracked) = self.jobs.write().await.get_mut(id)
+                && tracked.job.status == ScanJobStatus::Running
+            {
+                tracked.job.progress = Some(status.progress);
+            }
+        }
+
+        Ok(job)
+    }
+
+    /// List all tracked jobs, newest first
+    pub async fn list(&self) -> Vec<ScanJob> {
+        let mut jobs: Vec<ScanJob> = self.jobs.read().await.values().map(|t| t.job.clone()).collect();
+        jobs.sort_by_key(|job| std::cmp::Reverse(job.started_at));
+        jobs
+    }
+
+    /// Abort a running job on its node
+    pub async fn abort(&self, id: &str) -> Result<ScanJob, AppError> {
+        let rpc = {
+            let jobs = self.jobs.read().await;
+            let tracked = jobs
+                .get(id)
+                .ok_or_else(|| AppError::NotFound(format!("Scan job {} not found", id)))?;
+            if tracked.job.status != ScanJobStatus::Running {
+                return Err(AppError::Conflict(format!("Scan job {} is not running", id)));
+            }
+            tracked.rpc.clone()
+        };
+
+        rpc.scan_tx_out_set_abort().await?;
+
+        let mut jobs = self.jobs.write().await;
+        let tracked = jobs
+            .get_mut(id)
+            .ok_or_else(|| AppError::NotFound(format!("Scan job {} not found", id)))?;
+        if tracked.job.status == ScanJobStatus::Running {
+            tracked.job.status = ScanJobStatus::Aborted;
+            tracked.job.finished_at = Some(Utc::now().timestamp());
+        }
+
+        Ok(tracked.job.clone())
+    }
+
+    async fn finish(&self, id: &str, outcome: Result<ScanTxOutSetResultRaw, AppError>) {
+        let mut jobs = self.jobs.write().await;
+
+        if let Some(tracked) = jobs.get_mut(id) {
+            let job = &mut tracked.job;
+            job.finished_at = Some(Utc::now().timestamp());
+            job.progress = None;
+
+            match outcome {
+                Ok(raw) if raw.success => {
+                    job.status = ScanJobStatus::Completed;
+                    job.progress = Some(100.0);
+                    job.result = Some(scan_result_from_raw(raw));
+                }
+                // An aborted scan reports success = false
+                Ok(_) => job.status = ScanJobStatus::Aborted,
+                Err(_) if job.status == ScanJobStatus::Aborted => {}
+                Err(e) => {
+                    job.status = ScanJobStatus::Failed;
+                    job.error = Some(e.to_string());
+                }
+            }
+        }
+
+        // Forget the oldest finished jobs
+        let mut finished: Vec<(String, i64)> = jobs
+            .values()
+            .filter(|t| t.job.status != ScanJobStatus::Running)
+            .map(|t| (t.job.id.clone(), t.job.started_at))
+            .collect();
+        if finished.len() > MAX_FINISHED_JOBS {
+            finished.sort_by_key(|(_, started_at)| *started_at);
+            for (id, _) in finished.iter().take(finished.len() - MAX_FINISHED_JOBS) {
+                jobs.remove(id);
+            }
+        }
+    }
+}
+
+fn scan_result_from_raw(raw: ScanTxOutSetResultRaw) -> ScanResult {
+    ScanResult {
+        txouts_scanned: raw.txouts,
+        height: raw.height,
+        best_block: raw.bestblock,
+        utxos: raw
+            .unspents
+            .into_iter()
+            .map(|u| ScannedUtxo {
+                txid: u.txid,
+                vout: u.vout,
+                script_pub_key: u.script_pub_key,
+                descriptor: u.desc,
+                amount_sat: btc_to_sats(u.amount),
+                height: u.height,
+                coinbase: u.coinbase,
+            })
+            .collect(),
+        total_amount_sat: btc_to_sats(raw.total_amount),
+    }
+}
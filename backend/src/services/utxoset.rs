@@ -0,0 +1,172 @@
+use chrono::Utc;
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::db::{SqliteUtxoSetRepository, UtxoSetRepository};
+use crate::domain::utxoset::{UtxoSetHashType, UtxoSetInfo, UtxoSetJob, UtxoSetSummary};
+use crate::errors::AppError;
+use crate::services::bitcoin_rpc::{BitcoinRpc, TxOutSetInfoRaw};
+use crate::utils::amount::btc_to_sats;
+
+/// Cached results checked against the active chain when the tip has none
+const RECENT_SNAPSHOTS: i64 = 20;
+
+#[derive(Default)]
+struct JobState {
+    running: Option<UtxoSetJob>,
+    last_error: Option<String>,
+}
+
+/// Runs `gettxoutsetinfo` in the background. A full UTXO set hash takes
+/// minutes on mainnet, so at most one computation runs at a time.
+#[derive(Default)]
+pub struct UtxoSetJobManager {
+    state: RwLock<JobState>,
+}
+
+impl UtxoSetJobManager {
+    /// Start computing the UTXO set summary at the node's current tip
+    pub async fn start(
+        self: &Arc<Self>,
+        rpc: Arc<BitcoinRpc>,
+        db_pool: SqlitePool,
+        hash_type: UtxoSetHashType,
+    ) -> Result<UtxoSetJob, AppError> {
+        let mut state = self.state.write().await;
+        if let Some(job) = &state.running {
+            return Err(AppError::Conflict(format!(
+                "A UTXO set computation ({}) is already running",
+                job.hash_type.as_str()
+            )));
+        }
+
+        let job = UtxoSetJob {
+            hash_type,
+            started_at: Utc::now().timestamp(),
+        };
+        state.running = Some(job.clone());
+        drop(state);
+
+        println!("🧮 Computing UTXO set summary ({})", hash_type.as_str());
+
+        let manager = Arc::clone(self);
+        tokio::spawn(async move {
+            let outcome = match rpc.get_tx_out_set_info(hash_type.as_str(), None).await {
+                Ok(raw) => {
+                    let info = info_from_raw(raw, hash_type);
+                    SqliteUtxoSetRepository::new(db_pool)
+                        .store(&info)
+                        .await
+                        .map(|_| info)
+                        .map_err(AppError::from)
+                }
+                Err(e) => Err(e),
+            };
+
+            let mut state = manager.state.write().await;
+            state.running = None;
+            match outcome {
+                Ok(info) => {
+                    println!("✅ UTXO set summary ready at height {}", info.height);
+                    state.last_error = None;
+                }
+                Err(e) => {
+                    eprintln!("⚠️  UTXO set computation failed: {}", e);
+                    state.last_error = Some(e.to_string());
+                }
+            }
+        });
+
+        Ok(job)
+    }
+
+    /// Serve the newest cached result on the active chain without waiting.
+    /// Nothing is computed here; a refresh is started with `start`.
+    pub async fn summary(
+        &self,
+        rpc: Arc<BitcoinRpc>,
+        db_pool: SqlitePool,
+        hash_type: UtxoSetHashType,
+    ) -> Result<UtxoSetSummary, AppError> {
+        let repo = SqliteUtxoSetRepository::new(db_pool.clone());
+        let info = rpc.get_blockchain_info().await?;
+
+        let mut result = repo.get(&info.bestblockhash, hash_type).await?;
+        if result.is_none() {
+            for snapshot in repo.list_recent(hash_type, RECENT_SNAPSHOTS).await? {
+                // Blocks that were reorged out report -1 confirmations
+                if let Ok(header) = rpc.get_block_header(&snapshot.best_block).await
+                    && header.confirmations >= 0
+                {
+                    result = Some(snapshot);
+                    break;
+                }
+            }
+        }
+
+        let now = Utc::now().timestamp();
+        let state = self.state.read().await;
+        Ok(UtxoSetSummary {
+            age_secs: result.as_ref().map(|r| now - r.computed_at),
+            blocks_behind: result.as_ref().map(|r| info.blocks.saturating_sub(r.height)),
+            result,
+            job: state.running.clone(),
+            last_error: state.last_error.clone(),
+        })
+    }
+}
+
+/// Look up the UTXO set summary at a past height from the node's coinstatsindex
+pub async fn at_height(
+    rpc: &BitcoinRpc,
+    repo: &impl UtxoSetRepository,
+    hash_type: UtxoSetHashType,
+    height: u64,
+) -> Result<UtxoSetInfo, AppError> {
+    if hash_type == UtxoSetHashType::HashSerialized3 {
+        return Err(AppError::BadRequest(
+            "coinstatsindex only provides muhash or none; hash_serialized_3 is available at the tip only"
+                .to_string(),
+        ));
+    }
+
+    let hash = rpc.get_block_hash(height).await?;
+    if let Some(info) = repo.get(&hash, hash_type).await? {
+        return Ok(info);
+    }
+
+    let indexes = rpc.get_index_info().await?;
+    let index = indexes.get("coinstatsindex").ok_or_else(|| {
+        AppError::ServiceUnavailable(
+            "Height lookups require the node to run with -coinstatsindex".to_string(),
+        )
+    })?;
+    if index.best_block_height < height {
+        return Err(AppError::ServiceUnavailable(format!(
+            "coinstatsindex is {} at height {}",
+            if index.synced { "synced" } else { "syncing" },
+            index.best_block_height
+        )));
+    }
+
+    let raw = rpc.get_tx_out_set_info(hash_type.as_str(), Some(height)).await?;
+    let info = info_from_raw(raw, hash_type);
+    repo.store(&info).await?;
+    Ok(info)
+}
+
+fn info_from_raw(raw: TxOutSetInfoRaw, hash_type: UtxoSetHashType) -> UtxoSetInfo {
+    UtxoSetInfo {
+        height: raw.height,
+        best_block: raw.bestblock,
+        hash_type,
+        hash: raw.hash_serialized_3.or(raw.muhash),
+        txouts: raw.txouts,
+        bogosize: raw.bogosize,
+        total_amount_sat: btc_to_sats(raw.total_amount),
+        transactions: raw.transactions,
+        disk_size: raw.disk_size,
+        computed_at: Utc::now().timestamp(),
+    }
+}
@@ -0,0 +1,334 @@
+use bip39::Mnemonic;
+use bitcoin::bip32::{DerivationPath, Xpriv, Xpub};
+use bitcoin::secp256k1::{Secp256k1, rand::RngCore, rand::thread_rng};
+use bitcoin::{Network, NetworkKind};
+use std::sync::Arc;
+use zeroize::Zeroizing;
+
+use crate::crypto::{self, EncryptedSecret};
+use crate::db::WalletRepository;
+use crate::domain::wallet::{
+    AddressChain, NewWallet, ScriptType, Wallet, WalletAccount, WalletDescriptor, WalletKind, WalletLockStatus,
+};
+use crate::errors::AppError;
+use crate::services::descriptors::{self, KeyDescriptor};
+use crate::services::wallet_keyring::WalletKeyring;
+
+/// Longest wallet name accepted
+const MAX_NAME_LENGTH: usize = 64;
+
+/// Shortest wallet password accepted
+const MIN_PASSWORD_LENGTH: usize = 8;
+
+/// Map a chain name as used by node configurations to a network
+pub fn parse_network(network: &str) -> Result<Network, AppError> {
+    Network::from_core_arg(network).map_err(|_| {
+        AppError::BadRequest(format!(
+            "Unknown network '{}'; expected main, test, testnet4, signet or regtest",
+            network
+        ))
+    })
+}
+
+/// Generate a fresh 12 or 24 word English mnemonic
+pub fn generate_mnemonic(word_count: usize) -> Result<Mnemonic, AppError> {
+    let mut entropy = match word_count {
+        12 => vec![0u8; 16],
+        24 => vec![0u8; 32],
+        _ => return Err(AppError::BadRequest("word_count must be 12 or 24".to_string())),
+    };
+    thread_rng().fill_bytes(&mut entropy);
+
+    Mnemonic::from_entropy(&entropy)
+        .map_err(|e| AppError::Internal(format!("Failed to generate mnemonic: {}", e)))
+}
+
+/// Parse a mnemonic, validating its words and checksum
+pub fn parse_mnemonic(phrase: &str) -> Result<Mnemonic, AppError> {
+    let mnemonic = Mnemonic::parse_normalized(&phrase.split_whitespace().collect::<Vec<_>>().join(" "))
+        .map_err(|e| AppError::BadRequest(format!("Invalid mnemonic: {}", e)))?;
+
+    if ![12, 15, 18, 21, 24].contains(&mnemonic.word_count()) {
+        return Err(AppError::BadRequest(
+            "Mnemonic must have 12, 15, 18, 21 or 24 words".to_string(),
+        ));
+    }
+    Ok(mnemonic)
+}
+
+/// Derive the master key and account 0 of every script type from a BIP39 seed.
+/// Returns the master fingerprint and the account-level public keys.
+pub fn derive_accounts(seed: &[u8], network: Network) -> Result<(String, Vec<WalletAccount>), AppError> {
+    let secp = Secp256k1::new();
+    let master = Xpriv::new_master(network, seed)
+        .map_err(|e| AppError::Internal(format!("Failed to derive master key: {}", e)))?;
+
+    let coin_type = if network == Network::Bitcoin { 0 } else { 1 };
+    let accounts = ScriptType::ALL
+        .iter()
+        .map(|script_type| {
+            let path: DerivationPath = format!("m/{}'/{}'/0'", script_type.purpose(), coin_type)
+                .parse()
+                .map_err(|e| AppError::Internal(format!("Invalid derivation path: {}", e)))?;
+            let account = master
+                .derive_priv(&secp, &path)
+                .map_err(|e| AppError::Internal(format!("Failed to derive account key: {}", e)))?;
+
+            Ok(WalletAccount {
+                script_type: *script_type,
+                derivation_path: format!("m/{}", path),
+                xpub: Xpub::from_priv(&secp, &account).to_string(),
+            })
+        })
+        .collect::<Result<Vec<_>, AppError>>()?;
+
+    Ok((master.fingerprint(&secp).to_string(), accounts))
+}
+
+/// Store a seed wallet derived from `mnemonic`, with the seed encrypted under `password`
+pub async fn create_seed_wallet(
+    repo: &impl WalletRepository,
+    name: &str,
+    network: &str,
+    mnemonic: &Mnemonic,
+    passphrase: &str,
+    password: &Zeroizing<String>,
+) -> Result<Wallet, AppError> {
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(AppError::BadRequest(format!(
+            "Wallet password must be at least {} characters",
+            MIN_PASSWORD_LENGTH
+        )));
+    }
+    let name = validate_name(repo, name).await?;
+
+    let seed = Zeroizing::new(mnemonic.to_seed(passphrase).to_vec());
+    let (fingerprint, accounts) = derive_accounts(&seed, parse_network(network)?)?;
+    let secret = encrypt_seed(seed, password.clone(), fingerprint.clone()).await?;
+
+    let wallet = repo
+        .create(NewWallet {
+            name,
+            network: network.to_string(),
+            kind: WalletKind::Seed,
+            master_fingerprint: Some(fingerprint),
+            accounts,
+            secret: Some(secret),
+        })
+        .await?;
+
+    println!("👛 Created wallet '{}' ({})", wallet.name, wallet.network);
+    Ok(wallet)
+}
+
+/// Public key material a watch-only wallet is created from
+pub enum WatchOnlySource {
+    /// Account-level xpub/ypub/zpub (or testnet equivalent)
+    ExtendedKey {
+        key: String,
+        /// Required when the prefix (xpub/tpub) does not imply one
+        script_type: Option<ScriptType>,
+        master_fingerprint: Option<String>,
+        derivation_path: Option<String>,
+    },
+    /// Single-key output descriptors, one or two per script type
+    Descriptors(Vec<String>),
+}
+
+/// Store a watch-only wallet; its keys never leave the node it is built from
+pub async fn create_watch_only_wallet(
+    repo: &impl WalletRepository,
+    name: &str,
+    network: &str,
+    source: WatchOnlySource,
+) -> Result<Wallet, AppError> {
+    let name = validate_name(repo, name).await?;
+    let network_kind = NetworkKind::from(parse_network(network)?);
+
+    let keys = match source {
+        WatchOnlySource::ExtendedKey {
+            key,
+            script_type,
+            master_fingerprint,
+            derivation_path,
+        } => {
+            let (xpub, implied) = descriptors::parse_extended_key(key.trim())?;
+            let script_type = match (implied, script_type) {
+                (Some(implied), Some(given)) if implied != given => {
+                    return Err(AppError::BadRequest(format!(
+                        "The key prefix implies {}, not {}",
+                        implied.as_str(),
+                        given.as_str()
+                    )));
+                }
+                (Some(script_type), _) | (None, Some(script_type)) => script_type,
+                (None, None) => {
+                    return Err(AppError::BadRequest(
+                        "script_type is required for xpub/tpub keys".to_string(),
+                    ));
+                }
+            };
+            let master_fingerprint = master_fingerprint
+                .as_deref()
+                .map(descriptors::parse_fingerprint)
+                .transpose()?;
+            let derivation_path = derivation_path
+                .as_deref()
+                .map(descriptors::normalize_path)
+                .transpose()?;
+
+            vec![KeyDescriptor {
+                script_type,
+                master_fingerprint,
+                origin_path: derivation_path,
+                xpub,
+            }]
+        }
+        WatchOnlySource::Descriptors(list) => list
+            .iter()
+            .map(|d| descriptors::parse_key_descriptor(d))
+            .collect::<Result<Vec<_>, _>>()?,
+    };
+    if keys.is_empty() {
+        return Err(AppError::BadRequest("Provide an extended public key or descriptors".to_string()));
+    }
+
+    // One account per script type; a receive/change descriptor pair shares its key
+    let mut accounts: Vec<WalletAccount> = Vec::new();
+    let master_fingerprint = keys[0].master_fingerprint.clone();
+    for key in &keys {
+        if key.xpub.network != network_kind {
+            return Err(AppError::BadRequest(format!(
+                "Key {} does not belong to network '{}'",
+                key.xpub, network
+            )));
+        }
+        if key.master_fingerprint != master_fingerprint {
+            return Err(AppError::BadRequest(
+                "All descriptors must share the same master fingerprint".to_string(),
+            ));
+        }
+
+        let xpub = key.xpub.to_string();
+        match accounts.iter().find(|a| a.script_type == key.script_type) {
+            Some(account) if account.xpub == xpub => {}
+            Some(_) => {
+                return Err(AppError::BadRequest(format!(
+                    "Descriptors use two different {} keys",
+                    key.script_type.as_str()
+                )));
+            }
+            None => accounts.push(WalletAccount {
+                script_type: key.script_type,
+                derivation_path: key.origin_path.clone().unwrap_or_else(|| "m".to_string()),
+                xpub,
+            }),
+        }
+    }
+
+    let wallet = repo
+        .create(NewWallet {
+            name,
+            network: network.to_string(),
+            kind: WalletKind::WatchOnly,
+            master_fingerprint,
+            accounts,
+            secret: None,
+        })
+        .await?;
+
+    println!("👀 Created watch-only wallet '{}' ({})", wallet.name, wallet.network);
+    Ok(wallet)
+}
+
+/// Output descriptors (with checksums) for every account chain of a wallet
+pub fn wallet_descriptors(wallet: &Wallet) -> Result<Vec<WalletDescriptor>, AppError> {
+    let mut result = Vec::with_capacity(wallet.accounts.len() * 2);
+    for account in &wallet.accounts {
+        for chain in AddressChain::ALL {
+            result.push(WalletDescriptor {
+                script_type: account.script_type,
+                chain,
+                descriptor: descriptors::account_descriptor(
+                    account,
+                    wallet.master_fingerprint.as_deref(),
+                    chain,
+                )?,
+            });
+        }
+    }
+    Ok(result)
+}
+
+/// Refuse signing operations on wallets without private keys
+pub fn ensure_can_sign(wallet: &Wallet) -> Result<(), AppError> {
+    match wallet.kind {
+        WalletKind::Seed => Ok(()),
+        WalletKind::WatchOnly => Err(AppError::Forbidden(format!(
+            "Wallet '{}' is watch-only and cannot sign",
+            wallet.name
+        ))),
+    }
+}
+
+/// Get a wallet or fail with NotFound
+pub async fn load(repo: &impl WalletRepository, wallet_id: &str) -> Result<Wallet, AppError> {
+    repo.get(wallet_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Wallet {} not found", wallet_id)))
+}
+
+/// Decrypt a wallet's seed and keep it in the keyring for `timeout_secs`
+pub async fn unlock(
+    repo: &impl WalletRepository,
+    keyring: &Arc<WalletKeyring>,
+    wallet_id: &str,
+    password: Zeroizing<String>,
+    timeout_secs: Option<u64>,
+) -> Result<WalletLockStatus, AppError> {
+    let wallet = load(repo, wallet_id).await?;
+    ensure_can_sign(&wallet)?;
+    let secret = repo.get_secret(wallet_id).await?.ok_or_else(|| {
+        AppError::BadRequest(format!("Wallet '{}' has no stored seed to unlock", wallet.name))
+    })?;
+
+    // The master fingerprint is the associated data the seed was sealed with
+    let aad = wallet.master_fingerprint.unwrap_or_default();
+    let seed = tokio::task::spawn_blocking(move || crypto::decrypt(&secret, &password, aad.as_bytes()))
+        .await
+        .map_err(|e| AppError::Internal(format!("Decryption task failed: {}", e)))??;
+
+    let unlocked_until = keyring.unlock(wallet_id, seed, timeout_secs).await;
+    println!("🔓 Wallet '{}' unlocked", wallet.name);
+
+    Ok(WalletLockStatus {
+        wallet_id: wallet_id.to_string(),
+        unlocked: true,
+        unlocked_until: Some(unlocked_until),
+    })
+}
+
+/// Argon2id is deliberately slow, so encrypt off the async runtime
+async fn encrypt_seed(
+    seed: Zeroizing<Vec<u8>>,
+    password: Zeroizing<String>,
+    fingerprint: String,
+) -> Result<EncryptedSecret, AppError> {
+    tokio::task::spawn_blocking(move || crypto::encrypt(&seed, &password, fingerprint.as_bytes()))
+        .await
+        .map_err(|e| AppError::Internal(format!("Encryption task failed: {}", e)))?
+}
+
+async fn validate_name(repo: &impl WalletRepository, name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() || name.len() > MAX_NAME_LENGTH {
+        return Err(AppError::BadRequest(format!(
+            "Wallet name must be 1 to {} characters",
+            MAX_NAME_LENGTH
+        )));
+    }
+    if repo.get_by_name(name).await?.is_some() {
+        return Err(AppError::Conflict(format!("A wallet named '{}' already exists", name)));
+    }
+    Ok(name.to_string())
+}
@@ -0,0 +1,321 @@
+use bitcoin::bip32::{ChildNumber, Xpub};
+use bitcoin::secp256k1::{Secp256k1, Verification};
+use bitcoin::{Address, CompressedPublicKey, Network};
+use sqlx::SqlitePool;
+use std::collections::{HashMap, HashSet};
+
+use crate::config::indexer::AddressIndexConfig;
+use crate::db::{
+    AddressIndexRepository, PendingSendRepository, SqliteAddressIndexRepository, SqlitePendingSendRepository,
+    SqliteWalletAddressRepository, WalletAddressRepository,
+};
+use crate::domain::wallet::{
+    AddressChain, AddressChainState, AddressFilter, ScriptType, Wallet, WalletAccount, WalletAddress,
+};
+use crate::errors::AppError;
+use crate::services::node_wallets;
+use crate::services::wallet::parse_network;
+use crate::state::app_state::AppState;
+
+/// Unused addresses kept derived past the last used one (BIP44)
+pub const DEFAULT_GAP_LIMIT: u32 = 20;
+
+/// Rounds of marking used addresses and extending the lookahead per sync
+const MAX_SYNC_ROUNDS: usize = 10;
+
+/// Derive the address at `chain/index` below an account key
+pub fn derive_address<C: Verification>(
+    secp: &Secp256k1<C>,
+    account: &WalletAccount,
+    network: Network,
+    chain: AddressChain,
+    index: u32,
+) -> Result<WalletAddress, AppError> {
+    let xpub: Xpub = account
+        .xpub
+        .parse()
+        .map_err(|e| AppError::Internal(format!("Invalid account key: {}", e)))?;
+    let path = [
+        ChildNumber::from_normal_idx(chain.index()),
+        ChildNumber::from_normal_idx(index),
+    ]
+    .into_iter()
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(|e| AppError::BadRequest(format!("Invalid address index: {}", e)))?;
+    let key = xpub
+        .derive_pub(secp, &path)
+        .map_err(|e| AppError::Internal(format!("Failed to derive address key: {}", e)))?;
+
+    let public_key = CompressedPublicKey(key.public_key);
+    let address = match account.script_type {
+        ScriptType::P2pkh => Address::p2pkh(public_key, network),
+        ScriptType::P2shP2wpkh => Address::p2shwpkh(&public_key, network),
+        ScriptType::P2wpkh => Address::p2wpkh(&public_key, network),
+        // BIP86: key path only, no script tree
+        ScriptType::P2tr => Address::p2tr(secp, key.to_x_only_pub(), None, network),
+    };
+
+    Ok(WalletAddress {
+        script_pub_key: address.script_pubkey().to_hex_string(),
+        address: address.to_string(),
+        script_type: account.script_type,
+        chain,
+        index,
+        derivation_path: format!("{}/{}/{}", account.derivation_path, chain.index(), index),
+        used: false,
+    })
+}
+
+/// Mark addresses seen on chain as used and keep `gap_limit` unused
+/// addresses derived past the last used or issued one on every chain
+pub async fn sync_addresses(state: &AppState, wallet: &Wallet) -> Result<(), AppError> {
+    let repo = SqliteWalletAddressRepository::new(state.db_pool.clone());
+    // The node wallet tracks its descriptors past our lookahead, so one call covers every round
+    let node_seen = match AddressIndexConfig::from_env().enabled {
+        true => None,
+        false => Some(node_usage(state, wallet).await),
+    };
+
+    for _ in 0..MAX_SYNC_ROUNDS {
+        for account in &wallet.accounts {
+            for chain in AddressChain::ALL {
+                ensure_lookahead(&repo, wallet, account, chain).await?;
+            }
+        }
+
+        let seen = match &node_seen {
+            Some(seen) => seen.clone(),
+            None => index_usage(&state.db_pool, &repo, &wallet.id).await?,
+        };
+        // Newly used addresses move the window, so derive and check again
+        if repo.mark_used(&wallet.id, &seen).await? == 0 {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// List a wallet's derived addresses after syncing them
+pub async fn list_addresses(
+    state: &AppState,
+    wallet: &Wallet,
+    filter: &AddressFilter,
+) -> Result<Vec<WalletAddress>, AppError> {
+    sync_addresses(state, wallet).await?;
+
+    let repo = SqliteWalletAddressRepository::new(state.db_pool.clone());
+    Ok(repo.list_addresses(&wallet.id, filter).await?)
+}
+
+/// Hand out the next address that was never issued or used. Refuses to
+/// leave more than `gap_limit` unused addresses behind the last used one.
+pub async fn next_address(
+    state: &AppState,
+    wallet: &Wallet,
+    script_type: ScriptType,
+    chain: AddressChain,
+) -> Result<WalletAddress, AppError> {
+    let account = account(wallet, script_type)?;
+    sync_addresses(state, wallet).await?;
+
+    let repo = SqliteWalletAddressRepository::new(state.db_pool.clone());
+    let Some(index) = repo.reserve_index(&wallet.id, script_type, chain).await? else {
+        let state = chain_state(&repo, &wallet.id, script_type, chain).await?;
+        return Err(AppError::Conflict(format!(
+            "{} unused {} addresses are already issued (gap limit); reuse one of them",
+            state.gap_limit,
+            script_type.as_str()
+        )));
+    };
+    ensure_lookahead(&repo, wallet, account, chain).await?;
+
+    chain_addresses(&repo, &wallet.id, script_type, chain)
+        .await?
+        .into_iter()
+        .find(|a| a.index == index)
+        .ok_or_else(|| AppError::Internal(format!("Address {} was not derived", index)))
+}
+
+/// A change address for a send being built: the lowest unused internal
+/// address that was not issued through the API and is not the change of a
+/// send still in flight. Change never leaves the wallet, so the gap limit
+/// does not apply, and the change of abandoned sends is taken again instead
+/// of moving on to a new index.
+pub async fn change_address(
+    state: &AppState,
+    wallet: &Wallet,
+    script_type: ScriptType,
+) -> Result<WalletAddress, AppError> {
+    let account = account(wallet, script_type)?;
+    sync_addresses(state, wallet).await?;
+
+    let repo = SqliteWalletAddressRepository::new(state.db_pool.clone());
+    let held: HashSet<String> = SqlitePendingSendRepository::new(state.db_pool.clone())
+        .change_addresses(&wallet.id)
+        .await?
+        .into_iter()
+        .collect();
+    let issued = chain_state(&repo, &wallet.id, script_type, AddressChain::Internal)
+        .await?
+        .issued;
+    let addresses = chain_addresses(&repo, &wallet.id, script_type, AddressChain::Internal).await?;
+
+    let derived = addresses.len() as u32;
+    if let Some(address) = addresses
+        .into_iter()
+        .find(|a| !a.used && a.index >= issued && !held.contains(&a.address))
+    {
+        return Ok(address);
+    }
+
+    // Every derived one is taken by sends in flight
+    let secp = Secp256k1::verification_only();
+    let address = derive_address(
+        &secp,
+        account,
+        parse_network(&wallet.network)?,
+        AddressChain::Internal,
+        derived.max(issued),
+    )?;
+    repo.store_addresses(&wallet.id, std::slice::from_ref(&address)).await?;
+    Ok(address)
+}
+
+/// The wallet's account for a script type
+pub fn account(wallet: &Wallet, script_type: ScriptType) -> Result<&WalletAccount, AppError> {
+    wallet
+        .accounts
+        .iter()
+        .find(|a| a.script_type == script_type)
+        .ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "Wallet '{}' has no {} account",
+                wallet.name,
+                script_type.as_str()
+            ))
+        })
+}
+
+async fn ensure_lookahead(
+    repo: &impl WalletAddressRepository,
+    wallet: &Wallet,
+    account: &WalletAccount,
+    chain: AddressChain,
+) -> Result<(), AppError> {
+    let stored = repo.chain_state(&wallet.id, account.script_type, chain).await?;
+    let state = stored.unwrap_or(AddressChainState {
+        gap_limit: DEFAULT_GAP_LIMIT,
+        issued: 0,
+    });
+    let addresses = chain_addresses(repo, &wallet.id, account.script_type, chain).await?;
+
+    let target = first_after_used(&addresses).max(state.issued) + state.gap_limit;
+    let derived = addresses.len() as u32;
+    if derived >= target {
+        return Ok(());
+    }
+
+    let secp = Secp256k1::verification_only();
+    let network = parse_network(&wallet.network)?;
+    let new_addresses = (derived..target)
+        .map(|index| derive_address(&secp, account, network, chain, index))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    repo.store_addresses(&wallet.id, &new_addresses).await?;
+    if stored.is_none() {
+        repo.create_chain_state(&wallet.id, account.script_type, chain, state).await?;
+    }
+    Ok(())
+}
+
+/// Unused addresses of the wallet whose scriptPubKey the local address index has seen
+async fn index_usage(
+    db_pool: &SqlitePool,
+    repo: &impl WalletAddressRepository,
+    wallet_id: &str,
+) -> Result<Vec<String>, AppError> {
+    let unused: HashMap<String, String> = repo
+        .list_addresses(
+            wallet_id,
+            &AddressFilter {
+                used: Some(false),
+                ..Default::default()
+            },
+        )
+        .await?
+        .into_iter()
+        .map(|a| (a.script_pub_key, a.address))
+        .collect();
+
+    let script_pub_keys: Vec<String> = unused.keys().cloned().collect();
+    let seen = SqliteAddressIndexRepository::new(db_pool.clone())
+        .filter_seen(&script_pub_keys)
+        .await?;
+
+    Ok(seen.into_iter().filter_map(|spk| unused.get(&spk).cloned()).collect())
+}
+
+/// Addresses the wallet's node wallet has received to. Usage is left as
+/// stored when the node cannot tell, so issuing addresses keeps working.
+async fn node_usage(state: &AppState, wallet: &Wallet) -> Vec<String> {
+    let received = match state.active_rpc().await {
+        Ok(rpc) => {
+            rpc.for_wallet(&node_wallets::node_wallet_name(&wallet.id))
+                .list_received_outputs()
+                .await
+        }
+        Err(e) => Err(e),
+    };
+
+    match received {
+        Ok(entries) => entries.into_iter().filter_map(|entry| entry.address).collect(),
+        Err(e) => {
+            eprintln!("⚠️  Address usage of wallet '{}' not refreshed: {}", wallet.name, e);
+            Vec::new()
+        }
+    }
+}
+
+async fn chain_state(
+    repo: &impl WalletAddressRepository,
+    wallet_id: &str,
+    script_type: ScriptType,
+    chain: AddressChain,
+) -> Result<AddressChainState, AppError> {
+    Ok(repo
+        .chain_state(wallet_id, script_type, chain)
+        .await?
+        .unwrap_or(AddressChainState {
+            gap_limit: DEFAULT_GAP_LIMIT,
+            issued: 0,
+        }))
+}
+
+async fn chain_addresses(
+    repo: &impl WalletAddressRepository,
+    wallet_id: &str,
+    script_type: ScriptType,
+    chain: AddressChain,
+) -> Result<Vec<WalletAddress>, AppError> {
+    Ok(repo
+        .list_addresses(
+            wallet_id,
+            &AddressFilter {
+                script_type: Some(script_type),
+                chain: Some(chain),
+                used: None,
+            },
+        )
+        .await?)
+}
+
+/// Index following the last used address (0 when none is used)
+fn first_after_used(addresses: &[WalletAddress]) -> u32 {
+    addresses
+        .iter()
+        .filter(|a| a.used)
+        .map(|a| a.index + 1)
+        .max()
+        .unwrap_or(0)
+}
@@ -0,0 +1,94 @@
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::sync::RwLock;
+use zeroize::Zeroizing;
+
+use crate::errors::AppError;
+
+struct UnlockedSeed {
+    /// Zeroized when the entry is dropped
+    seed: Zeroizing<Vec<u8>>,
+    expires_at: i64,
+    /// Distinguishes this unlock from later ones when its timer fires
+    generation: u64,
+}
+
+/// Decrypted wallet seeds, held in memory only while a wallet is unlocked
+pub struct WalletKeyring {
+    max_unlock_secs: u64,
+    seeds: RwLock<HashMap<String, UnlockedSeed>>,
+    generation: AtomicU64,
+}
+
+impl WalletKeyring {
+    pub fn new(max_unlock_secs: u64) -> Self {
+        Self {
+            max_unlock_secs: max_unlock_secs.max(1),
+            seeds: RwLock::new(HashMap::new()),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    /// Keep a decrypted seed for `timeout_secs` (capped at the configured maximum).
+    /// Returns the time at which the wallet locks again.
+    pub async fn unlock(
+        self: &Arc<Self>,
+        wallet_id: &str,
+        seed: Zeroizing<Vec<u8>>,
+        timeout_secs: Option<u64>,
+    ) -> i64 {
+        let timeout_secs = timeout_secs.unwrap_or(self.max_unlock_secs).clamp(1, self.max_unlock_secs);
+        let generation = self.generation.fetch_add(1, Ordering::Relaxed);
+        let expires_at = Utc::now().timestamp() + timeout_secs as i64;
+
+        self.seeds.write().await.insert(
+            wallet_id.to_string(),
+            UnlockedSeed {
+                seed,
+                expires_at,
+                generation,
+            },
+        );
+
+        let keyring = Arc::clone(self);
+        let wallet_id = wallet_id.to_string();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(timeout_secs)).await;
+            let mut seeds = keyring.seeds.write().await;
+            if seeds.get(&wallet_id).is_some_and(|s| s.generation == generation) {
+                seeds.remove(&wallet_id);
+                println!("🔒 Wallet {} locked after timeout", wallet_id);
+            }
+        });
+
+        expires_at
+    }
+
+    /// Forget a wallet's seed; returns whether it was unlocked
+    pub async fn lock(&self, wallet_id: &str) -> bool {
+        self.seeds.write().await.remove(wallet_id).is_some()
+    }
+
+    /// Forget every seed, e.g. on shutdown
+    pub async fn lock_all(&self) {
+        self.seeds.write().await.clear();
+    }
+
+    /// When the wallet locks again, if it is unlocked
+    pub async fn unlocked_until(&self, wallet_id: &str) -> Option<i64> {
+        self.seeds.read().await.get(wallet_id).map(|s| s.expires_at)
+    }
+
+    /// A copy of the decrypted seed for signing
+    pub async fn seed(&self, wallet_id: &str) -> Result<Zeroizing<Vec<u8>>, AppError> {
+        self.seeds
+            .read()
+            .await
+            .get(wallet_id)
+            .map(|s| s.seed.clone())
+            .ok_or_else(|| AppError::Forbidden("Wallet is locked; unlock it first".to_string()))
+    }
+}
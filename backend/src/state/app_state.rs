@@ -1,12 +1,36 @@
 use crate::config::bitcoin::BitcoinConfig;
+use crate::config::managed_node::ManagedNodeConfig;
+use crate::db::{NodeConfigRepository, SqliteNodeConfigRepository};
+use crate::domain::chain::ChainEvent;
+use crate::errors::AppError;
 use crate::services::bitcoin_rpc::BitcoinRpc;
+use crate::services::node_process::NodeProcessManager;
+use crate::services::node_wallets::NodeWalletManager;
+use crate::services::utxo_scan::ScanJobManager;
+use crate::services::utxoset::UtxoSetJobManager;
+use crate::services::wallet_keyring::WalletKeyring;
 use sqlx::{sqlite::{SqliteConnectOptions, SqlitePool}};
 use std::sync::Arc;
+use tokio::sync::broadcast;
 
 #[derive(Clone)]
 pub struct AppState {
     pub bitcoin: Arc<BitcoinRpc>,
     pub db_pool: SqlitePool,
+    /// Token guarding write actions; write actions are disabled when unset
+    pub admin_token: Option<String>,
+    /// Chain events (new tips, reorgs) published by the chain monitor
+    pub chain_events: broadcast::Sender<ChainEvent>,
+    /// Long-running scantxoutset jobs
+    pub scan_jobs: Arc<ScanJobManager>,
+    /// Locally spawned bitcoind for regtest/signet
+    pub node_process: Arc<NodeProcessManager>,
+    /// Background gettxoutsetinfo computations
+    pub utxoset_jobs: Arc<UtxoSetJobManager>,
+    /// Decrypted seeds of unlocked wallets
+    pub wallet_keys: Arc<WalletKeyring>,
+    /// Descriptor imports into the node wallets mirroring ours
+    pub node_wallets: Arc<NodeWalletManager>,
 }
 
 impl AppState {
@@ -25,15 +49,40 @@ impl AppState {
         let option = SqliteConnectOptions::new().filename(db_file_name).create_if_missing(true);
         let db_pool = SqlitePool::connect_with(option).await.unwrap();
 
+        let admin_token = std::env::var(crate::config::constants::env_keys::ADMIN_API_TOKEN)
+            .ok()
+            .filter(|token| !token.is_empty());
+
         // Run migrations/setup
         Self::setup_database(&db_pool).await;
 
         AppState {
             bitcoin,
             db_pool,
+            admin_token,
+            chain_events: broadcast::channel(256).0,
+            scan_jobs: Arc::new(ScanJobManager::default()),
+            node_process: Arc::new(NodeProcessManager::new(ManagedNodeConfig::from_env())),
+            utxoset_jobs: Arc::new(UtxoSetJobManager::default()),
+            wallet_keys: Arc::new(WalletKeyring::new(crate::config::env::var_or(
+                crate::config::constants::env_keys::WALLET_UNLOCK_TIMEOUT_SECS,
+                300,
+            ))),
+            node_wallets: Arc::new(NodeWalletManager::default()),
         }
     }
 
+    /// RPC client for the active node configuration, falling back to the
+    /// node configured through environment variables when none is active
+    pub async fn active_rpc(&self) -> Result<Arc<BitcoinRpc>, AppError> {
+        let repo = SqliteNodeConfigRepository::new(self.db_pool.clone());
+
+        Ok(match repo.get_active().await? {
+            Some(config) => Arc::new(BitcoinRpc::from_node_config(&config)),
+            None => self.bitcoin.clone(),
+        })
+    }
+
     async fn setup_database(pool: &SqlitePool) {
         // Create node_configs table if it doesn't exist
         sqlx::query(
@@ -53,5 +102,428 @@ impl AppState {
         .execute(pool)
         .await
         .expect("Failed to create node_configs table");
+
+        // Best-block observations recorded by the chain monitor
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS best_tip_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                node_id TEXT NOT NULL,
+                height INTEGER NOT NULL,
+                hash TEXT NOT NULL,
+                observed_at INTEGER NOT NULL
+            )
+            "#
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to create best_tip_history table");
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_best_tip_history_node ON best_tip_history (node_id, id)"
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to create best_tip_history index");
+
+        // Latest getchaintips snapshot per node
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS chain_tips (
+                node_id TEXT NOT NULL,
+                hash TEXT NOT NULL,
+                height INTEGER NOT NULL,
+                branch_len INTEGER NOT NULL,
+                status TEXT NOT NULL,
+                observed_at INTEGER NOT NULL,
+                PRIMARY KEY (node_id, hash)
+            )
+            "#
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to create chain_tips table");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS reorg_events (
+                id TEXT PRIMARY KEY,
+                node_id TEXT NOT NULL,
+                fork_height INTEGER NOT NULL,
+                fork_hash TEXT NOT NULL,
+                depth INTEGER NOT NULL,
+                old_tip_height INTEGER NOT NULL,
+                old_tip_hash TEXT NOT NULL,
+                new_tip_height INTEGER NOT NULL,
+                new_tip_hash TEXT NOT NULL,
+                orphaned_hashes TEXT NOT NULL,
+                detected_at INTEGER NOT NULL
+            )
+            "#
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to create reorg_events table");
+
+        // Local block header index for the active node
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS block_headers (
+                height INTEGER PRIMARY KEY,
+                hash TEXT NOT NULL UNIQUE,
+                previous_hash TEXT,
+                version INTEGER NOT NULL,
+                merkle_root TEXT NOT NULL,
+                time INTEGER NOT NULL,
+                median_time INTEGER NOT NULL,
+                nonce INTEGER NOT NULL,
+                bits TEXT NOT NULL,
+                difficulty REAL NOT NULL,
+                chainwork TEXT NOT NULL,
+                tx_count INTEGER NOT NULL
+            )
+            "#
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to create block_headers table");
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_block_headers_time ON block_headers (time)")
+            .execute(pool)
+            .await
+            .expect("Failed to create block_headers index");
+
+        // Optional address index: blocks walked so far and the outputs they created
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS address_index_blocks (
+                height INTEGER PRIMARY KEY,
+                hash TEXT NOT NULL
+            )
+            "#
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to create address_index_blocks table");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS address_outputs (
+                txid TEXT NOT NULL,
+                vout INTEGER NOT NULL,
+                height INTEGER NOT NULL,
+                script_pub_key TEXT NOT NULL,
+                address TEXT,
+                value_sat INTEGER NOT NULL,
+                spent_txid TEXT,
+                spent_vin INTEGER,
+                spent_height INTEGER,
+                PRIMARY KEY (txid, vout)
+            )
+            "#
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to create address_outputs table");
+
+        for statement in [
+            "CREATE INDEX IF NOT EXISTS idx_address_outputs_script_pub_key ON address_outputs (script_pub_key)",
+            "CREATE INDEX IF NOT EXISTS idx_address_outputs_height ON address_outputs (height)",
+            "CREATE INDEX IF NOT EXISTS idx_address_outputs_spent_height ON address_outputs (spent_height)",
+        ] {
+            sqlx::query(statement)
+                .execute(pool)
+                .await
+                .expect("Failed to create address_outputs index");
+        }
+
+        // Periodic node metrics recorded by the metrics sampler
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS node_metrics (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                node_id TEXT NOT NULL,
+                sampled_at INTEGER NOT NULL,
+                block_height INTEGER NOT NULL,
+                headers INTEGER NOT NULL,
+                mempool_size INTEGER NOT NULL,
+                mempool_bytes INTEGER NOT NULL,
+                peer_count INTEGER NOT NULL,
+                bytes_recv INTEGER NOT NULL,
+                bytes_sent INTEGER NOT NULL,
+                verification_progress REAL NOT NULL
+            )
+            "#
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to create node_metrics table");
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_node_metrics_node_time ON node_metrics (node_id, sampled_at)"
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to create node_metrics index");
+
+        // getblockstats results, cached per block hash
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS block_stats (
+                hash TEXT PRIMARY KEY,
+                height INTEGER NOT NULL,
+                time INTEGER NOT NULL,
+                tx_count INTEGER NOT NULL,
+                total_fee_sat INTEGER NOT NULL,
+                subsidy_sat INTEGER NOT NULL,
+                median_feerate REAL NOT NULL,
+                avg_feerate REAL NOT NULL,
+                total_size INTEGER NOT NULL,
+                total_weight INTEGER NOT NULL,
+                segwit_tx_count INTEGER NOT NULL,
+                inputs INTEGER NOT NULL,
+                segwit_inputs INTEGER NOT NULL,
+                taproot_inputs INTEGER
+            )
+            "#
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to create block_stats table");
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_block_stats_height ON block_stats (height)")
+            .execute(pool)
+            .await
+            .expect("Failed to create block_stats index");
+
+        // gettxoutsetinfo results, cached per best block and hash type
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS utxoset_snapshots (
+                best_block TEXT NOT NULL,
+                hash_type TEXT NOT NULL,
+                height INTEGER NOT NULL,
+                hash TEXT,
+                txouts INTEGER NOT NULL,
+                bogosize INTEGER NOT NULL,
+                total_amount_sat INTEGER NOT NULL,
+                transactions INTEGER,
+                disk_size INTEGER,
+                computed_at INTEGER NOT NULL,
+                PRIMARY KEY (best_block, hash_type)
+            )
+            "#
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to create utxoset_snapshots table");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS wallets (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                network TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                master_fingerprint TEXT,
+                created_at INTEGER NOT NULL
+            )
+            "#
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to create wallets table");
+
+        // Account-level extended public keys, one per script type
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS wallet_accounts (
+                wallet_id TEXT NOT NULL REFERENCES wallets(id) ON DELETE CASCADE,
+                script_type TEXT NOT NULL,
+                purpose INTEGER NOT NULL,
+                derivation_path TEXT NOT NULL,
+                xpub TEXT NOT NULL,
+                PRIMARY KEY (wallet_id, script_type)
+            )
+            "#
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to create wallet_accounts table");
+
+        // BIP39 seeds, AES-256-GCM encrypted under an Argon2id key derived from the wallet password
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS wallet_secrets (
+                wallet_id TEXT PRIMARY KEY REFERENCES wallets(id) ON DELETE CASCADE,
+                ciphertext BLOB NOT NULL,
+                nonce BLOB NOT NULL,
+                salt BLOB NOT NULL,
+                kdf_m_cost INTEGER NOT NULL,
+                kdf_t_cost INTEGER NOT NULL,
+                kdf_p_cost INTEGER NOT NULL
+            )
+            "#
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to create wallet_secrets table");
+
+        // Gap limit and issuance pointer per account chain (0 external, 1 change)
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS wallet_address_chains (
+                wallet_id TEXT NOT NULL REFERENCES wallets(id) ON DELETE CASCADE,
+                script_type TEXT NOT NULL,
+                chain INTEGER NOT NULL,
+                gap_limit INTEGER NOT NULL,
+                issued INTEGER NOT NULL,
+                PRIMARY KEY (wallet_id, script_type, chain)
+            )
+            "#
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to create wallet_address_chains table");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS wallet_addresses (
+                wallet_id TEXT NOT NULL REFERENCES wallets(id) ON DELETE CASCADE,
+                script_type TEXT NOT NULL,
+                chain INTEGER NOT NULL,
+                idx INTEGER NOT NULL,
+                address TEXT NOT NULL,
+                script_pub_key TEXT NOT NULL,
+                derivation_path TEXT NOT NULL,
+                used INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (wallet_id, script_type, chain, idx)
+            )
+            "#
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to create wallet_addresses table");
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_wallet_addresses_address ON wallet_addresses (address)")
+            .execute(pool)
+            .await
+            .expect("Failed to create wallet_addresses index");
+
+        // Descriptor imports into each wallet's watch-only node wallet
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS wallet_node_sync (
+                wallet_id TEXT PRIMARY KEY REFERENCES wallets(id) ON DELETE CASCADE,
+                node_wallet TEXT NOT NULL,
+                node_id TEXT,
+                rescan_from INTEGER NOT NULL DEFAULT 0,
+                synced_at INTEGER,
+                last_error TEXT
+            )
+            "#
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to create wallet_node_sync table");
+
+        // Frozen flags and labels set on wallet outpoints
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS wallet_utxo_meta (
+                wallet_id TEXT NOT NULL REFERENCES wallets(id) ON DELETE CASCADE,
+                txid TEXT NOT NULL,
+                vout INTEGER NOT NULL,
+                frozen INTEGER NOT NULL DEFAULT 0,
+                label TEXT,
+                PRIMARY KEY (wallet_id, txid, vout)
+            )
+            "#
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to create wallet_utxo_meta table");
+
+        // Wallet history, refreshed incrementally with listsinceblock
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS wallet_transactions (
+                wallet_id TEXT NOT NULL REFERENCES wallets(id) ON DELETE CASCADE,
+                txid TEXT NOT NULL,
+                direction TEXT NOT NULL,
+                net_sat INTEGER NOT NULL,
+                fee_sat INTEGER,
+                block_height INTEGER,
+                block_hash TEXT,
+                block_time INTEGER,
+                first_seen INTEGER NOT NULL,
+                counterparties TEXT NOT NULL,
+                replaceable INTEGER,
+                replaced_by TEXT,
+                replaces TEXT,
+                conflicted INTEGER NOT NULL DEFAULT 0,
+                label TEXT,
+                PRIMARY KEY (wallet_id, txid)
+            )
+            "#
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to create wallet_transactions table");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS wallet_tx_sync (
+                wallet_id TEXT PRIMARY KEY REFERENCES wallets(id) ON DELETE CASCADE,
+                last_block TEXT NOT NULL,
+                tip_height INTEGER NOT NULL,
+                refreshed_at INTEGER NOT NULL
+            )
+            "#
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to create wallet_tx_sync table");
+
+        // Sends in the PSBT pipeline, kept so an unfinished send can be resumed
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS pending_sends (
+                id TEXT PRIMARY KEY,
+                wallet_id TEXT NOT NULL REFERENCES wallets(id) ON DELETE CASCADE,
+                stage TEXT NOT NULL,
+                unsigned_txid TEXT NOT NULL,
+                psbt TEXT NOT NULL,
+                tx_hex TEXT,
+                txid TEXT,
+                inputs TEXT NOT NULL,
+                recipients TEXT NOT NULL,
+                change_address TEXT,
+                change_sat INTEGER,
+                fee_sat INTEGER NOT NULL,
+                feerate REAL NOT NULL,
+                vsize INTEGER NOT NULL,
+                algorithm TEXT,
+                replaces TEXT,
+                last_error TEXT,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )
+            "#
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to create pending_sends table");
+
+        // An abandoned send may be rebuilt into the same transaction
+        sqlx::query(
+            r#"
+            CREATE UNIQUE INDEX IF NOT EXISTS idx_pending_sends_unsigned_txid
+            ON pending_sends (unsigned_txid) WHERE stage != 'abandoned'
+            "#
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to create pending_sends index");
     }
 }
@@ -0,0 +1,17 @@
+/// Satoshis per bitcoin
+pub const SATS_PER_BTC: u64 = 100_000_000;
+
+/// Convert a BTC amount as reported by Bitcoin Core RPC into satoshis
+pub fn btc_to_sats(btc: f64) -> u64 {
+    (btc * SATS_PER_BTC as f64).round() as u64
+}
+
+/// Convert satoshis into the BTC amount expected by Bitcoin Core RPC
+pub fn sats_to_btc(sats: u64) -> f64 {
+    sats as f64 / SATS_PER_BTC as f64
+}
+
+/// Convert a signed BTC amount (e.g. a wallet transaction's net amount) into satoshis
+pub fn btc_to_signed_sats(btc: f64) -> i64 {
+    (btc * SATS_PER_BTC as f64).round() as i64
+}
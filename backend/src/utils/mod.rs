@@ -1 +1 @@
-
+pub mod amount;